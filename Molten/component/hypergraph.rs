@@ -47,6 +47,23 @@ pub struct Hypergraph<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
     pub past: BTreeMap<Label, BTreeSet<Label>>,
 }
 
+impl<T: Clone + Eq + Ord + Serialize + DeserializeOwned> Default for Hypergraph<T> {
+    fn default() -> Self {
+        Self {
+            meta: Meta::default(),
+            nodes: BTreeSet::new(),
+            edges: BTreeSet::new(),
+            particles: 0,
+            refractions: BTreeMap::new(),
+            world: BTreeMap::new(),
+            worlds: 0,
+            united: BTreeMap::new(),
+            future: BTreeMap::new(),
+            past: BTreeMap::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Inference {
     pub edges: BTreeSet<Label>,
@@ -10,6 +10,16 @@ pub fn info(root: &str) -> index::Index {
     index::Index::new(root, "Molten/info.html", index::Context::Molten, "info")
 }
 
+#[must_use]
+pub fn playground(root: &str) -> index::Index {
+    index::Index::new(
+        root,
+        "Molten/playground.html",
+        index::Context::Molten,
+        "playground",
+    )
+}
+
 #[must_use]
 pub fn notice(root: &str) -> index::Index {
     index::Index::new(root, "Molten/notice.html", index::Context::Molten, "notice")
@@ -0,0 +1,53 @@
+use body::Body;
+
+const EXAMPLE: &str = "[Echo(Data.Format.Unicode, Binary.Width(8), value)] (Stream.Sink.Console(value))";
+
+#[must_use]
+pub fn page(root: &str) -> page::Page {
+    navigation::layout("Playground", &index::molten::playground(root), root, |c| {
+        c.title("Playground")
+            .subtitle("Evaluate Molten in the browser")
+            .rule()
+            .paragraph(|p| {
+                p.text("Edit the module below and ")
+                    .bold("run")
+                    .text(" it to infer its particles and derivations. Evaluation happens entirely in the browser through the same ")
+                    .code("Constructor")
+                    .text(", ")
+                    .code("Index")
+                    .text(" and ")
+                    .code("Evaluate")
+                    .text(" used by forge.")
+            })
+            .division(|d| editor(d).compose(results))
+            .data(attribute::playground(), "")
+            .html(&format!(
+                "<script type=\"module\">\nimport init from '{root}Molten/resource/system/document/molten.js';\nawait init();\n</script>"
+            ))
+    })
+}
+
+fn editor(body: Body) -> Body {
+    body.tag("textarea", |t| t.text(EXAMPLE))
+        .data(attribute::source(), "")
+        .attribute("spellcheck", "false")
+        .attribute("rows", "8")
+        .label("Molten source")
+        .preformatted(|p| p)
+        .data(attribute::highlight(), "")
+        .tag("button", |b| b.text("Run"))
+        .data(attribute::run(), "")
+        .attribute("type", "button")
+}
+
+fn results(body: Body) -> Body {
+    body.chapter("Diagnostics", |s| {
+        s.division(|d| d).data(attribute::diagnostics(), "")
+    })
+    .chapter("Particles", |s| {
+        s.division(|d| d).data(attribute::particles(), "")
+    })
+    .chapter("Derivations", |s| {
+        s.division(|d| d).data(attribute::derivations(), "")
+    })
+}
@@ -0,0 +1,3 @@
+fn main() -> miette::Result<()> {
+    html::execute(|arguments| html::generate(arguments, playground::page(&arguments.root)))
+}
@@ -108,6 +108,7 @@ impl<T: Eq + Hash + Serialize> Allocatable for Valued<T> {
             value = arc.as_ref(),
             state = State::Created
         );
+        record::gauge!(channels = [arena], size = self.values.len());
 
         Ok(id)
     }
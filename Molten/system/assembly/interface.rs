@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use component::graph::symbolic::constructor::Source;
+use graph::symbolic::constructor::Constructor;
+use hypergraph::program::{Derivation, Particulate, Program};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub offset: usize,
+    pub length: usize,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub message: String,
+    pub code: Option<String>,
+    pub help: Option<String>,
+    pub spans: Vec<Span>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Response {
+    pub particles: Vec<Particulate>,
+    pub derivations: Vec<Derivation>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[must_use]
+pub fn evaluate(source: &str) -> Response {
+    let outcome = Program::compile(Source::string(source)).and_then(|mut program| {
+        program.evaluate()?;
+        Ok((program.particles()?, program.derivations()?))
+    });
+
+    match outcome {
+        Ok((particles, derivations)) => Response {
+            particles,
+            derivations,
+            diagnostics: Vec::new(),
+        },
+        Err(error) => Response {
+            diagnostics: vec![diagnostic(&error)],
+            ..Response::default()
+        },
+    }
+}
+
+#[must_use]
+pub fn highlight(source: &str) -> Option<String> {
+    let module = Source::string(source).module().ok()?;
+    molten::molten(&module).ok()
+}
+
+#[must_use]
+pub fn json(source: &str) -> String {
+    serde_json::to_string(&evaluate(source)).unwrap_or_else(|error| {
+        serde_json::json!({
+            "particles": [],
+            "derivations": [],
+            "diagnostics": [{
+                "message": format!("failed to serialize response: {error}"),
+                "code": "assembly::serialize",
+                "help": null,
+                "spans": [],
+            }],
+        })
+        .to_string()
+    })
+}
+
+#[must_use]
+pub fn diagnostic(error: &dyn miette::Diagnostic) -> Diagnostic {
+    let mut result = Diagnostic {
+        message: error.to_string(),
+        code: None,
+        help: None,
+        spans: Vec::new(),
+    };
+
+    let mut current = Some(error);
+    while let Some(level) = current {
+        if let Some(code) = level.code() {
+            result.code = Some(code.to_string());
+        }
+        if let Some(help) = level.help() {
+            result.help = Some(help.to_string());
+        }
+        if let Some(labels) = level.labels() {
+            result.spans.extend(labels.map(|span| Span {
+                offset: span.offset(),
+                length: span.len(),
+                label: span.label().map(str::to_owned),
+            }));
+        }
+        current = level.diagnostic_source();
+    }
+
+    result
+}
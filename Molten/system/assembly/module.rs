@@ -0,0 +1,19 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(start)]
+pub fn start() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(document) = window.document() else {
+        return;
+    };
+
+    playground::initialize(&document);
+}
+
+#[wasm_bindgen]
+#[must_use]
+pub fn evaluate(source: &str) -> String {
+    interface::json(source)
+}
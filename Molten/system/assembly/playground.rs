@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use interface::{Diagnostic, Response};
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element};
+
+pub fn initialize(document: &Document) {
+    let Ok(containers) = document.query_selector_all(&attribute::playground().selector()) else {
+        return;
+    };
+
+    for index in 0..containers.length() {
+        let Some(node) = containers.get(index) else {
+            continue;
+        };
+        let container: &Element = node.unchecked_ref();
+
+        if container.get_attribute(attribute::bound().name()).is_some() {
+            continue;
+        }
+        let _ = container.set_attribute(attribute::bound().name(), "");
+
+        bind(container);
+    }
+}
+
+fn find(container: &Element, selector: &str) -> Option<Element> {
+    container.query_selector(selector).ok().flatten()
+}
+
+fn bind(container: &Element) {
+    let Some(source) = find(container, &attribute::source().selector()) else {
+        return;
+    };
+
+    highlight(container, &source);
+
+    let callback = {
+        let container = container.clone();
+        let source = source.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            highlight(&container, &source);
+        })
+    };
+    let _ = source.add_event_listener_with_callback("input", callback.as_ref().unchecked_ref());
+    callback.forget();
+
+    let Some(run) = find(container, &attribute::run().selector()) else {
+        return;
+    };
+
+    let callback = {
+        let container = container.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            event.prevent_default();
+            execute(&container, &source);
+        })
+    };
+    let _ = run.add_event_listener_with_callback("click", callback.as_ref().unchecked_ref());
+    callback.forget();
+}
+
+fn value(source: &Element) -> String {
+    js_sys::Reflect::get(source, &JsValue::from_str("value"))
+        .ok()
+        .and_then(|value| value.as_string())
+        .unwrap_or_default()
+}
+
+fn highlight(container: &Element, source: &Element) {
+    let Some(target) = find(container, &attribute::highlight().selector()) else {
+        return;
+    };
+    if let Some(html) = interface::highlight(&value(source)) {
+        target.set_inner_html(&html);
+    }
+}
+
+fn execute(container: &Element, source: &Element) {
+    let text = value(source);
+    let response = interface::evaluate(&text);
+
+    if let Some(target) = find(container, &attribute::particles().selector()) {
+        target.set_inner_html(&particles(&response));
+    }
+    if let Some(target) = find(container, &attribute::derivations().selector()) {
+        target.set_inner_html(&derivations(&response));
+    }
+    if let Some(target) = find(container, &attribute::diagnostics().selector()) {
+        target.set_inner_html(&diagnostics(&text, &response.diagnostics));
+    }
+}
+
+fn particles(response: &Response) -> String {
+    let mut html = String::from("<ol>");
+    for particulate in &response.particles {
+        write!(
+            html,
+            "<li><code>#{}</code> {}</li>",
+            particulate.label,
+            escape::escape(&particulate.particle)
+        )
+        .unwrap();
+    }
+    html.push_str("</ol>");
+    html
+}
+
+fn labels(labels: &[usize]) -> String {
+    labels
+        .iter()
+        .map(|label| format!("#{label}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn derivations(response: &Response) -> String {
+    let mut html = String::from("<ol>");
+    for derivation in &response.derivations {
+        write!(
+            html,
+            "<li><code>#{}</code> {} \u{21d2} {} <span>{}</span></li>",
+            derivation.label,
+            labels(&derivation.source),
+            labels(&derivation.sink),
+            escape::escape(&derivation.rule)
+        )
+        .unwrap();
+    }
+    html.push_str("</ol>");
+    html
+}
+
+fn diagnostics(source: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut html = String::new();
+    for diagnostic in diagnostics {
+        html.push_str("<div>");
+        if let Some(code) = &diagnostic.code {
+            write!(html, "<strong>{}</strong> ", escape::escape(code)).unwrap();
+        }
+        html.push_str(&escape::escape(&diagnostic.message));
+        if !diagnostic.spans.is_empty() {
+            write!(html, "<pre>{}</pre>", marked(source, diagnostic)).unwrap();
+        }
+        if let Some(help) = &diagnostic.help {
+            write!(html, "<p>{}</p>", escape::escape(help)).unwrap();
+        }
+        html.push_str("</div>");
+    }
+    html
+}
+
+fn marked(source: &str, diagnostic: &Diagnostic) -> String {
+    let mut spans = diagnostic.spans.iter().collect::<Vec<_>>();
+    spans.sort_by_key(|span| span.offset);
+
+    let mut html = String::new();
+    let mut position = 0;
+    for span in spans {
+        let start = span.offset.max(position);
+        let end = (span.offset + span.length.max(1)).min(source.len());
+        let (Some(before), Some(inside)) = (source.get(position..start), source.get(start..end))
+        else {
+            continue;
+        };
+        html.push_str(&escape::escape(before));
+        write!(
+            html,
+            "<mark title=\"{}\">{}</mark>",
+            escape::escape(span.label.as_deref().unwrap_or_default()),
+            escape::escape(inside)
+        )
+        .unwrap();
+        position = end;
+    }
+    html.push_str(&escape::escape(source.get(position..).unwrap_or_default()));
+    html
+}
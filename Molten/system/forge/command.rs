@@ -1,7 +1,16 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 use clap::{Parser, Subcommand};
 use component::graph::index::Index;
+use component::graph::relation::Edge as Relation;
+use component::graph::state::wave::Wave;
 use component::graph::symbolic::constructor::Source;
+use component::hypergraph::Label;
 use constructor::Constructor;
+use hypergraph::debug::{Candidate, Debugger, Phase, Step};
+use hypergraph::profile;
+use hypergraph::program::Program;
 use inquire::{
     Text, set_global_render_config,
     ui::{Color, RenderConfig, StyleSheet, Styled},
@@ -9,6 +18,7 @@ use inquire::{
 use miette::{Diagnostic, Result};
 use observe::trace;
 use record::info;
+use serde::Serialize;
 use thiserror::Error;
 
 #[ctor::ctor]
@@ -40,6 +50,28 @@ pub enum Error {
     )]
     Evaluate,
 
+    #[error("Unknown label `{label}`")]
+    #[diagnostic(
+        code(forge::label),
+        help("labels are the numbers printed by :worlds and :show")
+    )]
+    Label { label: String },
+
+    #[error("Missing argument for `{command}`")]
+    #[diagnostic(code(forge::usage), help("use {command} {placeholder}"))]
+    Usage {
+        command: String,
+        placeholder: &'static str,
+    },
+
+    #[error("Unable to write profile to {path}")]
+    #[diagnostic(code(forge::profile), help("check that the output path is writable"))]
+    Profile {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error(transparent)]
     #[diagnostic(transparent)]
     Arena(#[from] arena::error::Error),
@@ -65,17 +97,49 @@ enum Command {
         #[command(flatten)]
         observation: observation::argument::Argument,
     },
+
+    #[command(about = "Evaluate a Molten module to its fixed point")]
+    Run {
+        #[arg(help = "Molten module to evaluate")]
+        module: PathBuf,
+
+        #[arg(long, help = "Report per-rule evaluation cost")]
+        profile: bool,
+
+        #[arg(long, requires = "profile", help = "Write the profile as JSON")]
+        output: Option<PathBuf>,
+
+        #[command(flatten)]
+        observation: observation::argument::Argument,
+    },
+
+    #[command(about = "Step through rule application for a Molten module")]
+    Debug {
+        #[arg(help = "Molten module to evaluate")]
+        module: PathBuf,
+
+        #[command(flatten)]
+        observation: observation::argument::Argument,
+    },
 }
 
 fn main() -> Result<()> {
     command::execute(
         |arguments: &Arguments| {
-            let Command::Lava { ref observation } = arguments.command;
+            let (Command::Lava { observation }
+            | Command::Run { observation, .. }
+            | Command::Debug { observation, .. }) = &arguments.command;
             observation::initialize(&observation.sink)
         },
-        |arguments, _runtime| {
-            let Command::Lava { .. } = arguments.command;
-            lava()
+        |arguments, _runtime| match arguments.command {
+            Command::Lava { .. } => lava(),
+            Command::Run {
+                ref module,
+                profile,
+                ref output,
+                ..
+            } => run(module, profile, output.as_deref()),
+            Command::Debug { ref module, .. } => debug(module),
         },
     )
 }
@@ -120,3 +184,358 @@ fn lava() -> Result<()> {
 
     Ok(())
 }
+
+struct Cost {
+    rule: String,
+    matchings: usize,
+    absorbs: usize,
+    created: usize,
+    existing: usize,
+    elapsed: Duration,
+}
+
+#[derive(Serialize)]
+struct Report {
+    source: Origin,
+    functions: Vec<Function>,
+}
+
+#[derive(Serialize)]
+struct Origin {
+    template: PathBuf,
+    cases: PathBuf,
+    specification: PathBuf,
+}
+
+#[derive(Serialize)]
+struct Function {
+    name: String,
+    tags: Vec<String>,
+    expression: String,
+    dimension: Vec<String>,
+    observation: Observation,
+}
+
+#[derive(Serialize)]
+struct Observation {
+    time: Timed,
+}
+
+#[derive(Serialize)]
+struct Timed {
+    unit: String,
+    sample: Vec<Entry>,
+}
+
+#[derive(Serialize)]
+struct Entry {
+    point: Vec<f64>,
+    mean: f64,
+    deviation: f64,
+    interval: [f64; 2],
+    count: usize,
+}
+
+impl Report {
+    #[expect(clippy::cast_precision_loss)]
+    fn from(module: &Path, costs: &[Cost]) -> Self {
+        let functions = costs
+            .iter()
+            .map(|cost| {
+                let seconds = cost.elapsed.as_secs_f64();
+                Function {
+                    name: cost.rule.clone(),
+                    tags: vec!["profile".to_string()],
+                    expression: "profile".to_string(),
+                    dimension: ["matchings", "absorbs", "created", "existing"]
+                        .map(String::from)
+                        .to_vec(),
+                    observation: Observation {
+                        time: Timed {
+                            unit: "second".to_string(),
+                            sample: vec![Entry {
+                                point: [cost.matchings, cost.absorbs, cost.created, cost.existing]
+                                    .map(|value| value as f64)
+                                    .to_vec(),
+                                mean: seconds,
+                                deviation: 0.0,
+                                interval: [seconds, seconds],
+                                count: 1,
+                            }],
+                        },
+                    },
+                }
+            })
+            .collect();
+
+        // A profile measures one module, so it has no cases or specification to point at.
+        Self {
+            source: Origin {
+                template: module.to_path_buf(),
+                cases: PathBuf::new(),
+                specification: PathBuf::new(),
+            },
+            functions,
+        }
+    }
+}
+
+#[trace(channels = [core])]
+fn run(module: &Path, profiling: bool, output: Option<&Path>) -> Result<()> {
+    info!("Forge::Run");
+
+    let mut program = Program::path(module)?;
+
+    if !profiling {
+        program.evaluate()?;
+        print!("{}", program.text()?);
+        return Ok(());
+    }
+
+    let profile = profile::profile(&mut program.graph, program.index.relations.clone())?;
+    print!("{}", program.text()?);
+
+    let costs = profile
+        .sorted()
+        .into_iter()
+        .map(|(relation, cost)| {
+            Ok(Cost {
+                rule: program.relation(relation)?,
+                matchings: cost.matchings,
+                absorbs: cost.absorbs,
+                created: cost.created,
+                existing: cost.existing,
+                elapsed: cost.elapsed,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    println!();
+    println!(
+        "{:>12} {:>10} {:>8} {:>8} {:>9}  rule",
+        "time (µs)", "matchings", "absorbs", "new", "existing"
+    );
+    for cost in &costs {
+        println!(
+            "{:>12} {:>10} {:>8} {:>8} {:>9}  {}",
+            cost.elapsed.as_micros(),
+            cost.matchings,
+            cost.absorbs,
+            cost.created,
+            cost.existing,
+            cost.rule
+        );
+    }
+    println!(
+        "{} rules, {} edges, {} iterations",
+        costs.len(),
+        profile.inference.edges.len(),
+        profile.iterations
+    );
+
+    if let Some(path) = output {
+        serde_json::to_string_pretty(&Report::from(module, &costs))
+            .map_err(std::io::Error::from)
+            .and_then(|json| std::fs::write(path, json))
+            .map_err(|source| Error::Profile {
+                path: path.display().to_string(),
+                source,
+            })?;
+    }
+
+    Ok(())
+}
+
+#[trace(channels = [core])]
+fn debug(module: &Path) -> Result<()> {
+    info!("Forge::Debug");
+
+    let mut program = Program::path(module)?;
+    let mut debugger = Debugger::new(program.index.relations.clone());
+    let mut breakpoints = Vec::<String>::new();
+
+    pending(&program, &mut debugger)?;
+
+    loop {
+        let source = Text::new(">").with_help_message(
+            ":break <rule> · :step · :continue · :show <label> · :worlds · :united <label>",
+        );
+
+        let Ok(input) = source.prompt() else {
+            break;
+        };
+
+        if let Err(error) = command(input.trim(), &mut program, &mut debugger, &mut breakpoints) {
+            eprintln!("{error:?}");
+        }
+    }
+
+    Ok(())
+}
+
+fn command(
+    input: &str,
+    program: &mut Program,
+    debugger: &mut Debugger<usize>,
+    breakpoints: &mut Vec<String>,
+) -> Result<()> {
+    let (command, argument) = input.split_once(' ').unwrap_or((input, ""));
+    let argument = argument.trim();
+
+    match command {
+        "" => {}
+        ":break" => {
+            let argument = required(command, argument, "<rule>")?;
+            breakpoints.push(argument.to_string());
+            println!("breakpoint {}: {argument}", breakpoints.len());
+        }
+        ":step" => {
+            if let Some(step) = debugger.step(&mut program.graph)? {
+                report(&step);
+            }
+            pending(program, debugger)?;
+        }
+        ":continue" => {
+            let halting = halting(program, breakpoints)?;
+            for step in debugger.resume(&mut program.graph, |candidate| {
+                halting.contains(&candidate.relation)
+            })? {
+                report(&step);
+            }
+            pending(program, debugger)?;
+        }
+        ":show" => show(program, label(required(command, argument, "<label>")?)?)?,
+        ":worlds" => {
+            for (label, world) in &program.graph.world {
+                println!("#{} world {world}", label.0);
+            }
+        }
+        ":united" => {
+            let label = label(required(command, argument, "<label>")?)?;
+            let class = program
+                .graph
+                .united
+                .values()
+                .find(|members| members.contains(&label));
+            match class {
+                Some(members) => println!("{}", labels(members.iter())),
+                None => println!("#{} is not united", label.0),
+            }
+        }
+        _ => eprintln!("unknown command `{input}`"),
+    }
+
+    Ok(())
+}
+
+fn required<'a>(command: &str, argument: &'a str, placeholder: &'static str) -> Result<&'a str> {
+    if argument.is_empty() {
+        return Err(Error::Usage {
+            command: command.to_string(),
+            placeholder,
+        }
+        .into());
+    }
+    Ok(argument)
+}
+
+fn label(argument: &str) -> Result<Label> {
+    argument
+        .trim_start_matches('#')
+        .parse()
+        .map(Label)
+        .map_err(|_| {
+            Error::Label {
+                label: argument.to_string(),
+            }
+            .into()
+        })
+}
+
+fn labels<'a>(labels: impl Iterator<Item = &'a Label>) -> String {
+    labels
+        .map(|label| format!("#{}", label.0))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn rule(program: &Program, candidate: &Candidate<usize>) -> Result<String> {
+    Ok(program.relation(&candidate.relation)?)
+}
+
+fn halting(program: &Program, breakpoints: &[String]) -> Result<Vec<Relation<Wave<usize>>>> {
+    let mut halting = Vec::new();
+    for (source, sinks) in program.index.relations.clone() {
+        for sink in sinks {
+            let relation = Relation {
+                source: source.clone(),
+                sink,
+            };
+            let rule = program.relation(&relation)?;
+            if breakpoints.iter().any(|pattern| rule.contains(pattern)) {
+                halting.push(relation);
+            }
+        }
+    }
+    Ok(halting)
+}
+
+fn report(step: &Step<usize>) {
+    match (step.candidate.independent, step.edges.is_empty()) {
+        (false, _) => println!("skipped: matching is not independent"),
+        (true, true) => println!("absorbed: no new edges"),
+        (true, false) => println!("absorbed: {}", labels(step.edges.iter())),
+    }
+}
+
+fn pending(program: &Program, debugger: &mut Debugger<usize>) -> Result<()> {
+    let Some(candidate) = debugger.peek(&program.graph)? else {
+        println!("fixed point after {} iterations", debugger.iterations);
+        return Ok(());
+    };
+
+    let phase = match candidate.phase {
+        Phase::Direct => "direct",
+        Phase::Ancestral => "ancestral",
+    };
+    println!("rule        {} [{phase}]", rule(program, candidate)?);
+
+    let worlds = candidate
+        .combination
+        .iter()
+        .map(|label| match program.graph.world.get(label) {
+            Some(world) => format!("#{} world {world}", label.0),
+            None => format!("#{} world ?", label.0),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("matching    {worlds}");
+
+    let independent = if candidate.independent { "✓" } else { "✗" };
+    println!("independent {independent}");
+
+    Ok(())
+}
+
+fn show(program: &Program, label: Label) -> Result<()> {
+    if let Some(node) = program.graph.nodes.iter().find(|node| node.label == label) {
+        println!("#{}: {}", label.0, program.render(&node.particle)?);
+        return Ok(());
+    }
+
+    if let Some(edge) = program.graph.edges.iter().find(|edge| edge.label == label) {
+        println!(
+            "#{}: {} ⇒ {} via {}",
+            label.0,
+            labels(edge.inference.source.iter()),
+            labels(edge.inference.sink.iter()),
+            program.relation(&edge.relation)?
+        );
+        return Ok(());
+    }
+
+    Err(Error::Label {
+        label: label.0.to_string(),
+    }
+    .into())
+}
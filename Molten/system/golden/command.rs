@@ -0,0 +1,259 @@
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use difference::{Divergence, Segment};
+use hypergraph::program::{self, Particulate, Program};
+use miette::{Diagnostic, Result};
+use observe::trace;
+use record::info;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum Error {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Program(#[from] program::error::Error),
+
+    #[error("failed to read expectation {path}")]
+    #[diagnostic(
+        code(golden::read),
+        help("record the expectation by running the `.update` target")
+    )]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to write expectation {path}")]
+    #[diagnostic(code(golden::write), help("check that the workspace is writable"))]
+    Write {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("malformed expectation {path}")]
+    #[diagnostic(
+        code(golden::format),
+        help("expectations are either canonical particle text or a JSON array of particles")
+    )]
+    Format {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to serialize the evaluated particles")]
+    #[diagnostic(
+        code(golden::serialize),
+        help("particles are plain label and text pairs; this indicates a bug in the runner")
+    )]
+    Serialize(#[source] serde_json::Error),
+
+    #[error("{count} of {total} modules diverged from their expectations")]
+    #[diagnostic(
+        code(golden::mismatch),
+        help(
+            "inspect the divergences above, or run the `.update` target if the change is intended"
+        )
+    )]
+    Mismatch { count: usize, total: usize },
+}
+
+#[derive(Parser)]
+#[command(name = "golden")]
+#[command(about = "Golden-file runner for Molten modules", long_about = None)]
+#[command(version)]
+struct Arguments {
+    #[arg(
+        long = "module",
+        required = true,
+        help = "Module to evaluate against its .expected file"
+    )]
+    modules: Vec<PathBuf>,
+
+    #[arg(long, help = "Rewrite expectations from the evaluated modules")]
+    update: bool,
+
+    #[command(flatten)]
+    observation: observation::argument::Argument,
+}
+
+enum Expectation {
+    Text,
+    Structured,
+}
+
+impl Expectation {
+    fn detect(content: &str) -> Self {
+        match content.trim_start().chars().next() {
+            Some('[' | '{') => Expectation::Structured,
+            _ => Expectation::Text,
+        }
+    }
+
+    fn parse(&self, path: &Path, content: &str) -> Result<serde_json::Value> {
+        match self {
+            Expectation::Text => Ok(serde_json::Value::from(
+                content.lines().map(str::to_owned).collect::<Vec<_>>(),
+            )),
+            Expectation::Structured => {
+                let value = serde_json::from_str(content).map_err(|source| Error::Format {
+                    path: path.display().to_string(),
+                    source,
+                })?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn actual(&self, program: &Program) -> Result<serde_json::Value> {
+        let particles = particles(program)?;
+        match self {
+            Expectation::Text => Ok(serde_json::Value::from(
+                particles.iter().map(line).collect::<Vec<_>>(),
+            )),
+            Expectation::Structured => {
+                Ok(serde_json::to_value(particles).map_err(Error::Serialize)?)
+            }
+        }
+    }
+
+    fn render(&self, program: &Program) -> Result<String> {
+        let particles = particles(program)?;
+        match self {
+            Expectation::Text => Ok(particles
+                .iter()
+                .map(|particulate| line(particulate) + "\n")
+                .collect()),
+            Expectation::Structured => Ok(format!(
+                "{}\n",
+                serde_json::to_string_pretty(&particles).map_err(Error::Serialize)?
+            )),
+        }
+    }
+}
+
+fn particles(program: &Program) -> Result<Vec<Particulate>> {
+    Ok(program
+        .particles()?
+        .into_iter()
+        .map(|particulate| Particulate {
+            particle: normalize(&particulate.particle),
+            ..particulate
+        })
+        .collect())
+}
+
+fn line(particulate: &Particulate) -> String {
+    format!("{}: {}", particulate.label, particulate.particle)
+}
+
+// The renderer keeps source whitespace as void elements, so expectations drop
+// whitespace-only elements and collapse runs of spaces inside the others.
+fn normalize(particle: &str) -> String {
+    particle
+        .split(" · ")
+        .map(|element| element.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|element| !element.is_empty() && !element.starts_with("× "))
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+fn main() -> Result<()> {
+    command::execute(
+        |arguments: &Arguments| observation::initialize(&arguments.observation.sink),
+        |arguments, _runtime| {
+            if arguments.update {
+                update(&arguments.modules)
+            } else {
+                verify(&arguments.modules)
+            }
+        },
+    )
+}
+
+fn expectation(module: &Path) -> PathBuf {
+    module.with_extension("expected")
+}
+
+#[trace(channels = [core])]
+fn verify(modules: &[PathBuf]) -> Result<()> {
+    let mut count = 0;
+
+    for module in modules {
+        let path = expectation(module);
+        let content = std::fs::read_to_string(&path).map_err(|source| Error::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        let kind = Expectation::detect(&content);
+        let expected = kind.parse(&path, &content)?;
+        let program = program::evaluate(module)?;
+        let actual = kind.actual(&program)?;
+
+        let divergences = difference::compare(&expected, &actual);
+        if divergences.is_empty() {
+            info!("{}: pass", module.display());
+            continue;
+        }
+
+        count += 1;
+        eprintln!("{}: {} divergences", module.display(), divergences.len());
+        for (segments, divergence) in &divergences {
+            eprintln!("{}", describe(segments, divergence));
+        }
+    }
+
+    if count > 0 {
+        return Err(Error::Mismatch {
+            count,
+            total: modules.len(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+#[trace(channels = [core])]
+fn update(modules: &[PathBuf]) -> Result<()> {
+    let root = platform::run::workspace()
+        .or_else(platform::run::directory)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    for module in modules {
+        let path = root.join(expectation(module));
+        let kind = std::fs::read_to_string(&path)
+            .map_or(Expectation::Text, |content| Expectation::detect(&content));
+
+        let program = program::evaluate(module)?;
+        let content = kind.render(&program)?;
+
+        std::fs::write(&path, content).map_err(|source| Error::Write {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        info!("{}: updated", path.display());
+    }
+
+    Ok(())
+}
+
+fn describe(segments: &[Segment], divergence: &Divergence) -> String {
+    let location = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Index(index) => format!("[{index}]"),
+            Segment::Key(key) => format!(".{key}"),
+        })
+        .collect::<String>();
+
+    format!(
+        "  {location}\n    - {}\n    + {}",
+        divergence.expected, divergence.actual
+    )
+}
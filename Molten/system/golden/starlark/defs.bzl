@@ -0,0 +1,9 @@
+"""
+Public API for Molten golden-file tests.
+
+Exports: golden
+"""
+
+load(":golden.bzl", _golden = "golden")
+
+golden = _golden
@@ -0,0 +1,87 @@
+"""
+Golden-file tests for Molten modules.
+
+Public API:
+- golden: Evaluate each module and diff its particles against the adjacent .expected file
+"""
+
+def _golden_impl(ctx):
+    arguments = []
+    for module in ctx.files.modules:
+        arguments.extend(["--module", module.short_path])
+    if ctx.attr.update:
+        arguments.append("--update")
+
+    script = ctx.actions.declare_file(ctx.label.name + ".sh")
+    ctx.actions.write(
+        output = script,
+        content = "#!/usr/bin/env bash\nexec {runner} {arguments} \"$@\"\n".format(
+            runner = ctx.executable.runner.short_path,
+            arguments = " ".join([shell.quote(argument) for argument in arguments]),
+        ),
+        is_executable = True,
+    )
+
+    runfiles = ctx.runfiles(files = ctx.files.modules + ctx.files.expectations)
+    runfiles = runfiles.merge(ctx.attr.runner[DefaultInfo].default_runfiles)
+
+    return [DefaultInfo(executable = script, runfiles = runfiles)]
+
+_attributes = {
+    "modules": attr.label_list(allow_files = [".lava"], mandatory = True),
+    "expectations": attr.label_list(allow_files = [".expected"]),
+    "update": attr.bool(default = False),
+    "runner": attr.label(
+        default = "//Molten/system/golden:command",
+        executable = True,
+        cfg = "target",
+    ),
+}
+
+_golden_test = rule(
+    implementation = _golden_impl,
+    attrs = _attributes,
+    test = True,
+)
+
+_golden_update = rule(
+    implementation = _golden_impl,
+    attrs = _attributes,
+    executable = True,
+)
+
+def golden(name, modules, visibility = None, **kwargs):
+    """
+    Evaluate Molten modules against their recorded expectations.
+
+    Each module `foo.lava` is paired with `foo.expected` in the same package.
+    Expectations hold either canonical particle text, one `label: particle`
+    line per node, or the equivalent JSON array. `bazel run :<name>.update`
+    rewrites the expectations from the current evaluation.
+
+    Args:
+        name: Test target name
+        modules: Molten module sources in this package
+        visibility: Bazel visibility
+        **kwargs: Forwarded to the test rule
+    """
+    expectations = native.glob(
+        [module[:-len(".lava")] + ".expected" for module in modules],
+        allow_empty = True,
+    )
+
+    _golden_test(
+        name = name,
+        modules = modules,
+        expectations = expectations,
+        visibility = visibility,
+        **kwargs
+    )
+
+    _golden_update(
+        name = name + ".update",
+        modules = modules,
+        expectations = expectations,
+        update = True,
+        visibility = visibility,
+    )
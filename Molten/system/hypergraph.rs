@@ -1 +1,4 @@
+pub use debug;
 pub use evaluate;
+pub use profile;
+pub use program;
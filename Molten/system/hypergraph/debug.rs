@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use observe::trace;
+use serde::{Serialize, de::DeserializeOwned};
+
+use component::graph::relation::Edge as Relation;
+use component::graph::relation::Related as Relations;
+use component::graph::state::wave::Wave as Waveform;
+use component::hypergraph::{Hypergraph, Label, Translation};
+use evaluate::error::Result;
+use evaluate::{Evaluate, chains, separated, translations};
+use query::Ranked;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Direct,
+    Ancestral,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    pub combination: BTreeSet<Label>,
+    pub relation: Relation<Waveform<T>>,
+    pub independent: bool,
+    pub phase: Phase,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    pub candidate: Candidate<T>,
+    pub translations: Vec<Translation>,
+    pub edges: Vec<Label>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Direct,
+    Ancestral,
+    Settled,
+}
+
+pub struct Debugger<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    refractions: Relations<Waveform<T>>,
+    rules: VecDeque<(Waveform<T>, BTreeSet<Waveform<T>>)>,
+    snapshot: VecDeque<Label>,
+    pending: VecDeque<Candidate<T>>,
+    direct: BTreeSet<Label>,
+    round: BTreeSet<Label>,
+    stage: Stage,
+    pub iterations: usize,
+    pub enumeration: BTreeMap<Relation<Waveform<T>>, Duration>,
+}
+
+impl<T: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned> Debugger<T> {
+    #[must_use]
+    pub fn new(refractions: Relations<Waveform<T>>) -> Self {
+        let rules = refractions.clone().into_iter().collect();
+        Self {
+            refractions,
+            rules,
+            snapshot: VecDeque::new(),
+            pending: VecDeque::new(),
+            direct: BTreeSet::new(),
+            round: BTreeSet::new(),
+            stage: Stage::Direct,
+            iterations: 0,
+            enumeration: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn settled(&self) -> bool {
+        self.stage == Stage::Settled
+    }
+
+    #[trace(channels = [core])]
+    pub fn peek(&mut self, graph: &Hypergraph<T>) -> Result<Option<&Candidate<T>>> {
+        self.prime(graph)?;
+        Ok(self.pending.front())
+    }
+
+    #[trace(channels = [core])]
+    pub fn step(&mut self, graph: &mut Hypergraph<T>) -> Result<Option<Step<T>>> {
+        self.prime(graph)?;
+
+        let Some(candidate) = self.pending.pop_front() else {
+            return Ok(None);
+        };
+
+        let translations = if candidate.independent {
+            translations(graph, &candidate.combination, &candidate.relation)?
+        } else {
+            Vec::new()
+        };
+        let edges = translations
+            .iter()
+            .filter_map(Translation::created)
+            .collect::<Vec<_>>();
+
+        if candidate.phase == Phase::Direct {
+            self.direct.extend(edges.iter().copied());
+        }
+        self.round.extend(edges.iter().copied());
+
+        record::event!(
+            channels = [hypergraph],
+            source = candidate.combination,
+            rule = candidate.relation,
+            independent = candidate.independent,
+            edges = edges
+        );
+
+        Ok(Some(Step {
+            candidate,
+            translations,
+            edges,
+        }))
+    }
+
+    #[trace(channels = [core])]
+    pub fn resume(
+        &mut self,
+        graph: &mut Hypergraph<T>,
+        mut halt: impl FnMut(&Candidate<T>) -> bool,
+    ) -> Result<Vec<Step<T>>> {
+        let mut steps = Vec::new();
+        while let Some(step) = self.step(graph)? {
+            steps.push(step);
+            match self.peek(graph)? {
+                Some(candidate) if !halt(candidate) => {}
+                _ => break,
+            }
+        }
+        Ok(steps)
+    }
+
+    fn prime(&mut self, graph: &Hypergraph<T>) -> Result<()> {
+        while self.pending.is_empty() && self.stage != Stage::Settled {
+            match self.stage {
+                Stage::Direct => {
+                    if let Some((source, sinks)) = self.rules.pop_front() {
+                        self.direct(graph, &source, &sinks)?;
+                    } else {
+                        self.snapshot = self.direct.iter().copied().collect();
+                        self.stage = Stage::Ancestral;
+                    }
+                }
+                Stage::Ancestral => match self.snapshot.pop_front() {
+                    Some(label) => self.ancestral(graph, label)?,
+                    None if self.round.is_empty() => self.stage = Stage::Settled,
+                    None => {
+                        self.iterations += 1;
+                        self.rules = self.refractions.clone().into_iter().collect();
+                        self.direct.clear();
+                        self.round.clear();
+                        self.stage = Stage::Direct;
+                    }
+                },
+                Stage::Settled => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn direct(
+        &mut self,
+        graph: &Hypergraph<T>,
+        source: &Waveform<T>,
+        sinks: &BTreeSet<Waveform<T>>,
+    ) -> Result<()> {
+        let start = Instant::now();
+
+        for combination in graph.independent(source.rank()) {
+            if graph.bipartite(combination.clone(), source)?.count() == 0 {
+                continue;
+            }
+
+            for sink in sinks {
+                self.pending.push_back(Candidate {
+                    combination: combination.clone(),
+                    relation: Relation {
+                        source: source.clone(),
+                        sink: sink.clone(),
+                    },
+                    independent: true,
+                    phase: Phase::Direct,
+                });
+            }
+        }
+
+        // Candidates of a source are enumerated together, so their sinks share the cost.
+        let share = start.elapsed() / u32::try_from(sinks.len()).unwrap_or(u32::MAX).max(1);
+        for sink in sinks {
+            *self
+                .enumeration
+                .entry(Relation {
+                    source: source.clone(),
+                    sink: sink.clone(),
+                })
+                .or_default() += share;
+        }
+
+        Ok(())
+    }
+
+    fn ancestral(&mut self, graph: &Hypergraph<T>, label: Label) -> Result<()> {
+        let start = Instant::now();
+        let edge = graph.edge(label)?;
+        let sources = edge
+            .inference
+            .source
+            .iter()
+            .copied()
+            .collect::<Vec<Label>>();
+
+        for combination in chains(&sources, &graph.past, graph)
+            .into_iter()
+            .multi_cartesian_product()
+        {
+            let combination = combination.into_iter().collect::<BTreeSet<Label>>();
+            let independent = separated(&combination, &graph.united);
+
+            if independent
+                && graph
+                    .bipartite(combination.clone(), &edge.relation.source)?
+                    .count()
+                    == 0
+            {
+                continue;
+            }
+
+            self.pending.push_back(Candidate {
+                combination,
+                relation: edge.relation.clone(),
+                independent,
+                phase: Phase::Ancestral,
+            });
+        }
+
+        *self.enumeration.entry(edge.relation.clone()).or_default() += start.elapsed();
+
+        Ok(())
+    }
+}
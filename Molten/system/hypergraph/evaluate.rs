@@ -61,7 +61,7 @@ where
     result
 }
 
-fn chains<V: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
+pub fn chains<V: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
     sources: &[Label],
     past: &BTreeMap<Label, BTreeSet<Label>>,
     graph: &Hypergraph<V>,
@@ -79,6 +79,248 @@ where
         .collect()
 }
 
+pub fn translations<T: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
+    graph: &mut Hypergraph<T>,
+    source: &BTreeSet<Label>,
+    relation: &Relation<Waveform<T>>,
+) -> Result<Vec<Translation>> {
+    type Matching<V> = (BTreeSet<Label>, BTreeMap<Particulate<V>, usize>);
+
+    fn exclude<P: Clone>(items: &[(P, usize)], index: usize) -> Vec<(P, usize)> {
+        items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (p, c))| (i != index).then_some((p.clone(), *c)))
+            .collect()
+    }
+
+    fn reduce<P: Clone>(items: &[(P, usize)], index: usize, amount: usize) -> Vec<(P, usize)> {
+        items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (p, c))| {
+                if i == index {
+                    (c - amount > 0).then(|| (p.clone(), c - amount))
+                } else {
+                    Some((p.clone(), *c))
+                }
+            })
+            .collect()
+    }
+
+    fn enumerate<V: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
+        available: Vec<(Label, usize)>,
+        needed: Vec<(Particulate<V>, usize)>,
+        assigned: BTreeSet<Label>,
+        results: &mut Vec<Matching<V>>,
+        graph: &Hypergraph<V>,
+    ) -> Result<()>
+    where
+        Particulate<V>: Set + Ranked,
+    {
+        if needed.is_empty() {
+            results.push((assigned, needed.into_iter().collect()));
+            return Ok(());
+        }
+
+        if available.is_empty() {
+            return Ok(());
+        }
+
+        for (index, &(label, count)) in available.iter().enumerate() {
+            let node = graph.node(label)?;
+            let particle = &node.particle;
+
+            for (position, (target, quantity)) in needed.iter().enumerate() {
+                if particle.isomorphic(target).is_none() {
+                    continue;
+                }
+
+                let applied = count.min(*quantity);
+                let world = graph.world.get(&label).ok_or_else(|| Error::world(label))?;
+
+                let conflicting = assigned
+                    .iter()
+                    .map(|&assigned_label| graph.world.get(&assigned_label))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| Error::world(label))?
+                    .into_iter()
+                    .any(|assigned_world| assigned_world == world);
+
+                if conflicting {
+                    continue;
+                }
+
+                let mut remaining = exclude(&available, index);
+                if count > applied {
+                    remaining.push((label, count - applied));
+                }
+
+                let reduced = reduce(&needed, position, applied);
+
+                let mut next = assigned.clone();
+                next.insert(label);
+
+                enumerate(remaining, reduced, next, results, graph)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn search<V: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
+        targets: &[(Particulate<V>, usize)],
+        index: usize,
+        assigned: &mut BTreeSet<Label>,
+        unmatched: &mut BTreeMap<Particulate<V>, usize>,
+        results: &mut Vec<Matching<V>>,
+        graph: &Hypergraph<V>,
+    ) where
+        Particulate<V>: Set + Ranked,
+    {
+        if index >= targets.len() {
+            results.push((assigned.clone(), unmatched.clone()));
+            return;
+        }
+
+        let (particle, count) = &targets[index];
+        let isomorphic = graph.isomorphics(particle).collect::<Vec<Label>>();
+
+        if isomorphic.is_empty() {
+            *unmatched.entry(particle.clone()).or_insert(0) += count;
+            search(targets, index + 1, assigned, unmatched, results, graph);
+            if let Some(existing) = unmatched.get_mut(particle) {
+                *existing -= count;
+                if *existing == 0 {
+                    unmatched.remove(particle);
+                }
+            }
+            return;
+        }
+
+        let mut found = false;
+        for label in isomorphic {
+            let Some(world) = graph.world.get(&label) else {
+                continue;
+            };
+
+            let conflicting = assigned
+                .iter()
+                .filter_map(|&l| graph.world.get(&l))
+                .any(|w| w == world);
+
+            if conflicting {
+                continue;
+            }
+
+            found = true;
+            assigned.insert(label);
+            search(targets, index + 1, assigned, unmatched, results, graph);
+            assigned.remove(&label);
+        }
+
+        if !found {
+            *unmatched.entry(particle.clone()).or_insert(0) += count;
+            search(targets, index + 1, assigned, unmatched, results, graph);
+            if let Some(existing) = unmatched.get_mut(particle) {
+                *existing -= count;
+                if *existing == 0 {
+                    unmatched.remove(particle);
+                }
+            }
+        }
+    }
+
+    let particles = source
+        .iter()
+        .map(|&label| graph.node(label).map(|node| node.particle.clone()))
+        .collect::<Result<Vec<_>>>()?;
+    let wave = Waveform::from(particles.as_slice());
+
+    let mut all = Vec::new();
+    for residual in wave.diverges(&relation.source) {
+        let mut sink = relation.sink.particles.clone();
+        for (particle, count) in residual.particles {
+            *sink.entry(particle).or_insert(0) += count;
+        }
+        let sink = Waveform::new(sink);
+
+        let targets = (&sink)
+            .into_iter()
+            .map(|(p, &c)| (p.clone(), c))
+            .collect::<Vec<_>>();
+
+        let available = graph
+            .nodes
+            .iter()
+            .filter(|node| {
+                targets
+                    .iter()
+                    .any(|(target, _)| node.particle.isomorphic(target).is_some())
+            })
+            .map(|node| (node.label, 1))
+            .collect::<Vec<_>>();
+
+        let mut matchings = Vec::new();
+        enumerate(
+            available,
+            targets.clone(),
+            BTreeSet::new(),
+            &mut matchings,
+            graph,
+        )?;
+
+        if matchings.is_empty() {
+            search(
+                &targets,
+                0,
+                &mut BTreeSet::new(),
+                &mut BTreeMap::new(),
+                &mut matchings,
+                graph,
+            );
+
+            if matchings.is_empty() {
+                matchings.push((BTreeSet::new(), targets.into_iter().collect()));
+            }
+        }
+
+        for (matched, unmatched) in matchings {
+            let mut destinations = BTreeSet::new();
+
+            for &label in &matched {
+                if !destinations.insert(label) {
+                    return Err(Error::duplicate(label));
+                }
+            }
+
+            for (particle, count) in &unmatched {
+                for _ in 0..*count {
+                    let label = graph.focus(particle.clone());
+                    if !destinations.insert(label) {
+                        return Err(Error::duplicate(label));
+                    }
+                }
+            }
+
+            all.push(graph.translate(source.clone(), destinations, relation.clone())?);
+        }
+    }
+
+    Ok(all)
+}
+
+pub fn separated(combination: &BTreeSet<Label>, united: &BTreeMap<Label, BTreeSet<Label>>) -> bool {
+    let labels = combination.iter().copied().collect::<Vec<_>>();
+    labels.iter().enumerate().all(|(i, &label)| {
+        let class = united.values().find(|members| members.contains(&label));
+        match class {
+            Some(members) => labels[i + 1..].iter().all(|other| !members.contains(other)),
+            None => true,
+        }
+    })
+}
+
 pub trait Evaluate {
     type Value: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned;
     type Particle;
@@ -177,6 +419,7 @@ impl<T: Clone + Eq + Ord + std::hash::Hash + Serialize + DeserializeOwned> Evalu
             particle = particle,
             world = self.worlds - 1
         );
+        record::gauge!(channels = [hypergraph], nodes = self.nodes.len());
 
         label
     }
@@ -193,237 +436,16 @@ impl<T: Clone + Eq + Ord + std::hash::Hash + Serialize + DeserializeOwned> Evalu
         labels.into_iter()
     }
 
-    #[trace(channels = [core])]
+    #[trace(channels = [core], fields = [source, relation], err)]
     fn absorb(
         &mut self,
         source: BTreeSet<Label>,
         relation: Relation<Waveform<T>>,
     ) -> Result<impl Iterator<Item = Label>> {
-        type Matching<V> = (BTreeSet<Label>, BTreeMap<Particulate<V>, usize>);
-
-        fn exclude<P: Clone>(items: &[(P, usize)], index: usize) -> Vec<(P, usize)> {
-            items
-                .iter()
-                .enumerate()
-                .filter_map(|(i, (p, c))| (i != index).then_some((p.clone(), *c)))
-                .collect()
-        }
-
-        fn reduce<P: Clone>(items: &[(P, usize)], index: usize, amount: usize) -> Vec<(P, usize)> {
-            items
-                .iter()
-                .enumerate()
-                .filter_map(|(i, (p, c))| {
-                    if i == index {
-                        (c - amount > 0).then(|| (p.clone(), c - amount))
-                    } else {
-                        Some((p.clone(), *c))
-                    }
-                })
-                .collect()
-        }
-
-        fn enumerate<V: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
-            available: Vec<(Label, usize)>,
-            needed: Vec<(Particulate<V>, usize)>,
-            assigned: BTreeSet<Label>,
-            results: &mut Vec<Matching<V>>,
-            graph: &Hypergraph<V>,
-        ) -> Result<()>
-        where
-            Particulate<V>: Set + Ranked,
-        {
-            if needed.is_empty() {
-                results.push((assigned, needed.into_iter().collect()));
-                return Ok(());
-            }
-
-            if available.is_empty() {
-                return Ok(());
-            }
-
-            for (index, &(label, count)) in available.iter().enumerate() {
-                let node = graph.node(label)?;
-                let particle = &node.particle;
-
-                for (position, (target, quantity)) in needed.iter().enumerate() {
-                    if particle.isomorphic(target).is_none() {
-                        continue;
-                    }
-
-                    let applied = count.min(*quantity);
-                    let world = graph.world.get(&label).ok_or_else(|| Error::world(label))?;
-
-                    let conflicting = assigned
-                        .iter()
-                        .map(|&assigned_label| graph.world.get(&assigned_label))
-                        .collect::<Option<Vec<_>>>()
-                        .ok_or_else(|| Error::world(label))?
-                        .into_iter()
-                        .any(|assigned_world| assigned_world == world);
-
-                    if conflicting {
-                        continue;
-                    }
-
-                    let mut remaining = exclude(&available, index);
-                    if count > applied {
-                        remaining.push((label, count - applied));
-                    }
-
-                    let reduced = reduce(&needed, position, applied);
-
-                    let mut next = assigned.clone();
-                    next.insert(label);
-
-                    enumerate(remaining, reduced, next, results, graph)?;
-                }
-            }
-
-            Ok(())
-        }
-
-        fn search<V: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
-            targets: &[(Particulate<V>, usize)],
-            index: usize,
-            assigned: &mut BTreeSet<Label>,
-            unmatched: &mut BTreeMap<Particulate<V>, usize>,
-            results: &mut Vec<Matching<V>>,
-            graph: &Hypergraph<V>,
-        ) where
-            Particulate<V>: Set + Ranked,
-        {
-            if index >= targets.len() {
-                results.push((assigned.clone(), unmatched.clone()));
-                return;
-            }
-
-            let (particle, count) = &targets[index];
-            let isomorphic = graph.isomorphics(particle).collect::<Vec<Label>>();
-
-            if isomorphic.is_empty() {
-                *unmatched.entry(particle.clone()).or_insert(0) += count;
-                search(targets, index + 1, assigned, unmatched, results, graph);
-                if let Some(existing) = unmatched.get_mut(particle) {
-                    *existing -= count;
-                    if *existing == 0 {
-                        unmatched.remove(particle);
-                    }
-                }
-                return;
-            }
-
-            let mut found = false;
-            for label in isomorphic {
-                let Some(world) = graph.world.get(&label) else {
-                    continue;
-                };
-
-                let conflicting = assigned
-                    .iter()
-                    .filter_map(|&l| graph.world.get(&l))
-                    .any(|w| w == world);
-
-                if conflicting {
-                    continue;
-                }
-
-                found = true;
-                assigned.insert(label);
-                search(targets, index + 1, assigned, unmatched, results, graph);
-                assigned.remove(&label);
-            }
-
-            if !found {
-                *unmatched.entry(particle.clone()).or_insert(0) += count;
-                search(targets, index + 1, assigned, unmatched, results, graph);
-                if let Some(existing) = unmatched.get_mut(particle) {
-                    *existing -= count;
-                    if *existing == 0 {
-                        unmatched.remove(particle);
-                    }
-                }
-            }
-        }
-
-        let particles = source
+        let all = translations(self, &source, &relation)?
             .iter()
-            .map(|&label| self.node(label).map(|node| node.particle.clone()))
-            .collect::<Result<Vec<_>>>()?;
-        let wave = Waveform::from(particles.as_slice());
-
-        let mut all = Vec::new();
-        for residual in wave.diverges(&relation.source) {
-            let mut sink = relation.sink.particles.clone();
-            for (particle, count) in residual.particles {
-                *sink.entry(particle).or_insert(0) += count;
-            }
-            let sink = Waveform::new(sink);
-
-            let targets = (&sink)
-                .into_iter()
-                .map(|(p, &c)| (p.clone(), c))
-                .collect::<Vec<_>>();
-
-            let available = self
-                .nodes
-                .iter()
-                .filter(|node| {
-                    targets
-                        .iter()
-                        .any(|(target, _)| node.particle.isomorphic(target).is_some())
-                })
-                .map(|node| (node.label, 1))
-                .collect::<Vec<_>>();
-
-            let mut matchings = Vec::new();
-            enumerate(
-                available,
-                targets.clone(),
-                BTreeSet::new(),
-                &mut matchings,
-                self,
-            )?;
-
-            if matchings.is_empty() {
-                search(
-                    &targets,
-                    0,
-                    &mut BTreeSet::new(),
-                    &mut BTreeMap::new(),
-                    &mut matchings,
-                    self,
-                );
-
-                if matchings.is_empty() {
-                    matchings.push((BTreeSet::new(), targets.into_iter().collect()));
-                }
-            }
-
-            for (matched, unmatched) in matchings {
-                let mut destinations = BTreeSet::new();
-
-                for &label in &matched {
-                    if !destinations.insert(label) {
-                        return Err(Error::duplicate(label));
-                    }
-                }
-
-                for (particle, count) in &unmatched {
-                    for _ in 0..*count {
-                        let label = self.focus(particle.clone());
-                        if !destinations.insert(label) {
-                            return Err(Error::duplicate(label));
-                        }
-                    }
-                }
-
-                let translation = self.translate(source.clone(), destinations, relation.clone())?;
-                if let Some(label) = translation.created() {
-                    all.push(label);
-                }
-            }
-        }
+            .filter_map(Translation::created)
+            .collect::<Vec<Label>>();
 
         record::event!(
             channels = [hypergraph],
@@ -529,7 +551,7 @@ impl<T: Clone + Eq + Ord + std::hash::Hash + Serialize + DeserializeOwned> Evalu
         Ok(present)
     }
 
-    #[trace(channels = [core])]
+    #[trace(channels = [core], fields = [first, second], ret, err)]
     fn unite(&mut self, first: Label, second: Label) -> Result<Label> {
         let anchor = self.locate(first)?;
         let pivot = self.locate(second)?;
@@ -580,7 +602,7 @@ impl<T: Clone + Eq + Ord + std::hash::Hash + Serialize + DeserializeOwned> Evalu
         Ok(merged)
     }
 
-    #[trace(channels = [core])]
+    #[trace(channels = [core], fields = [source, destinations], ret, err)]
     fn translate(
         &mut self,
         source: BTreeSet<Label>,
@@ -687,21 +709,7 @@ impl<T: Clone + Eq + Ord + std::hash::Hash + Serialize + DeserializeOwned> Evalu
             for combination in ancestral.into_iter().multi_cartesian_product() {
                 let combined = combination.into_iter().collect::<BTreeSet<Label>>();
 
-                let labels = combined.iter().copied().collect::<Vec<_>>();
-                let independent = labels.iter().enumerate().all(|(i, &label)| {
-                    let class = self
-                        .united
-                        .values()
-                        .find(|members| members.contains(&label));
-                    match class {
-                        Some(members) => {
-                            labels[i + 1..].iter().all(|other| !members.contains(other))
-                        }
-                        None => true,
-                    }
-                });
-
-                if !independent {
+                if !separated(&combined, &self.united) {
                     continue;
                 }
 
@@ -751,6 +759,7 @@ impl<T: Clone + Eq + Ord + std::hash::Hash + Serialize + DeserializeOwned> Evalu
             iterations = iterations,
             count = all.edges.len()
         );
+        record::counter!(channels = [hypergraph], passes = iterations);
 
         Ok(all)
     }
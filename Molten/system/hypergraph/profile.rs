@@ -0,0 +1,85 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use observe::trace;
+use serde::{Serialize, de::DeserializeOwned};
+
+use component::graph::relation::Edge as Relation;
+use component::graph::relation::Related as Relations;
+use component::graph::state::wave::Wave as Waveform;
+use component::hypergraph::{Hypergraph, Inference};
+use debug::Debugger;
+use evaluate::error::Result;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cost {
+    pub matchings: usize,
+    pub absorbs: usize,
+    pub created: usize,
+    pub existing: usize,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct Profile<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    pub rules: BTreeMap<Relation<Waveform<T>>, Cost>,
+    pub inference: Inference,
+    pub iterations: usize,
+}
+
+impl<T: Clone + Eq + Ord + Serialize + DeserializeOwned> Profile<T> {
+    #[must_use]
+    pub fn sorted(&self) -> Vec<(&Relation<Waveform<T>>, &Cost)> {
+        let mut sorted = self.rules.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|(_, cost)| std::cmp::Reverse(cost.elapsed));
+        sorted
+    }
+}
+
+#[trace(channels = [core])]
+pub fn profile<T: Clone + Eq + Ord + Hash + Serialize + DeserializeOwned>(
+    graph: &mut Hypergraph<T>,
+    refractions: Relations<Waveform<T>>,
+) -> Result<Profile<T>> {
+    let mut debugger = Debugger::new(refractions);
+    let mut rules = BTreeMap::<Relation<Waveform<T>>, Cost>::new();
+    let mut edges = BTreeSet::new();
+
+    // Priming outside the timed step keeps enumeration off the previous rule's bill.
+    while debugger.peek(graph)?.is_some() {
+        let start = Instant::now();
+        let Some(step) = debugger.step(graph)? else {
+            break;
+        };
+        let elapsed = start.elapsed();
+
+        let cost = rules.entry(step.candidate.relation).or_default();
+        cost.matchings += 1;
+        cost.elapsed += elapsed;
+        if !step.edges.is_empty() {
+            cost.absorbs += 1;
+        }
+        cost.created += step.edges.len();
+        cost.existing += step.translations.len() - step.edges.len();
+
+        edges.extend(step.edges);
+    }
+
+    for (relation, elapsed) in std::mem::take(&mut debugger.enumeration) {
+        rules.entry(relation).or_default().elapsed += elapsed;
+    }
+
+    record::event!(
+        channels = [hypergraph],
+        rules = rules.len(),
+        edges = edges,
+        iterations = debugger.iterations
+    );
+
+    Ok(Profile {
+        rules,
+        inference: Inference { edges },
+        iterations: debugger.iterations,
+    })
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use observe::trace;
+use serde::{Deserialize, Serialize};
+
+use arena::Indexed;
+use component::graph::index::Index;
+use component::graph::relation::Edge as Relation;
+use component::graph::state::particle::Particle;
+use component::graph::state::wave::Wave;
+use component::graph::symbolic::constructor::Source;
+use component::hypergraph::{Hypergraph, Inference};
+use error::Result;
+use evaluate::Evaluate;
+use graph::index::Index as Allocate;
+use graph::symbolic::constructor::Constructor;
+use graph::symbolic::renderer;
+
+pub use error;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Particulate {
+    pub label: usize,
+    pub particle: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Derivation {
+    pub label: usize,
+    pub source: Vec<usize>,
+    pub sink: Vec<usize>,
+    pub rule: String,
+}
+
+pub struct Program {
+    pub index: Index<String>,
+    pub graph: Hypergraph<usize>,
+}
+
+impl Program {
+    #[trace(channels = [core])]
+    pub fn compile(source: Source) -> Result<Self> {
+        let module = source.module()?;
+        let mut index = Index::<String>::default();
+        let (_, signal) = Allocate::allocate(&mut index, module)?;
+
+        let mut graph = Hypergraph::default();
+        graph.diffuse(signal).for_each(drop);
+
+        Ok(Self { index, graph })
+    }
+
+    #[trace(channels = [core])]
+    pub fn path(path: impl AsRef<Path>) -> Result<Self> {
+        Self::compile(Source::path(path)?)
+    }
+
+    #[trace(channels = [core])]
+    pub fn evaluate(&mut self) -> Result<Inference> {
+        Ok(self.graph.fixed(self.index.relations.clone())?)
+    }
+
+    #[trace(channels = [core])]
+    pub fn render(&self, particle: &Particle<usize>) -> Result<String> {
+        let mut elements = BTreeMap::new();
+        for (&alias, &count) in particle {
+            let value = self.index.arena.value(alias)?;
+            let text = renderer::attribute(usize::MAX, &self.index.arena, value);
+            *elements.entry(text).or_insert(0) += count;
+        }
+        Ok(Particle::new(elements).to_string())
+    }
+
+    #[trace(channels = [core])]
+    pub fn wave(&self, wave: &Wave<usize>) -> Result<String> {
+        Ok(wave
+            .iter()
+            .map(|(particle, &count)| {
+                self.render(particle).map(|text| match count {
+                    1 => text,
+                    _ => format!("{text} × {count}"),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(", "))
+    }
+
+    #[trace(channels = [core])]
+    pub fn relation(&self, relation: &Relation<Wave<usize>>) -> Result<String> {
+        Ok(format!(
+            "{} → {}",
+            self.wave(&relation.source)?,
+            self.wave(&relation.sink)?
+        ))
+    }
+
+    #[trace(channels = [core])]
+    pub fn particles(&self) -> Result<Vec<Particulate>> {
+        self.graph
+            .nodes
+            .iter()
+            .map(|node| {
+                Ok(Particulate {
+                    label: node.label.0,
+                    particle: self.render(&node.particle)?,
+                })
+            })
+            .collect()
+    }
+
+    #[trace(channels = [core])]
+    pub fn derivations(&self) -> Result<Vec<Derivation>> {
+        self.graph
+            .edges
+            .iter()
+            .map(|edge| {
+                Ok(Derivation {
+                    label: edge.label.0,
+                    source: edge.inference.source.iter().map(|label| label.0).collect(),
+                    sink: edge.inference.sink.iter().map(|label| label.0).collect(),
+                    rule: self.relation(&edge.relation)?,
+                })
+            })
+            .collect()
+    }
+
+    #[trace(channels = [core])]
+    pub fn text(&self) -> Result<String> {
+        Ok(self
+            .particles()?
+            .iter()
+            .map(|particulate| format!("{}: {}\n", particulate.label, particulate.particle))
+            .collect())
+    }
+}
+
+#[trace(channels = [core])]
+pub fn evaluate(path: impl AsRef<Path>) -> Result<Program> {
+    let mut program = Program::path(path)?;
+    program.evaluate()?;
+    Ok(program)
+}
@@ -0,0 +1,27 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+pub type Result<T> = miette::Result<T, Error>;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum Error {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Resource(#[from] resource::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Source(#[from] constructor::Sourced),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Arena(#[from] arena::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Missing(#[from] arena::Missing),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Evaluate(#[from] evaluate::Error),
+}
@@ -36,6 +36,31 @@ pub enum Error {
         #[source]
         source: std::io::Error,
     },
+
+    #[error("snapshot index {index} is out of range for {count} snapshots")]
+    #[diagnostic(
+        code(observation::state::index),
+        help("reconstruct an index below the number of captured snapshots")
+    )]
+    Index { index: usize, count: usize },
+
+    #[error("failed to decode hypergraph event field: {field}")]
+    #[diagnostic(
+        code(observation::replay::event),
+        help("record may come from an incompatible evaluator version")
+    )]
+    Event {
+        field: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("no keyframe precedes snapshot {index}")]
+    #[diagnostic(
+        code(observation::state::keyframe),
+        help("include the keyframe that starts the delta sequence")
+    )]
+    Keyframe { index: usize },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,14 +1,53 @@
 use hypergraph::Hypergraph;
 use miette::IntoDiagnostic;
 use record::warn;
-use stream::Update;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use stream::{Snapshot, Update};
 use tokio::sync::mpsc::Sender;
 
 pub fn emit<T>(sender: &Sender<Update>, graph: &Hypergraph<T>, trigger: &str)
 where
     T: Clone + Eq + Ord + serde::Serialize + serde::de::DeserializeOwned,
 {
-    match state::capture(graph, trigger) {
+    send(sender, state::capture(graph, trigger));
+}
+
+pub fn trigger<T>(graph: Arc<RwLock<Hypergraph<T>>>, interval: usize) -> peer::Trigger
+where
+    T: Clone + Eq + Ord + Send + Sync + serde::Serialize + serde::de::DeserializeOwned + 'static,
+{
+    Arc::new(move || {
+        let graph = graph.clone();
+        let mut sequence = state::Sequence::new(interval);
+        Box::new(move |keyframe| {
+            if keyframe {
+                sequence.reset();
+            }
+            let graph = graph.read().unwrap_or_else(PoisonError::into_inner);
+            match sequence.capture(&graph, "command") {
+                Ok(snapshot) => Some(Update::Snapshot(snapshot)),
+                Err(e) => {
+                    warn!("{:?}", miette::Report::new(e));
+                    None
+                }
+            }
+        })
+    })
+}
+
+pub fn seeker<T>(replay: Arc<Mutex<replay::Replay<T>>>) -> peer::Seeker
+where
+    T: Clone + Eq + Ord + Send + serde::Serialize + serde::de::DeserializeOwned + 'static,
+{
+    Arc::new(move |timestamp: u64, sender: &Sender<Update>| {
+        let mut replay = replay.lock().unwrap_or_else(PoisonError::into_inner);
+        replay.seek(timestamp);
+        send(sender, state::capture(replay.graph(), "seek"));
+    })
+}
+
+fn send(sender: &Sender<Update>, captured: state::error::Result<Snapshot>) {
+    match captured {
         Ok(snapshot) => {
             if let Err(e) = sender
                 .try_send(Update::Snapshot(snapshot))
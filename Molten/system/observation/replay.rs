@@ -0,0 +1,293 @@
+pub use error;
+
+use std::collections::BTreeSet;
+
+use error::Error;
+use hypergraph::{Edge, Hypergraph, Label, Node};
+use particle::Particle;
+use serde::{Serialize, de::DeserializeOwned};
+use state::{Delta, Kind};
+use stream::{Field, Update, Value};
+use wave::Wave;
+
+const CHANNEL: &str = "hypergraph";
+
+enum Change<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    Keyframe(Hypergraph<T>),
+    Delta(Delta<T>),
+    Focus {
+        label: Label,
+        particle: Particle<T>,
+        world: usize,
+    },
+    Locate {
+        label: Label,
+        resolved: Label,
+    },
+    Unite {
+        merged: Label,
+        subset: Label,
+    },
+    Translate(Edge<Wave<T>>),
+}
+
+impl<T> Change<T>
+where
+    T: Clone + Eq + Ord + Serialize + DeserializeOwned,
+{
+    fn creates(&self, target: Label) -> bool {
+        match self {
+            Self::Focus { label, .. } => *label == target,
+            Self::Translate(edge) => edge.label == target,
+            _ => false,
+        }
+    }
+
+    fn apply(&self, graph: &mut Hypergraph<T>) {
+        match self {
+            Self::Keyframe(keyframe) => *graph = keyframe.clone(),
+            Self::Delta(delta) => delta.clone().apply(graph),
+            Self::Focus {
+                label,
+                particle,
+                world,
+            } => {
+                graph.nodes.insert(Node {
+                    label: *label,
+                    particle: particle.clone(),
+                });
+                graph.particles = graph.particles.max(label.0 + 1);
+                graph.refractions.insert(*label, *label);
+                graph.world.insert(*label, *world);
+                graph.worlds = graph.worlds.max(world + 1);
+                graph.united.insert(*label, BTreeSet::from([*label]));
+                graph.future.insert(*label, BTreeSet::new());
+                graph.past.insert(*label, BTreeSet::new());
+            }
+            Self::Locate { label, resolved } => {
+                graph.refractions.insert(*label, *resolved);
+            }
+            Self::Unite { merged, subset } => {
+                let rank = graph.world.get(merged).copied();
+                if let Some(rank) = rank.filter(|rank| graph.world.get(subset) == Some(rank)) {
+                    graph.world.insert(*merged, rank + 1);
+                }
+                graph.refractions.insert(*subset, *merged);
+                if let Some(united) = graph.united.remove(subset) {
+                    graph.united.entry(*merged).or_default().extend(united);
+                }
+            }
+            Self::Translate(edge) => {
+                graph.particles = graph.particles.max(edge.label.0 + 1);
+                for origin in &edge.inference.source {
+                    graph.future.entry(*origin).or_default().insert(edge.label);
+                }
+                for destination in &edge.inference.sink {
+                    graph
+                        .past
+                        .entry(*destination)
+                        .or_default()
+                        .insert(edge.label);
+                }
+                graph.edges.insert(edge.clone());
+            }
+        }
+    }
+}
+
+struct Frame<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    timestamp: u64,
+    change: Change<T>,
+}
+
+pub struct Replay<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    frames: Vec<Frame<T>>,
+    position: usize,
+    graph: Hypergraph<T>,
+}
+
+impl<T> Replay<T>
+where
+    T: Clone + Eq + Ord + Serialize + DeserializeOwned,
+{
+    pub fn new<I: IntoIterator<Item = Update>>(updates: I) -> error::Result<Self> {
+        let mut frames = updates
+            .into_iter()
+            .filter_map(|update| frame(update).transpose())
+            .collect::<error::Result<Vec<_>>>()?;
+        frames.sort_by_key(|frame| frame.timestamp);
+
+        Ok(Self {
+            frames,
+            position: 0,
+            graph: Hypergraph::default(),
+        })
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    #[must_use]
+    pub fn timestamp(&self) -> Option<u64> {
+        self.position
+            .checked_sub(1)
+            .map(|index| self.frames[index].timestamp)
+    }
+
+    #[must_use]
+    pub fn graph(&self) -> &Hypergraph<T> {
+        &self.graph
+    }
+
+    pub fn forward(&mut self) -> bool {
+        let Some(frame) = self.frames.get(self.position) else {
+            return false;
+        };
+        frame.change.apply(&mut self.graph);
+        self.position += 1;
+        true
+    }
+
+    pub fn backward(&mut self) -> bool {
+        let Some(position) = self.position.checked_sub(1) else {
+            return false;
+        };
+        self.jump(position);
+        true
+    }
+
+    pub fn seek(&mut self, timestamp: u64) {
+        let position = self
+            .frames
+            .partition_point(|frame| frame.timestamp <= timestamp);
+        self.jump(position);
+    }
+
+    pub fn created(&mut self, label: Label) -> bool {
+        let Some(index) = self
+            .frames
+            .iter()
+            .position(|frame| frame.change.creates(label))
+        else {
+            return false;
+        };
+        self.jump(index + 1);
+        true
+    }
+
+    fn jump(&mut self, position: usize) {
+        if position < self.position {
+            self.position = self.frames[..position]
+                .iter()
+                .rposition(|frame| matches!(frame.change, Change::Keyframe(_)))
+                .unwrap_or(0);
+            self.graph = Hypergraph::default();
+        }
+
+        while self.position < position && self.forward() {}
+    }
+}
+
+fn frame<T>(update: Update) -> error::Result<Option<Frame<T>>>
+where
+    T: Clone + Eq + Ord + Serialize + DeserializeOwned,
+{
+    match update {
+        Update::Snapshot(snapshot) => {
+            let change = match state::kind(&snapshot) {
+                Kind::Keyframe => Change::Keyframe(state::restore(&snapshot)?),
+                Kind::Delta => Change::Delta(state::delta(&snapshot)?),
+            };
+            Ok(Some(Frame {
+                timestamp: snapshot.timestamp,
+                change,
+            }))
+        }
+        Update::Event(event) if event.channels.iter().any(|channel| channel.name == CHANNEL) => {
+            Ok(change(&event.fields)?.map(|change| Frame {
+                timestamp: event.timestamp,
+                change,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn change<T>(fields: &[Field]) -> error::Result<Option<Change<T>>>
+where
+    T: Clone + Eq + Ord + Serialize + DeserializeOwned,
+{
+    let label = |name| number(fields, name).map(Label);
+
+    if let (Some(label), Some(world)) = (label("label"), number(fields, "world"))
+        && let Some(particle) = decode(fields, "particle")?
+    {
+        return Ok(Some(Change::Focus {
+            label,
+            particle,
+            world,
+        }));
+    }
+
+    if let (Some(label), Some(resolved)) = (label("label"), label("resolved")) {
+        return Ok(Some(Change::Locate { label, resolved }));
+    }
+
+    if let (Some(merged), Some(subset)) = (label("merged"), label("subset")) {
+        return Ok(Some(Change::Unite { merged, subset }));
+    }
+
+    let created = stream::field(fields, "state") == Some(&Value::Text("Created".to_string()));
+    if let Some(edge) = label("edge").filter(|_| created)
+        && let (Some(source), Some(sink), Some(relation)) = (
+            decode(fields, "source")?,
+            decode(fields, "destinations")?,
+            decode(fields, "rule")?,
+        )
+    {
+        return Ok(Some(Change::Translate(Edge {
+            label: edge,
+            inference: relation::Edge { source, sink },
+            relation,
+        })));
+    }
+
+    Ok(None)
+}
+
+fn number(fields: &[Field], name: &str) -> Option<usize> {
+    match stream::field(fields, name)? {
+        Value::Signed(value) => usize::try_from(*value).ok(),
+        Value::Unsigned(value) => usize::try_from(*value).ok(),
+        _ => None,
+    }
+}
+
+fn decode<D: DeserializeOwned>(fields: &[Field], name: &str) -> error::Result<Option<D>> {
+    let Some(value) = stream::field(fields, name) else {
+        return Ok(None);
+    };
+
+    let decoded = match value {
+        Value::Serialized(bytes) => serde_json::from_slice(bytes),
+        Value::Text(text) => serde_json::from_value(serde_json::Value::String(text.clone())),
+        other => serde_json::from_str(&other.to_string()),
+    };
+
+    decoded.map(Some).map_err(|source| Error::Event {
+        field: name.to_string(),
+        source,
+    })
+}
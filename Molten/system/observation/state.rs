@@ -1,14 +1,129 @@
 pub use error;
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::{Read, Write};
 
 use assemble::Assemble;
 use error::Error;
 use flate2::{Compression, read::GzDecoder, write::GzEncoder};
-use hypergraph::Hypergraph;
+use hypergraph::{Edge, Hypergraph, Label, Node};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use stream::Snapshot;
+use wave::Wave;
 
 const GZIP_HEADER: [u8; 2] = [0x1f, 0x8b];
+const KEYFRAME: &str = "keyframe:";
+const DELTA: &str = "delta:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Keyframe,
+    Delta,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: DeserializeOwned"))]
+pub struct Delta<T: Clone + Eq + Ord + Serialize + DeserializeOwned> {
+    pub nodes: Vec<Node<T>>,
+    pub edges: Vec<Edge<Wave<T>>>,
+    pub particles: usize,
+    pub refractions: BTreeMap<Label, Option<Label>>,
+    pub world: BTreeMap<Label, Option<usize>>,
+    pub worlds: usize,
+    pub united: BTreeMap<Label, Option<BTreeSet<Label>>>,
+    pub future: BTreeMap<Label, Option<BTreeSet<Label>>>,
+    pub past: BTreeMap<Label, Option<BTreeSet<Label>>>,
+}
+
+impl<T> Delta<T>
+where
+    T: Clone + Eq + Ord + Serialize + DeserializeOwned,
+{
+    #[must_use]
+    pub fn between(previous: &Hypergraph<T>, current: &Hypergraph<T>) -> Option<Self> {
+        if !previous.nodes.is_subset(&current.nodes) || !previous.edges.is_subset(&current.edges) {
+            return None;
+        }
+
+        Some(Self {
+            nodes: current.nodes.difference(&previous.nodes).cloned().collect(),
+            edges: current.edges.difference(&previous.edges).cloned().collect(),
+            particles: current.particles,
+            refractions: changed(&previous.refractions, &current.refractions),
+            world: changed(&previous.world, &current.world),
+            worlds: current.worlds,
+            united: changed(&previous.united, &current.united),
+            future: changed(&previous.future, &current.future),
+            past: changed(&previous.past, &current.past),
+        })
+    }
+
+    pub fn apply(self, graph: &mut Hypergraph<T>) {
+        graph.nodes.extend(self.nodes);
+        graph.edges.extend(self.edges);
+        graph.particles = self.particles;
+        settle(&mut graph.refractions, self.refractions);
+        settle(&mut graph.world, self.world);
+        graph.worlds = self.worlds;
+        settle(&mut graph.united, self.united);
+        settle(&mut graph.future, self.future);
+        settle(&mut graph.past, self.past);
+    }
+}
+
+pub struct Sequence<T>
+where
+    T: Clone + Eq + Ord + serde::Serialize + serde::de::DeserializeOwned,
+{
+    previous: Option<Hypergraph<T>>,
+    interval: usize,
+    elapsed: usize,
+    compressed: bool,
+}
+
+impl<T> Sequence<T>
+where
+    T: Clone + Eq + Ord + serde::Serialize + serde::de::DeserializeOwned,
+{
+    #[must_use]
+    pub fn new(interval: usize) -> Self {
+        Self {
+            previous: None,
+            interval: interval.max(1),
+            elapsed: 0,
+            compressed: false,
+        }
+    }
+
+    #[must_use]
+    pub fn compressed(mut self, enabled: bool) -> Self {
+        self.compressed = enabled;
+        self
+    }
+
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    pub fn capture(&mut self, graph: &Hypergraph<T>, trigger: &str) -> error::Result<Snapshot> {
+        let delta = self
+            .previous
+            .as_ref()
+            .filter(|_| self.elapsed < self.interval)
+            .and_then(|previous| Delta::between(previous, graph));
+
+        let snapshot = if let Some(delta) = delta {
+            self.elapsed += 1;
+            encode(&delta, format!("{DELTA}{trigger}"), self.compressed)?
+        } else {
+            self.elapsed = 1;
+            encode(graph, format!("{KEYFRAME}{trigger}"), self.compressed)?
+        };
+
+        self.previous = Some(graph.clone());
+        Ok(snapshot)
+    }
+}
 
 pub struct Assembler<'a, T>
 where
@@ -37,22 +152,7 @@ where
     type Output = error::Result<Snapshot>;
 
     fn assemble(self) -> Self::Output {
-        let serialized =
-            serde_json::to_vec(self.graph).map_err(|source| Error::Serialize { source })?;
-
-        let state = if self.compressed {
-            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-            encoder
-                .write_all(&serialized)
-                .map_err(|source| Error::Compress { source })?;
-            encoder
-                .finish()
-                .map_err(|source| Error::Compress { source })?
-        } else {
-            serialized
-        };
-
-        Ok(Snapshot::now(state, self.trigger.to_string()))
+        encode(self.graph, self.trigger.to_string(), self.compressed)
     }
 }
 
@@ -79,6 +179,75 @@ pub fn restore<T>(snapshot: &Snapshot) -> error::Result<Hypergraph<T>>
 where
     T: Clone + Eq + Ord + serde::Serialize + serde::de::DeserializeOwned,
 {
+    decode(snapshot)
+}
+
+pub fn reconstruct<T>(snapshots: &[Snapshot], index: usize) -> error::Result<Hypergraph<T>>
+where
+    T: Clone + Eq + Ord + serde::Serialize + serde::de::DeserializeOwned,
+{
+    let selected = snapshots.get(..=index).ok_or(Error::Index {
+        index,
+        count: snapshots.len(),
+    })?;
+
+    let start = selected
+        .iter()
+        .rposition(|snapshot| kind(snapshot) == Kind::Keyframe)
+        .ok_or(Error::Keyframe { index })?;
+
+    let mut graph = restore(&selected[start])?;
+    for snapshot in &selected[start + 1..] {
+        delta(snapshot)?.apply(&mut graph);
+    }
+
+    Ok(graph)
+}
+
+pub fn delta<T>(snapshot: &Snapshot) -> error::Result<Delta<T>>
+where
+    T: Clone + Eq + Ord + serde::Serialize + serde::de::DeserializeOwned,
+{
+    decode(snapshot)
+}
+
+#[must_use]
+pub fn kind(snapshot: &Snapshot) -> Kind {
+    if snapshot.trigger.starts_with(DELTA) {
+        Kind::Delta
+    } else {
+        Kind::Keyframe
+    }
+}
+
+#[must_use]
+pub fn trigger(snapshot: &Snapshot) -> &str {
+    let trigger = snapshot.trigger.as_str();
+    trigger
+        .strip_prefix(KEYFRAME)
+        .or_else(|| trigger.strip_prefix(DELTA))
+        .unwrap_or(trigger)
+}
+
+fn encode<S: Serialize>(value: &S, trigger: String, compressed: bool) -> error::Result<Snapshot> {
+    let serialized = serde_json::to_vec(value).map_err(|source| Error::Serialize { source })?;
+
+    let state = if compressed {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&serialized)
+            .map_err(|source| Error::Compress { source })?;
+        encoder
+            .finish()
+            .map_err(|source| Error::Compress { source })?
+    } else {
+        serialized
+    };
+
+    Ok(Snapshot::now(state, trigger))
+}
+
+fn decode<D: DeserializeOwned>(snapshot: &Snapshot) -> error::Result<D> {
     let decompressed = if snapshot.state.starts_with(&GZIP_HEADER) {
         let mut decoder = GzDecoder::new(&snapshot.state[..]);
         let mut decompressed = Vec::new();
@@ -92,3 +261,30 @@ where
 
     serde_json::from_slice(&decompressed).map_err(|source| Error::Deserialize { source })
 }
+
+fn changed<K, V>(previous: &BTreeMap<K, V>, current: &BTreeMap<K, V>) -> BTreeMap<K, Option<V>>
+where
+    K: Clone + Ord,
+    V: Clone + PartialEq,
+{
+    let removed = previous
+        .keys()
+        .filter(|key| !current.contains_key(*key))
+        .map(|key| (key.clone(), None));
+
+    current
+        .iter()
+        .filter(|(key, value)| previous.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), Some(value.clone())))
+        .chain(removed)
+        .collect()
+}
+
+fn settle<K: Ord, V>(map: &mut BTreeMap<K, V>, changes: BTreeMap<K, Option<V>>) {
+    for (key, value) in changes {
+        match value {
+            Some(value) => map.insert(key, value),
+            None => map.remove(&key),
+        };
+    }
+}
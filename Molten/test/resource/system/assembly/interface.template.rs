@@ -0,0 +1,14 @@
+fn diagnostics(source: String) -> usize {
+    interface::evaluate(&source).diagnostics.len()
+}
+
+fn spanned(source: String) -> bool {
+    interface::evaluate(&source)
+        .diagnostics
+        .iter()
+        .any(|diagnostic| !diagnostic.spans.is_empty())
+}
+
+fn inferred(source: String) -> bool {
+    !interface::evaluate(&source).particles.is_empty()
+}
@@ -0,0 +1,50 @@
+use component::graph::relation::Edge as Relation;
+use component::graph::relation::Related;
+use component::graph::state::wave::Wave;
+use component::hypergraph::{Hypergraph, Label};
+use system::hypergraph::debug::Debugger;
+use system::hypergraph::evaluate::Evaluate;
+
+fn step(mut graph: Hypergraph<usize>, refractions: Related<Wave<usize>>) -> Vec<Label> {
+    let mut debugger = Debugger::new(refractions);
+    utility::unwrap(debugger.step(&mut graph)).map_or_else(Vec::new, |step| step.edges)
+}
+
+fn settle(mut graph: Hypergraph<usize>, refractions: Related<Wave<usize>>) -> Vec<Vec<Label>> {
+    let mut debugger = Debugger::new(refractions);
+    let mut steps = Vec::new();
+    while let Some(step) = utility::unwrap(debugger.step(&mut graph)) {
+        steps.push(step.edges);
+    }
+    steps
+}
+
+fn equivalent(graph: Hypergraph<usize>, refractions: Related<Wave<usize>>) -> bool {
+    let mut stepped = graph.clone();
+    let mut debugger = Debugger::new(refractions.clone());
+    while utility::unwrap(debugger.step(&mut stepped)).is_some() {}
+
+    let mut fixed = graph;
+    utility::unwrap(fixed.fixed(refractions));
+
+    debugger.settled() && stepped == fixed
+}
+
+fn resume(
+    mut graph: Hypergraph<usize>,
+    refractions: Related<Wave<usize>>,
+    breakpoints: Vec<Relation<Wave<usize>>>,
+) -> Vec<usize> {
+    let mut debugger = Debugger::new(refractions);
+    let mut halts = Vec::new();
+    while !debugger.settled() {
+        let steps = utility::unwrap(debugger.resume(&mut graph, |candidate| {
+            breakpoints.contains(&candidate.relation)
+        }));
+        if steps.is_empty() {
+            break;
+        }
+        halts.push(steps.len());
+    }
+    halts
+}
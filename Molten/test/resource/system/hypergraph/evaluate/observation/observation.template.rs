@@ -29,9 +29,10 @@ fn capture<F: FnOnce() -> R, R>(channels: &[&str], emit: F) -> (R, Vec<Event>) {
         .iter()
         .map(|s| (*s).to_string())
         .collect::<Vec<_>>();
-    let predicate = std::sync::Arc::new(move |chs: &[stream::channel::Channel]| {
-        chs.iter().any(|c| filter.contains(&c.name))
-    });
+    let predicate =
+        std::sync::Arc::new(move |chs: &[stream::channel::Channel], _: stream::Level| {
+            chs.iter().any(|c| filter.contains(&c.name))
+        });
     let sink = Streamer::assembler(predicate).open();
     let result = emit();
     let events = sink.close().events().collect::<Vec<_>>();
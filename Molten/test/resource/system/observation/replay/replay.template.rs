@@ -0,0 +1,300 @@
+use assemble::Assemble;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, RwLock};
+
+use component::graph::relation::Edge as Relation;
+use component::graph::state::particle::Particle;
+use component::graph::state::wave::Wave;
+use component::hypergraph::{Hypergraph, Label};
+use layer::Streamer;
+use replay::Replay;
+use system::hypergraph::evaluate::Evaluate;
+use url::Url;
+
+fn capture<F: FnOnce()>(emit: F) -> Vec<stream::Update> {
+    let sink = Streamer::assembler(stream::predicate("hypergraph")).open();
+    emit();
+    sink.close().collect()
+}
+
+fn focused(graph: &mut Hypergraph<usize>, count: usize) -> Vec<Label> {
+    (0..count)
+        .map(|_| graph.focus(Particle::fundamental(1usize)))
+        .collect()
+}
+
+fn chained(graph: &mut Hypergraph<usize>, labels: &[Label]) {
+    let particle = Particle::fundamental(1usize);
+    let relation = Relation {
+        source: Wave::monochromatic(particle.clone()),
+        sink: Wave::monochromatic(particle),
+    };
+
+    for pair in labels.windows(2) {
+        graph
+            .translate(
+                BTreeSet::from([pair[0]]),
+                BTreeSet::from([pair[1]]),
+                relation.clone(),
+            )
+            .expect("translate failed");
+    }
+}
+
+fn evaluated(count: usize) -> (Hypergraph<usize>, Vec<stream::Update>) {
+    let mut graph = Hypergraph::default();
+    let updates = capture(|| {
+        let labels = focused(&mut graph, count);
+        chained(&mut graph, &labels);
+    });
+    (graph, updates)
+}
+
+fn replayed(count: usize) -> bool {
+    let (graph, updates) = evaluated(count);
+    let mut replay = Replay::<usize>::new(updates).expect("replay failed");
+
+    while replay.forward() {}
+    replay.graph() == &graph
+}
+
+fn rewound(count: usize) -> bool {
+    let (_, updates) = evaluated(count);
+    let mut replay = Replay::<usize>::new(updates).expect("replay failed");
+
+    let mut states = vec![replay.graph().clone()];
+    while replay.forward() {
+        states.push(replay.graph().clone());
+    }
+
+    while let Some(state) = states.pop() {
+        if replay.graph() != &state {
+            return false;
+        }
+        replay.backward();
+    }
+    replay.position() == 0
+}
+
+fn created(count: usize, label: usize) -> (bool, usize, usize) {
+    let (_, updates) = evaluated(count);
+    let mut replay = Replay::<usize>::new(updates).expect("replay failed");
+
+    let found = replay.created(Label(label));
+    (
+        found,
+        replay.graph().nodes.len(),
+        replay.graph().edges.len(),
+    )
+}
+
+fn resumed(count: usize) -> (bool, bool) {
+    let mut graph = Hypergraph::default();
+    let labels = focused(&mut graph, count);
+    let keyframe = graph.clone();
+
+    let mut updates = vec![stream::Update::Snapshot(
+        state::capture(&graph, "resume").expect("capture failed"),
+    )];
+    updates.extend(capture(|| chained(&mut graph, &labels)));
+
+    let mut replay = Replay::<usize>::new(updates).expect("replay failed");
+    while replay.forward() {}
+    let completed = replay.graph() == &graph;
+
+    while replay.position() > 1 {
+        replay.backward();
+    }
+    (completed, replay.graph() == &keyframe)
+}
+
+fn sought(port: u16, count: usize) -> (usize, usize) {
+    let (_, updates) = evaluated(count);
+    let mut replay = Replay::<usize>::new(updates).expect("replay failed");
+    while replay.forward() {}
+    let end = replay.timestamp().expect("empty replay");
+    replay.seek(0);
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let url = Url::parse(&format!("grpc://127.0.0.1:{port}")).unwrap();
+        let seeker = observer::seeker(Arc::new(Mutex::new(replay)));
+        let server = Arc::new(
+            peer::Assembler::new()
+                .address(url.clone())
+                .seeker(seeker)
+                .assemble(),
+        );
+
+        let serving = server.clone();
+        tokio::spawn(async move { serving.serve().await });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let client = peer::Assembler::new().assemble();
+        let handle = client.source(url).unwrap();
+        client.command(handle, stream::Command::Seek(end)).unwrap();
+
+        let update = tokio::time::timeout(std::time::Duration::from_secs(5), client.next()).await;
+        match update {
+            Ok(Some(stream::Update::Snapshot(snapshot))) => {
+                let graph: Hypergraph<usize> = state::restore(&snapshot).expect("restore failed");
+                (graph.nodes.len(), graph.edges.len())
+            }
+            _ => (0, 0),
+        }
+    })
+}
+
+fn snapshotted(name: String, count: usize, interval: usize) -> (Vec<String>, bool) {
+    let graph = Arc::new(RwLock::new(Hypergraph::<usize>::default()));
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let url = Url::parse(&format!("inproc://{name}")).unwrap();
+        let trigger = observer::trigger(graph.clone(), interval);
+        let server = Arc::new(
+            peer::Assembler::new()
+                .address(url.clone())
+                .trigger(trigger)
+                .assemble(),
+        );
+
+        let serving = server.clone();
+        tokio::spawn(async move { serving.serve().await });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let client = peer::Assembler::new().assemble();
+        let handle = client.source(url).unwrap();
+
+        let mut labels = Vec::new();
+        let mut states = Vec::new();
+        let mut snapshots = Vec::new();
+        for _ in 0..count {
+            {
+                let mut graph = graph.write().unwrap();
+                labels.push(graph.focus(Particle::fundamental(1usize)));
+                chained(&mut graph, &labels[labels.len().saturating_sub(2)..]);
+                states.push(graph.clone());
+            }
+
+            client.command(handle, stream::Command::Snapshot).unwrap();
+            let update =
+                tokio::time::timeout(std::time::Duration::from_secs(5), client.next()).await;
+            let Ok(Some(stream::Update::Snapshot(snapshot))) = update else {
+                break;
+            };
+            snapshots.push(snapshot);
+        }
+
+        (kinds(&snapshots), reconstructed(&snapshots, &states))
+    })
+}
+
+fn subscribed(name: String, count: usize, interval: usize) -> (Vec<String>, Vec<String>, bool) {
+    let graph = Arc::new(RwLock::new(Hypergraph::<usize>::default()));
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let url = Url::parse(&format!("inproc://{name}")).unwrap();
+        let server = served(&url, observer::trigger(graph.clone(), interval), 64).await;
+
+        let first = peer::Assembler::new().assemble();
+        let early = first.source(url.clone()).unwrap();
+        let second = peer::Assembler::new().assemble();
+        let late = second.source(url).unwrap();
+
+        let mut labels = Vec::new();
+        let (mut states, mut snapshots) = (Vec::new(), Vec::new());
+        for _ in 0..count {
+            states.push(grown(&graph, &mut labels));
+            first.command(early, stream::Command::Snapshot).unwrap();
+            snapshots.extend(snapshot(&first).await);
+        }
+
+        let current = graph.read().unwrap().clone();
+        second.command(late, stream::Command::Snapshot).unwrap();
+        let joined = snapshot(&second).await.into_iter().collect::<Vec<_>>();
+
+        states.push(grown(&graph, &mut labels));
+        first.command(early, stream::Command::Snapshot).unwrap();
+        snapshots.extend(snapshot(&first).await);
+
+        drop(server);
+        let consistent = reconstructed(&snapshots, &states)
+            && reconstructed(&joined, std::slice::from_ref(&current));
+        (kinds(&snapshots), kinds(&joined), consistent)
+    })
+}
+
+fn evicted(name: String, retention: usize, count: usize) -> (Vec<String>, bool) {
+    let graph = Arc::new(RwLock::new(Hypergraph::<usize>::default()));
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let url = Url::parse(&format!("inproc://{name}")).unwrap();
+        let server = served(&url, observer::trigger(graph.clone(), 64), retention).await;
+
+        let client = peer::Assembler::new().assemble();
+        let handle = client.source(url).unwrap();
+
+        let mut labels = Vec::new();
+        client.command(handle, stream::Command::Pause).unwrap();
+        for _ in 0..count {
+            grown(&graph, &mut labels);
+            client.command(handle, stream::Command::Snapshot).unwrap();
+        }
+        let mut states = vec![graph.read().unwrap().clone()];
+        client.command(handle, stream::Command::Resume).unwrap();
+        let mut snapshots = snapshot(&client).await.into_iter().collect::<Vec<_>>();
+
+        states.push(grown(&graph, &mut labels));
+        client.command(handle, stream::Command::Snapshot).unwrap();
+        snapshots.extend(snapshot(&client).await);
+
+        drop(server);
+        (kinds(&snapshots), reconstructed(&snapshots, &states))
+    })
+}
+
+async fn served(url: &Url, trigger: peer::Trigger, retention: usize) -> Arc<peer::Peer> {
+    let server = Arc::new(
+        peer::Assembler::new()
+            .address(url.clone())
+            .retention(retention)
+            .trigger(trigger)
+            .assemble(),
+    );
+
+    let serving = server.clone();
+    tokio::spawn(async move { serving.serve().await });
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    server
+}
+
+fn grown(graph: &RwLock<Hypergraph<usize>>, labels: &mut Vec<Label>) -> Hypergraph<usize> {
+    let mut graph = graph.write().unwrap();
+    labels.push(graph.focus(Particle::fundamental(1usize)));
+    chained(&mut graph, &labels[labels.len().saturating_sub(2)..]);
+    graph.clone()
+}
+
+async fn snapshot(client: &peer::Peer) -> Option<stream::Snapshot> {
+    let update = tokio::time::timeout(std::time::Duration::from_secs(5), client.next()).await;
+    match update {
+        Ok(Some(stream::Update::Snapshot(snapshot))) => Some(snapshot),
+        _ => None,
+    }
+}
+
+fn kinds(snapshots: &[stream::Snapshot]) -> Vec<String> {
+    snapshots
+        .iter()
+        .map(|snapshot| match state::kind(snapshot) {
+            state::Kind::Keyframe => "keyframe".to_string(),
+            state::Kind::Delta => "delta".to_string(),
+        })
+        .collect()
+}
+
+fn reconstructed(snapshots: &[stream::Snapshot], states: &[Hypergraph<usize>]) -> bool {
+    snapshots.len() == states.len()
+        && states.iter().enumerate().all(|(index, graph)| {
+            state::reconstruct::<usize>(snapshots, index).is_ok_and(|rebuilt| &rebuilt == graph)
+        })
+}
@@ -1,6 +1,8 @@
 use assemble::Assemble;
-use hypergraph::Hypergraph;
-use state::{assembler, capture, restore};
+use hypergraph::{Hypergraph, Label, Node};
+use particle::Particle;
+use state::{Kind, Sequence, assembler, capture, kind, reconstruct, restore};
+use std::collections::BTreeSet;
 
 fn roundtrip(graph: Hypergraph<usize>) -> bool {
     let snapshot = capture(&graph, "test").expect("capture failed");
@@ -32,3 +34,64 @@ fn timestamp(graph: Hypergraph<usize>) -> bool {
     let snapshot = capture(&graph, "test").expect("capture failed");
     snapshot.timestamp > 0
 }
+
+fn grown(size: usize) -> Hypergraph<usize> {
+    let mut graph = Hypergraph::default();
+    for index in 0..size {
+        let label = Label(index);
+        graph.nodes.insert(Node {
+            label,
+            particle: Particle::fundamental(index + 1),
+        });
+        graph.refractions.insert(label, label);
+        graph.world.insert(label, 0);
+        graph
+            .united
+            .entry(Label(0))
+            .or_insert_with(BTreeSet::new)
+            .insert(label);
+        graph.future.insert(label, BTreeSet::new());
+        graph.past.insert(label, BTreeSet::new());
+    }
+    graph.particles = size;
+    graph.worlds = usize::from(size > 0);
+    graph
+}
+
+fn sequenced(sizes: &[usize], interval: usize) -> Vec<stream::Snapshot> {
+    let mut sequence = Sequence::new(interval);
+    sizes
+        .iter()
+        .map(|&size| {
+            sequence
+                .capture(&grown(size), "step")
+                .expect("capture failed")
+        })
+        .collect()
+}
+
+fn kinds(sizes: Vec<usize>, interval: usize) -> Vec<String> {
+    sequenced(&sizes, interval)
+        .iter()
+        .map(|snapshot| match kind(snapshot) {
+            Kind::Keyframe => format!("keyframe {}", state::trigger(snapshot)),
+            Kind::Delta => format!("delta {}", state::trigger(snapshot)),
+        })
+        .collect()
+}
+
+fn reconstructed(sizes: Vec<usize>, interval: usize, index: usize) -> bool {
+    let snapshots = sequenced(&sizes, interval);
+    let graph: Hypergraph<usize> = reconstruct(&snapshots, index).expect("reconstruct failed");
+    graph == grown(sizes[index])
+}
+
+fn orphaned(sizes: Vec<usize>, interval: usize) -> bool {
+    let snapshots = sequenced(&sizes, interval);
+    reconstruct::<usize>(&snapshots[1..], 0).is_err()
+}
+
+fn delta(size: usize) -> bool {
+    let snapshots = sequenced(&[size, size + 1], 8);
+    snapshots[1].state.len() < snapshots[0].state.len()
+}
@@ -0,0 +1,38 @@
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const ECHO: &str =
+    "[Echo(Data.Format.Unicode, Binary.Width(8), value)] (Stream.Sink.Console(value))";
+
+#[wasm_bindgen_test]
+fn evaluate() {
+    let response = interface::evaluate(ECHO);
+
+    assert!(response.diagnostics.is_empty());
+    assert!(!response.particles.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn diagnose() {
+    let response = interface::evaluate("[Echo(Data.Format.Unicode]");
+
+    assert!(response.particles.is_empty());
+    assert_eq!(response.diagnostics.len(), 1);
+    assert!(!response.diagnostics[0].spans.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn json() {
+    let response = serde_json::from_str::<interface::Response>(&interface::json(ECHO))
+        .expect("valid response json");
+
+    assert_eq!(response, interface::evaluate(ECHO));
+}
+
+#[wasm_bindgen_test]
+fn malformed() {
+    let response =
+        serde_json::from_str::<interface::Response>(&interface::json("[Echo(Data.Format.Unicode]"))
+            .expect("valid response json");
+
+    assert_eq!(response.diagnostics.len(), 1);
+}
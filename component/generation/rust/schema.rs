@@ -21,6 +21,13 @@ pub struct Function<T = Value> {
     pub parameters: HashMap<String, T>,
     #[serde(default)]
     pub returns: HashMap<String, T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<Tolerance>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub types: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generate: Option<Generate>,
+    #[serde(default)]
     pub cases: Vec<Case<T>>,
 }
 
@@ -33,4 +40,68 @@ pub struct Case<T = Value> {
     pub parameters: HashMap<String, T>,
     #[serde(default)]
     pub returns: HashMap<String, T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panics: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Failure>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<Tolerance>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub types: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Failure {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Tolerance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub absolute: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ulps: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unordered: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subset: Option<bool>,
+}
+
+impl Tolerance {
+    #[must_use]
+    pub fn or(&self, defaults: &Self) -> Self {
+        Self {
+            absolute: self.absolute.or(defaults.absolute),
+            relative: self.relative.or(defaults.relative),
+            ulps: self.ulps.or(defaults.ulps),
+            unordered: self.unordered.or(defaults.unordered),
+            subset: self.subset.or(defaults.subset),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Generate {
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default = "Generate::count")]
+    pub count: usize,
+    pub parameters: HashMap<String, Value>,
+    #[serde(default)]
+    pub invariants: Vec<Invariant>,
+}
+
+impl Generate {
+    fn count() -> usize {
+        100
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Invariant {
+    Reference(String),
+    Relation(String),
 }
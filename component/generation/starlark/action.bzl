@@ -90,7 +90,7 @@ generate = rule(
 def _execute_impl(ctx):
     output = ctx.outputs.output
 
-    arguments = []
+    arguments = [ctx.expand_location(argument, ctx.attr.data) for argument in ctx.attr.arguments]
     for key, value in ctx.attr.parameters.items():
         arguments.extend(["--" + key, value])
 
@@ -125,6 +125,7 @@ execute = rule(
             executable = True,
             cfg = "exec",
         ),
+        "arguments": attr.string_list(),
         "parameters": attr.string_dict(),
         "data": attr.label_list(allow_files = True),
         "output": attr.output(mandatory = True),
@@ -0,0 +1,45 @@
+use miette::Diagnostic;
+use std::fmt;
+
+pub struct Code<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Diagnostic + ?Sized> fmt::Display for Code<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.code() {
+            Some(code) => write!(f, "{code}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Diagnostic + ?Sized> fmt::Debug for Code<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+pub struct Message<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Diagnostic + ?Sized> fmt::Display for Message<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
+
+impl<T: Diagnostic + ?Sized> fmt::Debug for Message<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[must_use]
+#[inline]
+pub fn code<T: Diagnostic + ?Sized>(error: &T) -> Code<'_, T> {
+    Code(error)
+}
+
+#[must_use]
+#[inline]
+pub fn message<T: Diagnostic + ?Sized>(error: &T) -> Message<'_, T> {
+    Message(error)
+}
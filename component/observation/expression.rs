@@ -1,22 +1,35 @@
 use std::sync::Arc;
 
 use channel::Channel;
-use stream::Predicate;
+use stream::{Level, Predicate};
 
 use tag::Expression;
 
 pub trait Filterable {
-    fn channels(&self, channels: &[Channel]) -> bool;
+    fn channels(&self, channels: &[Channel], level: Level) -> bool;
     fn predicate(self) -> Predicate;
 }
 
 impl Filterable for Expression {
-    fn channels(&self, channels: &[Channel]) -> bool {
-        let names = channels.iter().map(|c| &c.name).collect::<Vec<_>>();
-        self.evaluate(&names)
+    fn channels(&self, channels: &[Channel], level: Level) -> bool {
+        let weighted = channels
+            .iter()
+            .map(|c| (c.name.as_str(), c.weight))
+            .collect::<Vec<_>>();
+        self.matches(&weighted, Some(severity(level)))
     }
 
     fn predicate(self) -> Predicate {
-        Arc::new(move |channels| self.channels(channels))
+        Arc::new(move |channels, level| self.channels(channels, level))
+    }
+}
+
+fn severity(level: Level) -> tag::Level {
+    match level {
+        Level::Trace => tag::Level::Trace,
+        Level::Debug => tag::Level::Debug,
+        Level::Info => tag::Level::Info,
+        Level::Warn => tag::Level::Warn,
+        Level::Error => tag::Level::Error,
     }
 }
@@ -1,3 +1,3 @@
 pub fn parse(input: &str) -> Result<expression::Expression, expression::Sourced> {
-    expression::parse(input)
+    expression::channels(input)
 }
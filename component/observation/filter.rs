@@ -4,7 +4,7 @@ use tracing_subscriber::Layer;
 use tracing_subscriber::layer::Context;
 use tracing_subscriber::registry::LookupSpan;
 
-use stream::Predicate;
+use stream::{Level, Predicate};
 use visitor::Visitor;
 
 pub struct Filter {
@@ -31,7 +31,7 @@ where
         let dominated = match visitor.channels {
             None => true,
             Some(_) => match visitor.channels() {
-                Ok(c) => (self.predicate)(&c),
+                Ok(c) => (self.predicate)(&c, Level::from(*attrs.metadata().level())),
                 Err(e) => {
                     tracing::warn!("{:?}", miette::Report::new(e));
                     return;
@@ -1,5 +1,5 @@
 use dashmap::DashMap;
-use std::sync::OnceLock;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::mpsc;
 use tracing::Subscriber;
@@ -10,6 +10,9 @@ use tracing_subscriber::registry::LookupSpan;
 
 use assemble::Assemble;
 use channel::Channel;
+use metric::{Aggregator, Observation};
+use redaction::Redaction;
+use sample::Sample;
 use stream::{
     Begin, End, Event, Identifier, Level, Lifecycle, Metadata, Predicate, Span, Update, generate,
 };
@@ -51,6 +54,8 @@ pub type Receiver = mpsc::Receiver<Update>;
 pub struct Assembler {
     predicate: Predicate,
     backpressure: Backpressure,
+    sample: Sample,
+    redaction: Redaction,
 }
 
 impl Assembler {
@@ -60,6 +65,18 @@ impl Assembler {
         self
     }
 
+    #[must_use]
+    pub fn sample(mut self, sample: Sample) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    #[must_use]
+    pub fn redact(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
     #[must_use]
     pub fn open(self) -> Sink {
         let (streamer, receiver) = self.assemble();
@@ -102,11 +119,15 @@ impl Assemble for Assembler {
             sender,
             predicate: self.predicate,
             backpressure: self.backpressure,
+            sample: self.sample,
+            redaction: self.redaction,
             spans: DashMap::new(),
-            trace: OnceLock::new(),
-            dropped: AtomicU64::new(0),
+            discarded: DashMap::new(),
+            trace: generate(),
+            roots: AtomicU64::new(0),
+            dropped: Arc::new(AtomicU64::new(0)),
+            metrics: Aggregator::default(),
         };
-        streamer.trace.get_or_init(generate);
         (streamer, receiver)
     }
 }
@@ -115,9 +136,14 @@ pub struct Streamer {
     sender: Sender,
     predicate: Predicate,
     backpressure: Backpressure,
+    sample: Sample,
+    redaction: Redaction,
     spans: DashMap<tracing::span::Id, State>,
-    trace: OnceLock<u64>,
-    dropped: AtomicU64,
+    discarded: DashMap<tracing::span::Id, ()>,
+    trace: u64,
+    roots: AtomicU64,
+    dropped: Arc<AtomicU64>,
+    metrics: Aggregator,
 }
 
 impl Streamer {
@@ -126,6 +152,8 @@ impl Streamer {
         Assembler {
             predicate,
             backpressure: Backpressure::default(),
+            sample: Sample::default(),
+            redaction: Redaction::default(),
         }
     }
 
@@ -145,11 +173,52 @@ impl Streamer {
     }
 
     fn identifier(&self, id: &tracing::span::Id, parent: Option<&tracing::span::Id>) -> Identifier {
-        let trace = self.trace.get().copied().unwrap_or_else(generate);
         let span = id.into_u64();
-        match parent.and_then(|p| self.spans.get(p)) {
-            Some(state) => Identifier::child(trace, span, state.id.span),
-            None => Identifier::root(trace, span),
+        if let Some(state) = parent.and_then(|p| self.spans.get(p)) {
+            return Identifier::child(state.id.trace, span, state.id.span);
+        }
+
+        let root = self.roots.fetch_add(1, Ordering::Relaxed);
+        Identifier::root(self.trace.wrapping_add(root), span)
+    }
+
+    fn sampled<S>(&self, id: &tracing::span::Id, ctx: &Context<'_, S>) -> Option<bool>
+    where
+        S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+    {
+        ctx.span_scope(id)?.skip(1).find_map(|ancestor| {
+            if self.spans.contains_key(&ancestor.id()) {
+                Some(true)
+            } else if self.discarded.contains_key(&ancestor.id()) {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn measure(&self, collector: &Visitor, level: Level) {
+        let channels = match collector.channels() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!("{:?}", miette::Report::new(e));
+                return;
+            }
+        };
+        if !(self.predicate)(&channels, level) {
+            return;
+        }
+
+        if let Some(observation) = Observation::parse(&collector.fields)
+            && let Some(metric) = self.metrics.record(observation, channels)
+        {
+            self.emit(Update::Metric(metric));
+        }
+    }
+
+    fn settle(&self) {
+        for metric in self.metrics.due() {
+            self.emit(Update::Metric(metric));
         }
     }
 
@@ -157,6 +226,21 @@ impl Streamer {
     pub fn dropped(&self) -> u64 {
         self.dropped.load(Ordering::Relaxed)
     }
+
+    #[must_use]
+    pub fn counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.dropped)
+    }
+}
+
+impl Drop for Streamer {
+    fn drop(&mut self) {
+        for metric in self.metrics.flush() {
+            if self.sender.try_send(Update::Metric(metric)).is_err() {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
 }
 
 impl<S> Layer<S> for Streamer
@@ -177,7 +261,7 @@ where
             }
         };
 
-        if !(self.predicate)(&channels) {
+        if !(self.predicate)(&channels, Level::from(*meta.level())) {
             return;
         }
 
@@ -186,6 +270,17 @@ where
             .and_then(|mut scope| scope.nth(1).map(|span| span.id()));
 
         let identifier = self.identifier(id, parent.as_ref());
+        let admitted = if self.sample.inherited() {
+            self.sampled(id, &ctx)
+                .unwrap_or_else(|| self.sample.admit(&channels, identifier))
+        } else {
+            self.sample.admit(&channels, identifier)
+        };
+        if !admitted {
+            self.discarded.insert(id.clone(), ());
+            return;
+        }
+
         let metadata = metadata(meta);
 
         self.spans.insert(
@@ -201,13 +296,15 @@ where
             id: identifier,
             metadata,
             channels,
-            lifecycle: Lifecycle::Begin(Begin::now(collector.fields)),
+            lifecycle: Lifecycle::Begin(Begin::now(self.redaction.apply(collector.fields))),
         };
 
         self.emit(Update::Span(span));
     }
 
     fn on_close(&self, id: tracing::span::Id, _ctx: Context<'_, S>) {
+        self.settle();
+        self.discarded.remove(&id);
         if let Some((_, state)) = self.spans.remove(&id) {
             let span = Span {
                 id: state.id,
@@ -220,10 +317,16 @@ where
     }
 
     fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        self.settle();
         let meta = event.metadata();
         let mut collector = Visitor::default();
         event.record(&mut collector);
 
+        if meta.target() == metric::TARGET {
+            self.measure(&collector, Level::from(*meta.level()));
+            return;
+        }
+
         if collector.channels.is_some() {
             let channels = match collector.channels() {
                 Ok(c) => c,
@@ -232,7 +335,15 @@ where
                     return;
                 }
             };
-            if !(self.predicate)(&channels) {
+            if !(self.predicate)(&channels, Level::from(*meta.level())) {
+                return;
+            }
+
+            let discarded = ctx.event_span(event).is_some_and(|span| {
+                self.discarded.contains_key(&span.id())
+                    || self.sampled(&span.id(), &ctx) == Some(false)
+            });
+            if discarded {
                 return;
             }
 
@@ -240,7 +351,8 @@ where
                 .event_span(event)
                 .map(|span| self.spans.get(&span.id()).map_or(0, |state| state.id.span));
 
-            let observation = Event::now(parent, metadata(meta), channels, collector.fields);
+            let fields = self.redaction.apply(collector.fields);
+            let observation = Event::now(parent, metadata(meta), channels, fields);
             self.emit(Update::Event(observation));
             return;
         }
@@ -255,7 +367,8 @@ where
 
         let parent = span.map(|s| self.spans.get(&s.id()).map_or(0, |state| state.id.span));
 
-        let observation = Event::now(parent, metadata(meta), vec![], collector.fields);
+        let fields = self.redaction.apply(collector.fields);
+        let observation = Event::now(parent, metadata(meta), vec![], fields);
         self.emit(Update::Event(observation));
     }
 }
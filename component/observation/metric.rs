@@ -0,0 +1,173 @@
+use channel::Channel;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use stream::{Field, Histogram, Measure, Metric, Value};
+
+pub const TARGET: &str = "observation::metric";
+
+pub const INTERVAL: Duration = Duration::from_millis(250);
+
+pub const BOUNDS: [u64; 11] = [
+    1, 4, 16, 64, 256, 1024, 4096, 16384, 65536, 262_144, 1_048_576,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl Kind {
+    #[must_use]
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "counter" => Some(Self::Counter),
+            "gauge" => Some(Self::Gauge),
+            "histogram" => Some(Self::Histogram),
+            _ => None,
+        }
+    }
+
+    fn measure(self) -> Measure {
+        match self {
+            Self::Counter => Measure::Counter(0),
+            Self::Gauge => Measure::Gauge(0),
+            Self::Histogram => Measure::Histogram(Histogram {
+                bounds: BOUNDS.to_vec(),
+                counts: vec![0; BOUNDS.len() + 1],
+                count: 0,
+                sum: 0,
+            }),
+        }
+    }
+
+    fn matches(self, measure: &Measure) -> bool {
+        matches!(
+            (self, measure),
+            (Self::Counter, Measure::Counter(_))
+                | (Self::Gauge, Measure::Gauge(_))
+                | (Self::Histogram, Measure::Histogram(_))
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    pub kind: Kind,
+    pub name: String,
+    pub value: i64,
+}
+
+impl Observation {
+    #[must_use]
+    pub fn parse(fields: &[Field]) -> Option<Self> {
+        let kind = match stream::field(fields, "kind")? {
+            Value::Text(kind) => Kind::parse(kind)?,
+            _ => return None,
+        };
+        let name = match stream::field(fields, "name")? {
+            Value::Text(name) => name.clone(),
+            _ => return None,
+        };
+        let value = match stream::field(fields, "value")? {
+            Value::Signed(value) => *value,
+            Value::Unsigned(value) => i64::try_from(*value).unwrap_or(i64::MAX),
+            _ => return None,
+        };
+        Some(Self { kind, name, value })
+    }
+}
+
+struct Series {
+    measure: Measure,
+    emitted: Option<Instant>,
+    pending: bool,
+}
+
+impl Series {
+    fn due(&self, now: Instant) -> bool {
+        self.emitted
+            .is_none_or(|emitted| now.duration_since(emitted) >= INTERVAL)
+    }
+
+    fn publish(&mut self, name: String, channels: Vec<Channel>, now: Instant) -> Metric {
+        self.emitted = Some(now);
+        self.pending = false;
+        Metric::now(name, channels, self.measure.clone())
+    }
+}
+
+#[derive(Default)]
+pub struct Aggregator {
+    series: Mutex<BTreeMap<(String, Vec<Channel>), Series>>,
+    pending: AtomicBool,
+}
+
+impl Aggregator {
+    pub fn record(&self, observation: Observation, channels: Vec<Channel>) -> Option<Metric> {
+        let mut series = self.series.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = series
+            .entry((observation.name.clone(), channels.clone()))
+            .or_insert_with(|| Series {
+                measure: observation.kind.measure(),
+                emitted: None,
+                pending: false,
+            });
+        if !observation.kind.matches(&entry.measure) {
+            entry.measure = observation.kind.measure();
+        }
+
+        accumulate(&mut entry.measure, observation.value);
+        let now = Instant::now();
+        if entry.due(now) {
+            return Some(entry.publish(observation.name, channels, now));
+        }
+
+        entry.pending = true;
+        self.pending.store(true, Ordering::Relaxed);
+        None
+    }
+
+    pub fn due(&self) -> Vec<Metric> {
+        let now = Instant::now();
+        self.collect(|series| series.due(now))
+    }
+
+    pub fn flush(&self) -> Vec<Metric> {
+        self.collect(|_| true)
+    }
+
+    fn collect(&self, due: impl Fn(&Series) -> bool) -> Vec<Metric> {
+        if !self.pending.load(Ordering::Relaxed) {
+            return Vec::new();
+        }
+
+        let mut series = self.series.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        let metrics = series
+            .iter_mut()
+            .filter(|(_, series)| series.pending && due(series))
+            .map(|((name, channels), series)| series.publish(name.clone(), channels.clone(), now))
+            .collect();
+        let pending = series.values().any(|series| series.pending);
+        self.pending.store(pending, Ordering::Relaxed);
+        metrics
+    }
+}
+
+fn accumulate(measure: &mut Measure, value: i64) {
+    let magnitude = u64::try_from(value).unwrap_or(0);
+    match measure {
+        Measure::Counter(total) => *total = total.saturating_add(magnitude),
+        Measure::Gauge(current) => *current = value,
+        Measure::Histogram(histogram) => {
+            let bucket = histogram.bounds.partition_point(|bound| *bound < magnitude);
+            histogram.counts[bucket] += 1;
+            histogram.count += 1;
+            histogram.sum = histogram.sum.saturating_add(magnitude);
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 pub struct Guard(PhantomData<()>);
 
 pub use category;
+pub use diagnostic;
 pub use serialize;
 
 #[inline(always)]
@@ -18,21 +19,45 @@ macro_rules! enter {
 
 #[macro_export]
 macro_rules! channels {
-    ($($channel:ident $(: $weight:literal)?),* $(,)?) => {
+    ($($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?) => {
         ""
     };
 }
 
 #[macro_export]
 macro_rules! event {
-    (channels = [$($channel:ident $(: $weight:literal)?),* $(,)?], $($field:tt)*) => {{
-        $(let _ = stringify!($channel);)*
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $($field:tt)*) => {{
+        $(let _ = stringify!($channel $(. $segment)*);)*
         $crate::event!($($field)*)
     }};
     ($($key:ident = $value:expr),* $(,)?) => {{ $(let _ = &$value;)* }};
     ($($arg:tt)*) => {{ let _ = format_args!($($arg)*); }};
 }
 
+#[macro_export]
+macro_rules! counter {
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $name:ident $(= $value:expr)? $(,)?) => {{
+        $(let _ = stringify!($channel $(. $segment)*);)*
+        $(let _ = &$value;)?
+    }};
+}
+
+#[macro_export]
+macro_rules! gauge {
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $name:ident = $value:expr $(,)?) => {{
+        $(let _ = stringify!($channel $(. $segment)*);)*
+        let _ = &$value;
+    }};
+}
+
+#[macro_export]
+macro_rules! histogram {
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $name:ident = $value:expr $(,)?) => {{
+        $(let _ = stringify!($channel $(. $segment)*);)*
+        let _ = &$value;
+    }};
+}
+
 #[macro_export]
 macro_rules! trace {
     ($($key:ident = $value:expr),* $(,)?) => {{ $(let _ = &$value;)* }};
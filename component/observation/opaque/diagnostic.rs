@@ -0,0 +1,42 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+pub struct Code<'a, T: ?Sized>(PhantomData<&'a T>);
+
+impl<T: ?Sized> fmt::Display for Code<'_, T> {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Code<'_, T> {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+pub struct Message<'a, T: ?Sized>(PhantomData<&'a T>);
+
+impl<T: ?Sized> fmt::Display for Message<'_, T> {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Message<'_, T> {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+#[must_use]
+#[inline]
+pub fn code<T: ?Sized>(_: &T) -> Code<'_, T> {
+    Code(PhantomData)
+}
+
+#[must_use]
+#[inline]
+pub fn message<T: ?Sized>(_: &T) -> Message<'_, T> {
+    Message(PhantomData)
+}
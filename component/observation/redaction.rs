@@ -0,0 +1,213 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::OnceLock;
+use stream::{Field, Value};
+
+const KEY: usize = 32;
+const UNKEYED: &str = "[unkeyed]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Drop,
+    Hash,
+    Truncate(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub pattern: String,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Redaction {
+    pub rules: Vec<Rule>,
+    pub limit: Option<usize>,
+    pub salt: Option<String>,
+}
+
+#[derive(thiserror::Error, miette::Diagnostic, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("invalid redaction rule: {rule}")]
+    #[diagnostic(
+        code(observation::redaction::rule),
+        help("use pattern:action pairs (e.g., password:drop,token*:hash,source:truncate(64))")
+    )]
+    Rule { rule: String },
+
+    #[error("invalid redaction action: {action}")]
+    #[diagnostic(
+        code(observation::redaction::action),
+        help("use drop, hash or truncate(N)")
+    )]
+    Action { action: String },
+}
+
+impl Redaction {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let rules = input
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty() && !segment.starts_with('#'))
+            .map(|segment| {
+                let (pattern, action) = segment.rsplit_once(':').ok_or_else(|| Error::Rule {
+                    rule: segment.to_string(),
+                })?;
+                Ok(Rule {
+                    pattern: pattern.trim().to_string(),
+                    action: action.trim().parse()?,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            rules,
+            limit: None,
+            salt: None,
+        })
+    }
+
+    #[must_use]
+    pub fn limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    #[must_use]
+    pub fn salt(mut self, salt: Option<String>) -> Self {
+        self.salt = salt;
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.limit.is_none()
+    }
+
+    #[must_use]
+    pub fn apply(&self, fields: Vec<Field>) -> Vec<Field> {
+        if self.is_empty() {
+            return fields;
+        }
+        fields
+            .into_iter()
+            .filter_map(|field| self.field(field))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn field(&self, field: Field) -> Option<Field> {
+        let action = self
+            .rules
+            .iter()
+            .find(|rule| matches(&rule.pattern, &field.name))
+            .map(|rule| rule.action);
+
+        let value = match (action, self.limit) {
+            (Some(Action::Drop), _) => return None,
+            (Some(Action::Hash), _) => Value::Text(self.hash(&field.value)),
+            (Some(Action::Truncate(length)), _) => truncate(field.value, length),
+            (None, Some(limit)) => cap(field.value, limit),
+            (None, None) => field.value,
+        };
+
+        Some(Field {
+            name: field.name,
+            value,
+        })
+    }
+
+    fn hash(&self, value: &Value) -> String {
+        let bytes = match value {
+            Value::Text(text) => text.as_bytes().to_vec(),
+            Value::Serialized(bytes) => bytes.clone(),
+            other => other.to_string().into_bytes(),
+        };
+        let key = match &self.salt {
+            Some(salt) => salt.as_bytes(),
+            None => match process() {
+                Some(key) => key.as_slice(),
+                None => return UNKEYED.to_string(),
+            },
+        };
+        let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(key) else {
+            return UNKEYED.to_string();
+        };
+        mac.update(&bytes);
+        let digest = mac.finalize().into_bytes();
+        let prefix = u64::from_be_bytes(digest[..8].try_into().unwrap_or_default());
+        format!("#{prefix:016x}")
+    }
+}
+
+impl std::str::FromStr for Action {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "drop" => Ok(Self::Drop),
+            "hash" => Ok(Self::Hash),
+            _ => input
+                .strip_prefix("truncate(")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|length| length.trim().parse().ok())
+                .map(Self::Truncate)
+                .ok_or_else(|| Error::Action {
+                    action: input.to_string(),
+                }),
+        }
+    }
+}
+
+fn matches(pattern: &str, name: &str) -> bool {
+    let mut segments = pattern.split('*');
+    let first = segments.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let segments = segments.collect::<Vec<_>>();
+    let Some((last, middle)) = segments.split_last() else {
+        return rest.is_empty();
+    };
+    for segment in middle {
+        match rest.find(segment) {
+            Some(index) => rest = &rest[index + segment.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+fn process() -> Option<&'static [u8; KEY]> {
+    static PROCESS: OnceLock<Option<[u8; KEY]>> = OnceLock::new();
+    PROCESS
+        .get_or_init(|| {
+            let mut key = [0u8; KEY];
+            getrandom::fill(&mut key).ok().map(|()| key)
+        })
+        .as_ref()
+}
+
+fn truncate(value: Value, length: usize) -> Value {
+    match value {
+        Value::Text(text) if text.chars().count() > length => Value::Text(format!(
+            "{}…",
+            text.chars().take(length).collect::<String>()
+        )),
+        Value::Serialized(bytes) if bytes.len() > length => {
+            Value::Text(format!("{}…", String::from_utf8_lossy(&bytes[..length])))
+        }
+        other => other,
+    }
+}
+
+fn cap(value: Value, limit: usize) -> Value {
+    match value {
+        Value::Serialized(bytes) if bytes.len() > limit => {
+            Value::Text(format!("[{} bytes elided]", bytes.len()))
+        }
+        text @ Value::Text(_) => truncate(text, limit),
+        other => other,
+    }
+}
@@ -0,0 +1,106 @@
+use channel::Channel;
+use std::collections::BTreeMap;
+use stream::Identifier;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Sample {
+    #[default]
+    All,
+    Weight(u8),
+    Rate(f64),
+    Channels(BTreeMap<String, f64>),
+}
+
+#[derive(thiserror::Error, miette::Diagnostic, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("invalid sampling weight: {weight}")]
+    #[diagnostic(
+        code(observation::sample::weight),
+        help("use weight>=N where N is between 0 and 255")
+    )]
+    Weight { weight: String },
+
+    #[error("invalid sampling rate: {rate}")]
+    #[diagnostic(
+        code(observation::sample::rate),
+        help("use a rate between 0 and 1 (e.g., 0.25 or core:0.01,query:0.5)")
+    )]
+    Rate { rate: String },
+}
+
+impl Sample {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() || trimmed == "all" {
+            return Ok(Self::All);
+        }
+
+        if let Some(weight) = trimmed.strip_prefix("weight>=") {
+            return weight
+                .trim()
+                .parse()
+                .map(Self::Weight)
+                .map_err(|_| Error::Weight {
+                    weight: weight.to_string(),
+                });
+        }
+
+        if !trimmed.contains(':') {
+            return rate(trimmed).map(Self::Rate);
+        }
+
+        trimmed
+            .split(',')
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                let (name, value) = segment.split_once(':').ok_or_else(|| Error::Rate {
+                    rate: segment.to_string(),
+                })?;
+                Ok((name.trim().to_string(), rate(value.trim())?))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map(Self::Channels)
+    }
+
+    #[must_use]
+    pub fn inherited(&self) -> bool {
+        !matches!(self, Self::Weight(_))
+    }
+
+    #[must_use]
+    pub fn admit(&self, channels: &[Channel], identifier: Identifier) -> bool {
+        match self {
+            Self::All => true,
+            Self::Weight(minimum) => channels.iter().any(|c| c.weight >= *minimum),
+            Self::Rate(rate) => fraction(identifier) < *rate,
+            Self::Channels(rates) => {
+                let rate = channels
+                    .iter()
+                    .map(|c| rates.get(&c.name).copied().unwrap_or(1.0))
+                    .reduce(f64::max)
+                    .unwrap_or(1.0);
+                fraction(identifier) < rate
+            }
+        }
+    }
+}
+
+fn rate(input: &str) -> Result<f64, Error> {
+    input
+        .parse::<f64>()
+        .ok()
+        .filter(|r| (0.0..=1.0).contains(r))
+        .ok_or_else(|| Error::Rate {
+            rate: input.to_string(),
+        })
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn fraction(identifier: Identifier) -> f64 {
+    let mut mixed = identifier.trace;
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    mixed ^= mixed >> 31;
+    (mixed >> 11) as f64 / (1u64 << 53) as f64
+}
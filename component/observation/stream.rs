@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-pub type Predicate = Arc<dyn Fn(&[Channel]) -> bool + Send + Sync>;
+pub type Predicate = Arc<dyn Fn(&[Channel], Level) -> bool + Send + Sync>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Identifier {
@@ -175,11 +175,48 @@ impl Snapshot {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub bounds: Vec<u64>,
+    pub counts: Vec<u64>,
+    pub count: u64,
+    pub sum: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Measure {
+    Counter(u64),
+    Gauge(i64),
+    Histogram(Histogram),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metric {
+    pub name: String,
+    pub channels: Vec<Channel>,
+    pub timestamp: u64,
+    pub measure: Measure,
+}
+
+impl Metric {
+    #[inline]
+    #[must_use]
+    pub fn now(name: String, channels: Vec<Channel>, measure: Measure) -> Self {
+        Self {
+            name,
+            channels,
+            timestamp: timestamp(),
+            measure,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Update {
     Span(Span),
     Event(Event),
     Snapshot(Snapshot),
+    Metric(Metric),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -228,6 +265,13 @@ pub trait Updates: Iterator<Item = Update> + Sized {
             _ => None,
         })
     }
+
+    fn metrics(self) -> impl Iterator<Item = Metric> {
+        self.filter_map(|u| match u {
+            Update::Metric(m) => Some(m),
+            _ => None,
+        })
+    }
 }
 
 impl<I: Iterator<Item = Update>> Updates for I {}
@@ -235,7 +279,7 @@ impl<I: Iterator<Item = Update>> Updates for I {}
 #[must_use]
 pub fn predicate(name: &str) -> Predicate {
     let name = name.to_string();
-    Arc::new(move |channels: &[_]| channels.iter().any(|c| c.name == name))
+    Arc::new(move |channels: &[_], _| channels.iter().any(|c| c.name == name))
 }
 
 #[must_use]
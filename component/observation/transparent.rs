@@ -1,23 +1,63 @@
 pub use category;
 pub use collector;
+pub use diagnostic;
+pub use metric;
 pub use serialize;
 pub use span;
 
 #[macro_export]
 macro_rules! event {
-    (channels = [$($channel:ident $(: $weight:literal)?),* $(,)?], $($key:ident = $value:expr),* $(,)?) => {{
-        let channels = $crate::channels!($($channel $(: $weight)?),*);
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $($key:ident = $value:expr),* $(,)?) => {{
+        let channels = $crate::channels!($($channel $(. $segment)* $(: $weight)?),*);
         $crate::collector::tracing::info!(channels = %channels, $($key = %$crate::serialize::json(&$value)),*)
     }};
 }
 
+#[macro_export]
+macro_rules! counter {
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $name:ident $(,)?) => {
+        $crate::counter!(channels = [$($channel $(. $segment)* $(: $weight)?),*], $name = 1)
+    };
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $name:ident = $value:expr $(,)?) => {
+        $crate::measure!(counter, [$($channel $(. $segment)* $(: $weight)?),*], $name, $value)
+    };
+}
+
+#[macro_export]
+macro_rules! gauge {
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $name:ident = $value:expr $(,)?) => {
+        $crate::measure!(gauge, [$($channel $(. $segment)* $(: $weight)?),*], $name, $value)
+    };
+}
+
+#[macro_export]
+macro_rules! histogram {
+    (channels = [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?], $name:ident = $value:expr $(,)?) => {
+        $crate::measure!(histogram, [$($channel $(. $segment)* $(: $weight)?),*], $name, $value)
+    };
+}
+
+#[macro_export]
+macro_rules! measure {
+    ($kind:ident, [$($channel:ident $(. $segment:ident)* $(: $weight:literal)?),*], $name:ident, $value:expr) => {{
+        let channels = $crate::channels!($($channel $(. $segment)* $(: $weight)?),*);
+        $crate::collector::tracing::info!(
+            target: $crate::metric::TARGET,
+            channels = %channels,
+            kind = stringify!($kind),
+            name = stringify!($name),
+            value = %$crate::serialize::json(&$value)
+        )
+    }};
+}
+
 #[macro_export]
 macro_rules! channels {
-    ($($channel:ident $(: $weight:literal)?),* $(,)?) => {{
+    ($($channel:ident $(. $segment:ident)* $(: $weight:literal)?),* $(,)?) => {{
         let mut parts = Vec::<String>::new();
         $(
             let weight: u8 = $crate::channels!(@weight $($weight)?);
-            parts.push(format!("{}:{}", stringify!($channel), weight));
+            parts.push(format!("{}:{}", concat!(stringify!($channel) $(, ".", stringify!($segment))*), weight));
         )*
         parts.join(",")
     }};
@@ -4,7 +4,7 @@ use quaternion::Quaternion;
 use tolerance::EPSILON;
 use vector::Vector;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct Matrix {
     pub columns: [[f32; 4]; 4],
 }
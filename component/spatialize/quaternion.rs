@@ -1,6 +1,6 @@
 use vector::Vector;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct Quaternion {
     pub w: f32,
     pub x: f32,
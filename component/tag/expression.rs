@@ -6,21 +6,78 @@ use thiserror::Error;
 #[derive(Debug, Clone)]
 pub enum Expression {
     Name(String),
+    Weight {
+        name: String,
+        operator: Operator,
+        weight: u8,
+    },
+    Level {
+        operator: Operator,
+        level: Level,
+    },
     Not(Box<Expression>),
     And(Vec<Expression>),
     Or(Vec<Expression>),
     Any,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Less,
+    LessEqual,
+    Equal,
+    GreaterEqual,
+    Greater,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Operator {
+    #[must_use]
+    pub fn holds<T: Ord>(self, left: T, right: T) -> bool {
+        match self {
+            Self::Less => left < right,
+            Self::LessEqual => left <= right,
+            Self::Equal => left == right,
+            Self::GreaterEqual => left >= right,
+            Self::Greater => left > right,
+        }
+    }
+}
+
 impl Expression {
     #[must_use]
     pub fn evaluate<S: AsRef<str>>(&self, names: &[S]) -> bool {
+        let weighted = names.iter().map(|n| (n.as_ref(), 1)).collect::<Vec<_>>();
+        self.matches(&weighted, None)
+    }
+
+    #[must_use]
+    pub fn matches<S: AsRef<str>>(&self, names: &[(S, u8)], level: Option<Level>) -> bool {
         match self {
             Self::Any => true,
-            Self::Name(name) => names.iter().any(|n| n.as_ref() == name),
-            Self::Not(inner) => !inner.evaluate(names),
-            Self::And(terms) => terms.iter().all(|t| t.evaluate(names)),
-            Self::Or(terms) => terms.iter().any(|t| t.evaluate(names)),
+            Self::Name(pattern) => names.iter().any(|(n, _)| matches(pattern, n.as_ref())),
+            Self::Weight {
+                name,
+                operator,
+                weight,
+            } => names
+                .iter()
+                .any(|(n, w)| matches(name, n.as_ref()) && operator.holds(*w, *weight)),
+            Self::Level {
+                operator,
+                level: bound,
+            } => level.is_some_and(|level| operator.holds(level, *bound)),
+            Self::Not(inner) => !inner.matches(names, level),
+            Self::And(terms) => terms.iter().all(|t| t.matches(names, level)),
+            Self::Or(terms) => terms.iter().any(|t| t.matches(names, level)),
         }
     }
 
@@ -41,13 +98,42 @@ impl Expression {
     }
 }
 
+fn matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        None => pattern == name,
+        Some("") => true,
+        Some(prefix) => {
+            name.starts_with(prefix)
+                || prefix
+                    .strip_suffix('.')
+                    .is_some_and(|parent| name == parent)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grammar {
+    Tags,
+    Channels,
+}
+
 pub fn parse(input: &str) -> Result<Expression, Sourced> {
+    read(input, Grammar::Tags)
+}
+
+/// Parses a channel expression, where a dotted run such as `hypergraph.match` also selects
+/// the hierarchical channel of that name besides the conjunction of its segments.
+pub fn channels(input: &str) -> Result<Expression, Sourced> {
+    read(input, Grammar::Channels)
+}
+
+fn read(input: &str, grammar: Grammar) -> Result<Expression, Sourced> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return Ok(Expression::Any);
     }
     let mut cursor = Cursor::new(trimmed.as_bytes().to_vec());
-    let result = sum(&mut cursor).map_err(|error| sourced(input, error))?;
+    let result = sum(&mut cursor, grammar).map_err(|error| sourced(input, error))?;
     let remaining = remaining(&mut cursor);
     if !remaining.is_empty() {
         let position = cursor.position().saturating_sub(remaining.len() as u64);
@@ -138,12 +224,12 @@ fn identifier<Source: Read + Seek>(source: &mut Source) -> Result<String, Error>
     Ok(name)
 }
 
-fn sum<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error> {
-    let mut terms = vec![product(source)?];
+fn sum<Source: Read + Seek>(source: &mut Source, grammar: Grammar) -> Result<Expression, Error> {
+    let mut terms = vec![product(source, grammar)?];
 
     while let Some(b',') = peek(source) {
         advance(source);
-        terms.push(product(source)?);
+        terms.push(product(source, grammar)?);
     }
 
     Ok(match terms.len() {
@@ -152,12 +238,15 @@ fn sum<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error> {
     })
 }
 
-fn product<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error> {
-    let mut factors = vec![unary(source)?];
+fn product<Source: Read + Seek>(
+    source: &mut Source,
+    grammar: Grammar,
+) -> Result<Expression, Error> {
+    let mut factors = vec![unary(source, grammar)?];
 
-    while let Some(b'.') = peek(source) {
+    while let Some(b'.' | b'&') = peek(source) {
         advance(source);
-        factors.push(unary(source)?);
+        factors.push(unary(source, grammar)?);
     }
 
     Ok(match factors.len() {
@@ -166,14 +255,14 @@ fn product<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error
     })
 }
 
-fn unary<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error> {
+fn unary<Source: Read + Seek>(source: &mut Source, grammar: Grammar) -> Result<Expression, Error> {
     match peek(source) {
         Some(b'!') => {
             advance(source);
-            let operand = unary(source)?;
+            let operand = unary(source, grammar)?;
             Ok(Expression::Not(Box::new(operand)))
         }
-        Some(_) => atom(source),
+        Some(_) => atom(source, grammar),
         None => {
             let position = source.stream_position().unwrap_or(0);
             Err(Error::End {
@@ -184,11 +273,11 @@ fn unary<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error>
     }
 }
 
-fn atom<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error> {
+fn atom<Source: Read + Seek>(source: &mut Source, grammar: Grammar) -> Result<Expression, Error> {
     match peek(source) {
         Some(b'(') => {
             advance(source);
-            let inner = sum(source)?;
+            let inner = sum(source, grammar)?;
             match peek(source) {
                 Some(b')') => {
                     advance(source);
@@ -210,9 +299,23 @@ fn atom<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error> {
                 }
             }
         }
+        Some(b'*') => {
+            advance(source);
+            weight(source, "*".to_string())
+        }
         Some(b) if b.is_ascii_alphabetic() || b == b'_' => {
             let name = identifier(source)?;
-            Ok(Expression::Name(name))
+            if name == "level"
+                && let Some(operator) = operator(source)
+            {
+                return level(source, operator);
+            }
+            if grammar == Grammar::Tags {
+                return weight(source, name);
+            }
+            let segments = path(source, name);
+            let expression = weight(source, segments.join("."))?;
+            Ok(dotted(&segments, expression))
         }
         Some(_) => {
             let position = source.stream_position().unwrap_or(0);
@@ -231,12 +334,160 @@ fn atom<Source: Read + Seek>(source: &mut Source) -> Result<Expression, Error> {
     }
 }
 
+fn next<Source: Read + Seek>(source: &mut Source) -> Option<u8> {
+    let mut byte = [0u8; 1];
+    let position = source.stream_position().unwrap_or(0);
+    match source.read(&mut byte) {
+        Ok(1..) => {
+            source.seek(std::io::SeekFrom::Start(position)).ok();
+            Some(byte[0])
+        }
+        _ => None,
+    }
+}
+
+fn path<Source: Read + Seek>(source: &mut Source, first: String) -> Vec<String> {
+    let mut segments = vec![first];
+    loop {
+        let position = source.stream_position().unwrap_or(0);
+        if next(source) != Some(b'.') {
+            break;
+        }
+        advance(source);
+        match next(source) {
+            Some(b'*') => {
+                advance(source);
+                segments.push("*".to_string());
+                break;
+            }
+            Some(b) if b.is_ascii_alphabetic() || b == b'_' => match identifier(source) {
+                Ok(segment)
+                    if segment != "level" || !matches!(peek(source), Some(b'<' | b'>' | b'=')) =>
+                {
+                    segments.push(segment);
+                }
+                _ => {
+                    source.seek(std::io::SeekFrom::Start(position)).ok();
+                    break;
+                }
+            },
+            _ => {
+                source.seek(std::io::SeekFrom::Start(position)).ok();
+                break;
+            }
+        }
+    }
+    segments
+}
+
+fn dotted(segments: &[String], expression: Expression) -> Expression {
+    let Some((last, parents)) = segments.split_last() else {
+        return expression;
+    };
+    if parents.is_empty() || last == "*" {
+        return expression;
+    }
+    let mut factors = parents
+        .iter()
+        .cloned()
+        .map(Expression::Name)
+        .collect::<Vec<_>>();
+    factors.push(match &expression {
+        Expression::Weight {
+            operator, weight, ..
+        } => Expression::Weight {
+            name: last.clone(),
+            operator: *operator,
+            weight: *weight,
+        },
+        _ => Expression::Name(last.clone()),
+    });
+    Expression::Or(vec![expression, Expression::And(factors)])
+}
+
+fn operator<Source: Read + Seek>(source: &mut Source) -> Option<Operator> {
+    let operator = match peek(source)? {
+        b'<' => Operator::Less,
+        b'>' => Operator::Greater,
+        b'=' => Operator::Equal,
+        _ => return None,
+    };
+    advance(source);
+    if operator == Operator::Equal || next(source) != Some(b'=') {
+        return Some(operator);
+    }
+    advance(source);
+    Some(match operator {
+        Operator::Less => Operator::LessEqual,
+        _ => Operator::GreaterEqual,
+    })
+}
+
+fn weight<Source: Read + Seek>(source: &mut Source, name: String) -> Result<Expression, Error> {
+    if peek(source) != Some(b':') {
+        return Ok(Expression::Name(name));
+    }
+    advance(source);
+
+    let Some(operator) = operator(source) else {
+        let position = source.stream_position().unwrap_or(0);
+        return Err(Error::Token {
+            expected: "comparison (<, <=, =, >=, >)".to_string(),
+            span: offset(position, 1),
+        });
+    };
+
+    whitespace(source);
+    let position = source.stream_position().unwrap_or(0);
+    let mut digits = String::new();
+    while let Some(b) = next(source).filter(u8::is_ascii_digit) {
+        advance(source);
+        digits.push(b as char);
+    }
+    match digits.parse() {
+        Ok(weight) => Ok(Expression::Weight {
+            name,
+            operator,
+            weight,
+        }),
+        Err(_) => Err(Error::Weight {
+            weight: digits.clone(),
+            span: offset(position, digits.len().max(1)),
+        }),
+    }
+}
+
+fn level<Source: Read + Seek>(
+    source: &mut Source,
+    operator: Operator,
+) -> Result<Expression, Error> {
+    whitespace(source);
+    let position = source.stream_position().unwrap_or(0);
+    let name = identifier(source)?;
+    let level = match name.as_str() {
+        "trace" => Level::Trace,
+        "debug" => Level::Debug,
+        "info" => Level::Info,
+        "warn" => Level::Warn,
+        "error" => Level::Error,
+        _ => {
+            return Err(Error::Level {
+                span: offset(position, name.len()),
+                level: name,
+            });
+        }
+    };
+    Ok(Expression::Level { operator, level })
+}
+
 #[derive(Error, Debug, Diagnostic)]
 pub enum Error {
     #[error("unexpected token in expression")]
     #[diagnostic(
         code(tag::expression::token),
-        help("use . (and), , (or), ! (not), () (group) — e.g. performance.sorting, benchmark")
+        help(
+            "use . or & (and), , (or), ! (not), () (group), name.* (subtree), name:>=N (weight), level>=debug — e.g. performance.sorting, benchmark"
+        )
     )]
     Token {
         expected: String,
@@ -254,6 +505,28 @@ pub enum Error {
         #[label("expected {expected} after this")]
         span: SourceSpan,
     },
+
+    #[error("invalid weight in expression")]
+    #[diagnostic(
+        code(tag::expression::weight),
+        help("weights are whole numbers between 0 and 255 — e.g. core:>=2")
+    )]
+    Weight {
+        weight: String,
+        #[label("expected a weight")]
+        span: SourceSpan,
+    },
+
+    #[error("unknown level in expression: {level}")]
+    #[diagnostic(
+        code(tag::expression::level),
+        help("use one of trace, debug, info, warn, error — e.g. level>=debug")
+    )]
+    Level {
+        level: String,
+        #[label("unknown level")]
+        span: SourceSpan,
+    },
 }
 
 #[derive(Error, Debug, Diagnostic)]
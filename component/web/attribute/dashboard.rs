@@ -0,0 +1,36 @@
+use reference::Reference;
+
+#[must_use]
+pub fn dashboard() -> Reference {
+    Reference("data-dashboard")
+}
+
+#[must_use]
+pub fn status() -> Reference {
+    Reference("data-dashboard-status")
+}
+
+#[must_use]
+pub fn timeline() -> Reference {
+    Reference("data-dashboard-timeline")
+}
+
+#[must_use]
+pub fn filter() -> Reference {
+    Reference("data-dashboard-filter")
+}
+
+#[must_use]
+pub fn log() -> Reference {
+    Reference("data-dashboard-log")
+}
+
+#[must_use]
+pub fn snapshots() -> Reference {
+    Reference("data-dashboard-snapshots")
+}
+
+#[must_use]
+pub fn bound() -> Reference {
+    Reference("data-dashboard-bound")
+}
@@ -0,0 +1,41 @@
+use reference::Reference;
+
+#[must_use]
+pub fn playground() -> Reference {
+    Reference("data-playground")
+}
+
+#[must_use]
+pub fn source() -> Reference {
+    Reference("data-playground-source")
+}
+
+#[must_use]
+pub fn highlight() -> Reference {
+    Reference("data-playground-highlight")
+}
+
+#[must_use]
+pub fn run() -> Reference {
+    Reference("data-playground-run")
+}
+
+#[must_use]
+pub fn particles() -> Reference {
+    Reference("data-playground-particles")
+}
+
+#[must_use]
+pub fn derivations() -> Reference {
+    Reference("data-playground-derivations")
+}
+
+#[must_use]
+pub fn diagnostics() -> Reference {
+    Reference("data-playground-diagnostics")
+}
+
+#[must_use]
+pub fn bound() -> Reference {
+    Reference("data-playground-bound")
+}
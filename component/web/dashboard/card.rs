@@ -1,4 +1,5 @@
 use body::Body;
+use difference::Tolerance;
 use element::Location;
 use serde_json::Value;
 
@@ -20,6 +21,7 @@ pub struct Group {
 pub struct Case {
     pub parameters: Value,
     pub returns: Value,
+    pub tolerance: Tolerance,
     pub unexpected: Option<Value>,
 }
 
@@ -50,10 +52,10 @@ pub fn source(body: Body, group: &Group) -> Body {
 pub fn cases(
     body: Body,
     group: &Group,
-    highlight: impl Fn(&Value, Option<&Value>) -> Option<String>,
+    highlight: impl Fn(&Value, Option<&Value>, &Tolerance) -> Option<String>,
 ) -> Body {
     group.cases.iter().enumerate().fold(body, |c, (i, case)| {
-        let output = highlight(&case.returns, case.unexpected.as_ref());
+        let output = highlight(&case.returns, case.unexpected.as_ref(), &case.tolerance);
         detail::render(
             c,
             i,
@@ -10,19 +10,58 @@ pub struct Divergence {
     pub actual: serde_json::Value,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+    pub ulps: u64,
+    pub unordered: bool,
+    pub subset: bool,
+}
+
+impl Tolerance {
+    #[must_use]
+    pub fn close(&self, expected: f64, actual: f64) -> bool {
+        let distance = (expected - actual).abs();
+        distance <= self.absolute
+            || distance <= self.relative * expected.abs().max(actual.abs())
+            || ordered(expected).abs_diff(ordered(actual)) <= self.ulps
+    }
+
+    fn numeric(&self) -> bool {
+        self.absolute > 0.0 || self.relative > 0.0 || self.ulps > 0
+    }
+}
+
+fn ordered(value: f64) -> i64 {
+    let bits = value.to_bits().cast_signed();
+    if bits < 0 { i64::MIN - bits } else { bits }
+}
+
 #[must_use]
 pub fn compare(
     expected: &serde_json::Value,
     actual: &serde_json::Value,
+) -> Vec<(Vec<Segment>, Divergence)> {
+    within(expected, actual, &Tolerance::default())
+}
+
+#[must_use]
+pub fn within(
+    expected: &serde_json::Value,
+    actual: &serde_json::Value,
+    tolerance: &Tolerance,
 ) -> Vec<(Vec<Segment>, Divergence)> {
     let mut result = Vec::new();
-    walk(expected, actual, &mut Vec::new(), &mut result);
+    walk(expected, actual, tolerance, &mut Vec::new(), &mut result);
     result
 }
 
 fn walk(
     expected: &serde_json::Value,
     actual: &serde_json::Value,
+    tolerance: &Tolerance,
     path: &mut Vec<Segment>,
     result: &mut Vec<(Vec<Segment>, Divergence)>,
 ) {
@@ -31,11 +70,14 @@ fn walk(
             for (key, value) in expected {
                 descend(path, Segment::Key(key.clone()), |path| {
                     match actual.get(key) {
-                        Some(other) => walk(value, other, path, result),
+                        Some(other) => walk(value, other, tolerance, path, result),
                         None => emit(result, path, value, &serde_json::Value::Null),
                     }
                 });
             }
+            if tolerance.subset {
+                return;
+            }
             for (key, value) in actual {
                 if expected.contains_key(key) {
                     continue;
@@ -45,12 +87,19 @@ fn walk(
                 });
             }
         }
+        (serde_json::Value::Array(expected), serde_json::Value::Array(actual))
+            if tolerance.unordered =>
+        {
+            unordered(expected, actual, tolerance, path, result);
+        }
         (serde_json::Value::Array(expected), serde_json::Value::Array(actual)) => {
             let length = expected.len().max(actual.len());
             for index in 0..length {
                 descend(path, Segment::Index(index), |path| {
                     match (expected.get(index), actual.get(index)) {
-                        (Some(value), Some(other)) => walk(value, other, path, result),
+                        (Some(value), Some(other)) => {
+                            walk(value, other, tolerance, path, result);
+                        }
                         (Some(value), None) => {
                             emit(result, path, value, &serde_json::Value::Null);
                         }
@@ -62,11 +111,81 @@ fn walk(
                 });
             }
         }
+        (serde_json::Value::Number(left), serde_json::Value::Number(right)) => {
+            match (left.as_f64(), right.as_f64()) {
+                _ if left == right => {}
+                (Some(left), Some(right))
+                    if tolerance.numeric() && tolerance.close(left, right) => {}
+                _ => emit(result, path, expected, actual),
+            }
+        }
         _ if expected == actual => {}
         _ => emit(result, path, expected, actual),
     }
 }
 
+fn unordered(
+    expected: &[serde_json::Value],
+    actual: &[serde_json::Value],
+    tolerance: &Tolerance,
+    path: &mut Vec<Segment>,
+    result: &mut Vec<(Vec<Segment>, Divergence)>,
+) {
+    let fits = expected
+        .iter()
+        .map(|value| {
+            actual
+                .iter()
+                .map(|other| within(value, other, tolerance).is_empty())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut owner = vec![None; actual.len()];
+    let mut missing = Vec::new();
+    for index in 0..expected.len() {
+        let mut seen = vec![false; actual.len()];
+        if !augment(index, &fits, &mut seen, &mut owner) {
+            missing.push(index);
+        }
+    }
+
+    let mut surplus = actual
+        .iter()
+        .zip(&owner)
+        .filter(|(_, owner)| owner.is_none())
+        .map(|(value, _)| value);
+    for index in missing {
+        descend(path, Segment::Index(index), |path| match surplus.next() {
+            Some(other) => walk(&expected[index], other, tolerance, path, result),
+            None => emit(result, path, &expected[index], &serde_json::Value::Null),
+        });
+    }
+    for (offset, value) in surplus.enumerate() {
+        descend(path, Segment::Index(expected.len() + offset), |path| {
+            emit(result, path, &serde_json::Value::Null, value);
+        });
+    }
+}
+
+fn augment(
+    index: usize,
+    fits: &[Vec<bool>],
+    seen: &mut [bool],
+    owner: &mut [Option<usize>],
+) -> bool {
+    for position in 0..owner.len() {
+        if !fits[index][position] || seen[position] {
+            continue;
+        }
+        seen[position] = true;
+        if owner[position].is_none_or(|other| augment(other, fits, seen, owner)) {
+            owner[position] = Some(index);
+            return true;
+        }
+    }
+    false
+}
+
 fn descend(path: &mut Vec<Segment>, segment: Segment, visit: impl FnOnce(&mut Vec<Segment>)) {
     path.push(segment);
     visit(path);
@@ -4,3 +4,10 @@ use std::path::PathBuf;
 pub fn directory() -> Option<PathBuf> {
     std::env::current_dir().ok()
 }
+
+#[must_use]
+pub fn workspace() -> Option<PathBuf> {
+    std::env::var("BUILD_WORKSPACE_DIRECTORY")
+        .ok()
+        .map(PathBuf::from)
+}
@@ -81,7 +81,9 @@ fn process(
             let inputs = function::Inputs {
                 parameters: &function.parameters,
                 returns: &function.returns,
-                functions: &context.functions,
+                tolerance: function.tolerance.as_ref(),
+                types: &function.types,
+                context,
             };
 
             let registration = function::build(
@@ -95,6 +97,10 @@ fn process(
 
             registrations.push(registration);
         }
+
+        if let Some(generate) = &function.generate {
+            registrations.push(property::build(generate, function, context, content, path)?);
+        }
     }
 
     Ok(registrations)
@@ -137,25 +143,41 @@ fn emit(reg: function::Registration) -> syn::Stmt {
         .collect::<Vec<_>>();
     let parameters = serde_json::to_string(&reg.parameters).expect("Value is always serializable");
     let expected = serde_json::to_string(&reg.expected).expect("Value is always serializable");
+    let tolerance = serde_json::to_string(&reg.tolerance).expect("Value is always serializable");
     let statements = reg.statements;
     let actuals = reg.actuals;
     let comparisons = reg.comparisons;
 
+    let body: syn::Expr = syn::parse_quote! {
+        {
+            #(#statements)*
+            let actuals: serde_json::Map<String, serde_json::Value> = #actuals.into();
+            #(#comparisons)*
+            Ok(actuals)
+        }
+    };
+    let evaluation: syn::Expr = match reg.property {
+        Some(specification) => {
+            let parameters = keyword::parameters().variable;
+            syn::parse_quote! {
+                function::property::check(#specification, |#parameters| #body)
+            }
+        }
+        None => body,
+    };
+
     syn::parse_quote! {
         {
             let parameters: serde_json::Value = serde_json::from_str(#parameters).into_diagnostic()?;
             let expected: serde_json::Value = serde_json::from_str(#expected).into_diagnostic()?;
+            let tolerance: Option<serde_json::Value> = serde_json::from_str(#tolerance).into_diagnostic()?;
             executor.register(
                 #name,
                 &[#(#tags),*],
                 parameters,
                 expected,
-                || {
-                    #(#statements)*
-                    let actuals: serde_json::Map<String, serde_json::Value> = #actuals.into();
-                    #(#comparisons)*
-                    Ok(actuals)
-                },
+                tolerance,
+                || #evaluation,
             );
         }
     }
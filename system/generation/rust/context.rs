@@ -1,42 +1,348 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use quote::ToTokens;
 use syn::{
-    File, Ident, ItemFn, ItemMod, Signature,
+    Expr, File, FnArg, GenericArgument, GenericParam, Generics, Ident, ImplItem, Item, ItemFn,
+    ItemImpl, ItemMod, Path, PathSegment, Signature, Type, UseTree,
+    punctuated::Punctuated,
     visit::{self, Visit},
+    visit_mut::{self, VisitMut},
 };
 
+const DEPTH: usize = 8;
+
 pub struct Context {
     pub functions: HashMap<String, Signature>,
+    pub owners: HashMap<String, Owner>,
+    pub ambiguous: HashMap<String, Vec<String>>,
+}
+
+#[derive(Clone)]
+pub struct Owner {
+    pub ty: Type,
+    pub generics: Generics,
+    pub interface: Option<Path>,
+}
+
+pub struct Target {
+    pub signature: Signature,
+    pub callee: Expr,
+}
+
+#[derive(Debug)]
+pub enum Unresolved {
+    Undefined,
+    Ambiguous(Vec<String>),
+    Missing(String),
+    Unknown(String, Vec<String>),
+    Invalid(String, String),
 }
 
 impl Context {
     #[must_use]
     pub fn from(ast: &File) -> Self {
-        let mut collector = Collector::default();
+        let mut scope = Scope::default();
+        scope.visit_file(ast);
+
+        let mut collector = Collector {
+            scope,
+            ..Collector::default()
+        };
         collector.visit_file(ast);
+        let ambiguous = collector.settle();
 
         Self {
             functions: collector.functions,
+            owners: collector.owners,
+            ambiguous,
+        }
+    }
+
+    pub fn instantiate(
+        &self,
+        qualified: &str,
+        types: &HashMap<String, String>,
+    ) -> Result<Target, Unresolved> {
+        if let Some(candidates) = self.ambiguous.get(qualified) {
+            return Err(Unresolved::Ambiguous(candidates.clone()));
+        }
+        let mut signature = self
+            .functions
+            .get(qualified)
+            .cloned()
+            .ok_or(Unresolved::Undefined)?;
+        let mut owner = self.owners.get(qualified).cloned();
+
+        let parameters = owner
+            .iter()
+            .flat_map(|owner| owner.generics.params.iter())
+            .chain(signature.generics.params.iter())
+            .filter_map(|parameter| match parameter {
+                GenericParam::Type(parameter) => Some((parameter.ident.to_string(), false)),
+                GenericParam::Const(parameter) => Some((parameter.ident.to_string(), true)),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let generics = parameters
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+
+        if let Some(unknown) = types.keys().find(|name| !generics.contains(*name)) {
+            return Err(Unresolved::Unknown(unknown.clone(), generics));
+        }
+
+        let mut substitution = Substitution::default();
+        for (name, constant) in &parameters {
+            let argument = types
+                .get(name)
+                .ok_or_else(|| Unresolved::Missing(name.clone()))?;
+            let invalid = |e: syn::Error| Unresolved::Invalid(name.clone(), e.to_string());
+            if *constant {
+                let value = match syn::parse_str::<Expr>(argument).map_err(invalid)? {
+                    value @ (Expr::Lit(_) | Expr::Path(_)) => value,
+                    value => syn::parse_quote! { { #value } },
+                };
+                substitution.constants.insert(name.clone(), value);
+            } else {
+                let ty = syn::parse_str::<Type>(argument).map_err(invalid)?;
+                substitution.types.insert(name.clone(), ty);
+            }
+        }
+
+        for input in &mut signature.inputs {
+            substitution.visit_fn_arg_mut(input);
         }
+        substitution.visit_return_type_mut(&mut signature.output);
+
+        let arguments = signature
+            .generics
+            .params
+            .iter()
+            .filter_map(|parameter| match parameter {
+                GenericParam::Type(parameter) => Some(GenericArgument::Type(
+                    substitution.types[&parameter.ident.to_string()].clone(),
+                )),
+                GenericParam::Const(parameter) => Some(GenericArgument::Const(
+                    substitution.constants[&parameter.ident.to_string()].clone(),
+                )),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let ident = &signature.ident;
+        let turbofish: Option<syn::AngleBracketedGenericArguments> =
+            (!arguments.is_empty()).then(|| syn::parse_quote! { ::<#(#arguments),*> });
+
+        let callee = match owner.as_mut() {
+            Some(owner) if owner.interface.is_some() || !generics.is_empty() => {
+                substitution.visit_type_mut(&mut owner.ty);
+                let ty = &owner.ty;
+                match &owner.interface {
+                    Some(interface) => syn::parse_quote! { <#ty as #interface>::#ident #turbofish },
+                    None => syn::parse_quote! { <#ty>::#ident #turbofish },
+                }
+            }
+            _ => {
+                let path = syn::parse_str::<Path>(qualified)
+                    .map_err(|e| Unresolved::Invalid(qualified.to_string(), e.to_string()))?;
+                syn::parse_quote! { #path #turbofish }
+            }
+        };
+
+        Ok(Target { signature, callee })
+    }
+}
+
+#[derive(Default)]
+struct Scope {
+    defined: HashSet<String>,
+    imported: HashMap<String, Path>,
+    module: Vec<Ident>,
+}
+
+impl Visit<'_> for Scope {
+    fn visit_item(&mut self, item: &Item) {
+        let ident = match item {
+            Item::Struct(item) => Some(&item.ident),
+            Item::Enum(item) => Some(&item.ident),
+            Item::Union(item) => Some(&item.ident),
+            Item::Trait(item) => Some(&item.ident),
+            Item::Type(item) => Some(&item.ident),
+            Item::Mod(item) => Some(&item.ident),
+            _ => None,
+        };
+        if let Some(ident) = ident {
+            self.defined
+                .insert(qualified(&self.module, &ident.to_string()));
+        }
+        if let Item::Use(item) = item
+            && item.leading_colon.is_none()
+        {
+            self.import(&mut Vec::new(), &item.tree);
+        }
+
+        visit::visit_item(self, item);
+    }
+
+    fn visit_item_mod(&mut self, item: &ItemMod) {
+        self.module.push(item.ident.clone());
+        visit::visit_item_mod(self, item);
+        self.module.pop();
+    }
+}
+
+impl Scope {
+    fn import(&mut self, prefix: &mut Vec<Ident>, tree: &UseTree) {
+        let (path, name) = match tree {
+            UseTree::Path(tree) => {
+                prefix.push(tree.ident.clone());
+                self.import(prefix, &tree.tree);
+                prefix.pop();
+                return;
+            }
+            UseTree::Group(group) => {
+                for tree in &group.items {
+                    self.import(prefix, tree);
+                }
+                return;
+            }
+            UseTree::Glob(_) => return,
+            UseTree::Name(tree) if tree.ident == "self" => {
+                let Some(name) = prefix.last() else {
+                    return;
+                };
+                (prefix.clone(), name.clone())
+            }
+            UseTree::Name(tree) => {
+                let mut path = prefix.clone();
+                path.push(tree.ident.clone());
+                (path, tree.ident.clone())
+            }
+            UseTree::Rename(tree) => {
+                let mut path = prefix.clone();
+                path.push(tree.ident.clone());
+                (path, tree.rename.clone())
+            }
+        };
+        self.imported.insert(
+            qualified(&self.module, &name.to_string()),
+            syn::parse_quote! { #(#path)::* },
+        );
+    }
+
+    // Paths inside modules are re-rooted at the template root, where the
+    // generated tests live: template items gain their module, imports are
+    // replaced by what they import and anything else is kept as written.
+    fn resolve(&self, module: &[Ident], path: &Path, depth: usize) -> Path {
+        if path.leading_colon.is_some() || depth > DEPTH {
+            return path.clone();
+        }
+
+        let mut base = module.to_vec();
+        let mut segments = path.segments.iter().cloned().collect::<Vec<_>>();
+        let mut relative = false;
+        while let Some(first) = segments.first() {
+            if first.ident == "self" {
+                segments.remove(0);
+            } else if first.ident == "super" {
+                base.pop();
+                segments.remove(0);
+            } else {
+                break;
+            }
+            relative = true;
+        }
+
+        let Some(first) = segments.first() else {
+            return path.clone();
+        };
+        let local = qualified(&base, &first.ident.to_string());
+        if relative || self.defined.contains(&local) {
+            return rooted(&base, segments);
+        }
+        let Some(import) = self.imported.get(&local) else {
+            return path.clone();
+        };
+
+        let mut imported = import.segments.iter().cloned().collect::<Vec<_>>();
+        if let Some(last) = imported.last_mut() {
+            last.arguments = first.arguments.clone();
+        }
+        imported.extend(segments.into_iter().skip(1));
+        self.resolve(&base, &rooted(&[], imported), depth + 1)
     }
 }
 
 #[derive(Default)]
 struct Collector {
     functions: HashMap<String, Signature>,
+    owners: HashMap<String, Owner>,
+    inherent: HashSet<String>,
+    provided: HashMap<String, Vec<String>>,
+    scope: Scope,
     module: Vec<Ident>,
 }
 
 impl Visit<'_> for Collector {
     fn visit_item_fn(&mut self, item: &ItemFn) {
         let name = item.sig.ident.to_string();
-        let qualified = self.qualified(&name);
+        let qualified = qualified(&self.module, &name);
 
         self.functions.insert(qualified, item.sig.clone());
 
         visit::visit_item_fn(self, item);
     }
 
+    fn visit_item_impl(&mut self, item: &ItemImpl) {
+        let Type::Path(path) = item.self_ty.as_ref() else {
+            return;
+        };
+        let Some(segment) = path.path.segments.last() else {
+            return;
+        };
+        let name = segment.ident.to_string();
+
+        let owner = Owner {
+            ty: self.located(item.self_ty.as_ref()),
+            generics: item.generics.clone(),
+            interface: item
+                .trait_
+                .as_ref()
+                .map(|(_, interface, _)| self.path(interface)),
+        };
+        let interface = item.trait_.as_ref().and_then(|(_, interface, _)| {
+            interface
+                .segments
+                .last()
+                .map(|segment| segment.to_token_stream().to_string().replace(' ', ""))
+        });
+
+        for entry in &item.items {
+            let ImplItem::Fn(method) = entry else {
+                continue;
+            };
+            let plain = qualified(&self.module, &format!("{name}::{}", method.sig.ident));
+            let qualified = if let Some(interface) = &interface {
+                let key = qualified(
+                    &self.module,
+                    &format!("{name}::{interface}::{}", method.sig.ident),
+                );
+                self.provided.entry(plain).or_default().push(key.clone());
+                key
+            } else {
+                self.inherent.insert(plain.clone());
+                plain
+            };
+
+            let mut signature = method.sig.clone();
+            receive(&mut signature);
+            SelfType { ty: &owner.ty }.visit_signature_mut(&mut signature);
+
+            self.functions.insert(qualified.clone(), signature);
+            self.owners.insert(qualified, owner.clone());
+        }
+    }
+
     fn visit_item_mod(&mut self, item: &ItemMod) {
         self.module.push(item.ident.clone());
         visit::visit_item_mod(self, item);
@@ -45,17 +351,140 @@ impl Visit<'_> for Collector {
 }
 
 impl Collector {
-    fn qualified(&self, name: &str) -> String {
-        if self.module.is_empty() {
-            name.to_string()
-        } else {
-            let path = self
-                .module
-                .iter()
-                .map(ToString::to_string)
-                .collect::<Vec<_>>()
-                .join("::");
-            format!("{path}::{name}")
+    fn path(&self, path: &Path) -> Path {
+        self.scope.resolve(&self.module, path, 0)
+    }
+
+    fn located(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Path(path) if path.qself.is_none() => Type::Path(syn::TypePath {
+                qself: None,
+                path: self.path(&path.path),
+            }),
+            _ => ty.clone(),
+        }
+    }
+
+    fn settle(&mut self) -> HashMap<String, Vec<String>> {
+        let mut ambiguous = HashMap::new();
+        for (plain, mut candidates) in std::mem::take(&mut self.provided) {
+            // Inherent methods shadow trait methods of the same name, as in Rust.
+            if self.inherent.contains(&plain) {
+                continue;
+            }
+            let first = &candidates[0];
+            self.functions
+                .insert(plain.clone(), self.functions[first].clone());
+            self.owners
+                .insert(plain.clone(), self.owners[first].clone());
+            if candidates.len() > 1 {
+                candidates.sort();
+                ambiguous.insert(plain, candidates);
+            }
+        }
+        ambiguous
+    }
+}
+
+fn qualified(module: &[Ident], name: &str) -> String {
+    if module.is_empty() {
+        name.to_string()
+    } else {
+        let path = module
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("::");
+        format!("{path}::{name}")
+    }
+}
+
+fn rooted(module: &[Ident], segments: Vec<PathSegment>) -> Path {
+    Path {
+        leading_colon: None,
+        segments: module
+            .iter()
+            .cloned()
+            .map(PathSegment::from)
+            .chain(segments)
+            .collect::<Punctuated<_, _>>(),
+    }
+}
+
+fn receive(signature: &mut Signature) {
+    let variable = keyword::receiver().variable;
+    for input in &mut signature.inputs {
+        if let FnArg::Receiver(receiver) = input {
+            let ty = &receiver.ty;
+            *input = syn::parse_quote! { #variable: #ty };
+        }
+    }
+}
+
+struct SelfType<'a> {
+    ty: &'a Type,
+}
+
+impl VisitMut for SelfType<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        match ty {
+            Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self") => {
+                *ty = self.ty.clone();
+            }
+            _ => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Substitution {
+    types: HashMap<String, Type>,
+    constants: HashMap<String, Expr>,
+}
+
+impl Substitution {
+    fn constant(&self, path: &Path) -> Option<&Expr> {
+        path.get_ident()
+            .and_then(|ident| self.constants.get(&ident.to_string()))
+    }
+}
+
+impl VisitMut for Substitution {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        let argument = match ty {
+            Type::Path(path) if path.qself.is_none() => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.types.get(&ident.to_string())),
+            _ => None,
+        };
+        match argument {
+            Some(argument) => *ty = argument.clone(),
+            None => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        let value = match expr {
+            Expr::Path(path) if path.qself.is_none() => self.constant(&path.path),
+            _ => None,
+        };
+        match value {
+            Some(value) => *expr = value.clone(),
+            None => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_generic_argument_mut(&mut self, argument: &mut GenericArgument) {
+        let value = match argument {
+            GenericArgument::Type(Type::Path(path)) if path.qself.is_none() => {
+                self.constant(&path.path)
+            }
+            _ => None,
+        };
+        match value {
+            Some(value) => *argument = GenericArgument::Const(value.clone()),
+            None => visit_mut::visit_generic_argument_mut(self, argument),
         }
     }
 }
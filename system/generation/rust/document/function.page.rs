@@ -45,6 +45,118 @@ pub fn page(root: &str) -> page::Page {
                 })
             })
             .rule()
+            .chapter("Expectations", |s| {
+                s.paragraph(|p| {
+                    p.text("A case compares ")
+                        .code("returns")
+                        .text(" by default. Either of two fields instead expects the function to fail:")
+                })
+                .list(|ul| {
+                    ul.glossary("\"panics\": \"message\"", ": Catch the panic and require its message to contain the given text")
+                        .glossary("\"error\": { \"code\": \"crate::code\" }", ": Require an Err whose miette diagnostic code matches")
+                })
+                .paragraph(|p| {
+                    p.text("A panicking case takes no ")
+                        .code("returns")
+                        .text(". A failing case may still check mutable parameters, but not ")
+                        .code("()")
+                        .text(", and its function must return a ")
+                        .code("Result")
+                        .text(".")
+                })
+            })
+            .rule()
+            .chapter("Tolerance", |s| {
+                s.paragraph(|p| {
+                    p.text("Returns are compared exactly unless a ")
+                        .code("tolerance")
+                        .text(" is given on the function, or on a case to override individual fields:")
+                })
+                .list(|ul| {
+                    ul.glossary("absolute", ": Largest accepted difference between numbers")
+                        .glossary("relative", ": Largest accepted difference as a fraction of the larger magnitude")
+                        .glossary("ulps", ": Largest accepted distance in representable doubles")
+                        .glossary("unordered", ": Match arrays regardless of element order")
+                        .glossary("subset", ": Ignore object keys that are absent from the expected value")
+                })
+                .paragraph(|p| {
+                    p.text("Tolerant comparisons serialize both values, so they apply inside vectors, matrices and structs alike. The dashboard diff highlights only values outside the tolerance.")
+                })
+            })
+            .rule()
+            .chapter("Properties", |s| {
+                s.paragraph(|p| {
+                    p.text("A ")
+                        .code("generate")
+                        .text(" block runs a function on seeded random parameters. Every parameter takes a strategy:")
+                })
+                .list(|ul| {
+                    ul.glossary("{\"range\": [low, high]}", ": Integer between the inclusive bounds")
+                        .glossary("{\"regex\": \"[a-z]{1,8}\"}", ": String matching the pattern")
+                        .glossary("{\"vector\": {\"of\": strategy, \"length\": [min, max]}}", ": Array of generated elements")
+                        .glossary("{\"oneOf\": [strategy, ...]}", ": Any one of the strategies")
+                        .glossary("{\"value\": json}", ": A fixed value")
+                })
+                .paragraph(|p| {
+                    p.text("Invariants either name a ")
+                        .code("reference")
+                        .text(" function whose result must match, or state a ")
+                        .code("relation")
+                        .text(" such as ")
+                        .code("returns.().length == parameters.items.length")
+                        .text(". The ")
+                        .code("seed")
+                        .text(" and ")
+                        .code("count")
+                        .text(" fields fix the random cases, 100 by default.")
+                })
+                .paragraph(|p| {
+                    p.text("A failure is shrunk to a minimal counterexample and reported as a literal case for ")
+                        .code("cases")
+                        .text(". Only reference mismatches carry ")
+                        .code("returns")
+                        .text("; panics and broken relations report just the parameters, so the expected outcome is filled in by hand. Parameters are deserialized at runtime, so their types need ")
+                        .code("serde::Deserialize")
+                        .text(".")
+                })
+            })
+            .rule()
+            .chapter("Methods", |s| {
+                s.paragraph(|p| {
+                    p.text("Functions inside ")
+                        .code("impl")
+                        .text(" blocks are named by their type, as in ")
+                        .code("Vector.new")
+                        .text(" or ")
+                        .code("Rectangle.area")
+                        .text(". A receiver is the parameter ")
+                        .code("self")
+                        .text(", deserialized from the case like any other, and ")
+                        .code("&mut self")
+                        .text(" can be checked under ")
+                        .code("returns")
+                        .text(". Trait methods are called through their trait; inherent methods of the same name take precedence.")
+                })
+                .paragraph(|p| {
+                    p.text("When two traits provide the same method, name the trait in the target, as in ")
+                        .code("Label.Named.name")
+                        .text(" or ")
+                        .code("Meters.From<f64>.from")
+                        .text(". Traits are resolved through the ")
+                        .code("use")
+                        .text(" imports of the module that implements them.")
+                })
+                .paragraph(|p| {
+                    p.text("Generic functions and methods of generic types are instantiated with ")
+                        .code("types")
+                        .text(", such as ")
+                        .code("{\"T\": \"i32\"}")
+                        .text(", on the function or on a case to override it. Const parameters take a value, as in ")
+                        .code("{\"N\": \"3\"}")
+                        .text(". Every generic parameter needs an argument.")
+                })
+            })
+            .rule()
             .chapter("Features", |s| {
                 s.list(|ul| {
                     ul.feature(
@@ -56,6 +168,22 @@ pub fn page(root: &str) -> page::Page {
                         "Schema validation",
                         ": Parameters match function signatures",
                     )
+                    .feature(
+                        "Failure expectations",
+                        ": Panics and error codes as expected outcomes",
+                    )
+                    .feature(
+                        "Tolerant comparison",
+                        ": Approximate numbers and structural matching",
+                    )
+                    .feature(
+                        "Property generation",
+                        ": Seeded random cases shrunk to minimal counterexamples",
+                    )
+                    .feature(
+                        "Method targets",
+                        ": Associated, trait and generic functions",
+                    )
                     .feature(
                         "Rich diagnostics",
                         ": Error reporting via miette with source locations",
@@ -26,6 +26,8 @@ pub struct Function {
 pub struct Case {
     pub parameters: Value,
     pub returns: Value,
+    #[serde(default)]
+    pub tolerance: difference::Tolerance,
     pub unexpected: Option<Value>,
 }
 
@@ -54,6 +56,7 @@ pub fn cards(execution: Execution, templates: &[Template]) -> Vec<card::Group> {
                     .map(|c| card::Case {
                         parameters: c.parameters,
                         returns: c.returns,
+                        tolerance: c.tolerance,
                         unexpected: c.unexpected,
                     })
                     .collect::<Vec<_>>(),
@@ -5,7 +5,13 @@ use proc_macro2::Span;
 use serde_json::{Map, Value};
 use syn::{Expr, FnArg, Signature, Type};
 
-use component::generation::rust::{error::Error, schema::Case, types::Callable};
+use context::{Context, Unresolved};
+
+use component::generation::rust::{
+    error::Error,
+    schema::{Case, Tolerance},
+    types::Callable,
+};
 use literal::expression;
 
 #[derive(Debug, Clone)]
@@ -14,10 +20,52 @@ struct Instance {
     returns: HashMap<String, Value>,
 }
 
+#[derive(Debug, Clone)]
+enum Expectation {
+    Returns,
+    Panics(String),
+    Fails(String),
+}
+
+impl Expectation {
+    fn from(case: &Case) -> Self {
+        match (&case.panics, &case.error) {
+            (Some(message), _) => Self::Panics(message.clone()),
+            (None, Some(failure)) => Self::Fails(failure.code.clone()),
+            (None, None) => Self::Returns,
+        }
+    }
+
+    fn record(&self, returns: &HashMap<String, Value>) -> Value {
+        let mut expected = serde_json::to_value(returns)
+            .expect("returns are already Value and always serializable");
+        if let Value::Object(object) = &mut expected {
+            match self {
+                Self::Returns => {}
+                Self::Panics(message) => {
+                    object.insert(
+                        keyword::panic().key.to_string(),
+                        Value::String(message.clone()),
+                    );
+                }
+                Self::Fails(code) => {
+                    object.insert(
+                        keyword::error().key.to_string(),
+                        serde_json::json!({ "code": code }),
+                    );
+                }
+            }
+        }
+        expected
+    }
+}
+
 pub struct Inputs<'a> {
     pub parameters: &'a HashMap<String, Value>,
     pub returns: &'a HashMap<String, Value>,
-    pub functions: &'a HashMap<String, Signature>,
+    pub tolerance: Option<&'a Tolerance>,
+    pub types: &'a HashMap<String, String>,
+    pub context: &'a Context,
 }
 
 pub struct Registration {
@@ -25,9 +73,11 @@ pub struct Registration {
     pub tags: Vec<String>,
     pub parameters: Value,
     pub expected: Value,
+    pub tolerance: Option<Value>,
     pub statements: Vec<syn::Stmt>,
     pub actuals: syn::Expr,
     pub comparisons: Vec<syn::Stmt>,
+    pub property: Option<String>,
 }
 
 pub fn build(
@@ -38,51 +88,87 @@ pub fn build(
     content: &str,
     path: &str,
 ) -> Result<Registration, Box<Error>> {
-    let signature = inputs.functions.get(&target.qualified).ok_or_else(|| {
-        let functions = inputs.functions.keys().cloned().collect::<Vec<String>>();
-        let suggestion =
-            similarity::nearest(&target.qualified, &functions).unwrap_or_default();
+    let functions = &inputs.context.functions;
+    if !functions.contains_key(&target.qualified) {
+        let functions = functions.keys().cloned().collect::<Vec<String>>();
+        let suggestion = similarity::nearest(&target.qualified, &functions).unwrap_or_default();
 
-        Box::new(Error::Untargetable {
+        return Err(Box::new(Error::Untargetable {
             name: format!(
                 "Available functions: [{}]{}\n\nTip: Check that the function name matches exactly with a function in your template file.",
                 functions.join(", "),
                 suggestion
             ),
-        })
-    })?;
+        }));
+    }
 
     let function = &target.qualified;
+    let mut types = inputs.types.clone();
+    types.extend(case.types.clone());
+    let instance = inputs
+        .context
+        .instantiate(function, &types)
+        .map_err(|unresolved| unresolve(&unresolved, function, content, path))?;
+    let signature = &instance.signature;
 
     let parameters = shadowed(inputs.parameters, &case.parameters);
     let returns = shadowed(inputs.returns, &case.returns);
 
     let tags = merge(tags, &case.tags);
 
-    let validated = validate(signature, &parameters, &returns, content, path)?;
+    if case.panics.is_some() && case.error.is_some() {
+        let pattern = "\"panics\"";
+        return Err(Box::new(Error::test(
+            path,
+            content.to_string(),
+            content.find(pattern).map(|pos| (pos, pattern.len())),
+            "Conflicting expectations 'panics' and 'error'",
+            "A case either panics or returns an error; keep only one of the two.",
+        )));
+    }
+    let expectation = Expectation::from(case);
+    let tolerance = match (&case.tolerance, inputs.tolerance) {
+        (Some(tolerance), Some(defaults)) => Some(tolerance.or(defaults)),
+        (tolerance, defaults) => tolerance.clone().or_else(|| defaults.cloned()),
+    };
+
+    let validated = validate(
+        signature,
+        &parameters,
+        &returns,
+        &expectation,
+        content,
+        path,
+    )?;
 
     let (statements, actuals, comparisons) = generate(
-        function,
+        &instance.callee,
         signature,
         &validated.parameters,
         &validated.returns,
+        &expectation,
+        tolerance.as_ref(),
         path,
     )?;
 
     let name = target.qualified.replace("::", ".");
     let serialized = serde_json::to_value(&parameters)
         .expect("parameters are already Value and always serializable");
-    let returns =
-        serde_json::to_value(&returns).expect("returns are already Value and always serializable");
+    let returns = expectation.record(&returns);
+    let tolerance = tolerance.map(|tolerance| {
+        serde_json::to_value(tolerance).expect("tolerance is always serializable")
+    });
 
     Ok(Registration {
         name,
         tags,
         parameters: serialized,
         expected: returns,
+        tolerance,
         statements,
         actuals,
         comparisons,
+        property: None,
     })
 }
 
@@ -90,6 +176,7 @@ fn validate(
     signature: &Signature,
     parameters: &HashMap<String, Value>,
     returns: &HashMap<String, Value>,
+    expectation: &Expectation,
     content: &str,
     path: &str,
 ) -> Result<Instance, Box<Error>> {
@@ -103,7 +190,7 @@ fn validate(
             },
             FnArg::Receiver(_) => None,
         })
-        .map(|ident| ident.to_string())
+        .map(|ident| keyword::key(&ident))
         .collect::<Vec<String>>();
 
     for name in &expected {
@@ -167,22 +254,59 @@ fn validate(
         }
     }
 
-    if returns.is_empty() {
-        return Err(Box::new(Error::Missing {
-            field: "return validation".to_string(),
-            context: "test case - at least one return check was required".to_string(),
-        }));
-    }
+    expect(signature, returns, expectation, content, path)?;
 
     Ok(Instance {
         parameters: parameters
             .iter()
-            .map(|(k, v)| (syn::Ident::new(k, Span::call_site()), v.clone()))
+            .map(|(k, v)| (keyword::ident(k), v.clone()))
             .collect::<HashMap<_, _>>(),
         returns: returns.clone(),
     })
 }
 
+fn expect(
+    signature: &Signature,
+    returns: &HashMap<String, Value>,
+    expectation: &Expectation,
+    content: &str,
+    path: &str,
+) -> Result<(), Box<Error>> {
+    let ident = &signature.ident;
+    let (pattern, issue, suggestion) = match expectation {
+        Expectation::Returns if returns.is_empty() => {
+            return Err(Box::new(Error::Missing {
+                field: "return validation".to_string(),
+                context: "test case - at least one return check was required".to_string(),
+            }));
+        }
+        Expectation::Panics(_) if !returns.is_empty() => (
+            "\"panics\"",
+            format!("Return values alongside 'panics' for function '{ident}'"),
+            "A panicking case produces no values to compare; remove its 'returns'.",
+        ),
+        Expectation::Fails(_) if returns.contains_key(keyword::result().key) => (
+            "\"error\"",
+            format!("Return value '()' alongside 'error' for function '{ident}'"),
+            "A failing case has no return value; remove '()' from its 'returns'.",
+        ),
+        Expectation::Fails(_) if !fallible(signature) => (
+            "\"error\"",
+            format!("Function '{ident}' does not return a Result"),
+            "Only functions returning Result<T, E> with E: miette::Diagnostic can expect an 'error'.",
+        ),
+        Expectation::Returns | Expectation::Panics(_) | Expectation::Fails(_) => return Ok(()),
+    };
+
+    Err(Box::new(Error::test(
+        path,
+        content.to_string(),
+        content.find(pattern).map(|pos| (pos, pattern.len())),
+        &issue,
+        suggestion,
+    )))
+}
+
 fn shadow(target: &mut Value, patch: &Value) {
     match patch {
         Value::Object(rewrites) => {
@@ -232,18 +356,31 @@ fn merge(function: &[String], case: &[String]) -> Vec<String> {
     tags
 }
 
+fn fallible(signature: &Signature) -> bool {
+    match &signature.output {
+        syn::ReturnType::Type(_, ty) => matches!(
+            ty.as_ref(),
+            Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Result")
+        ),
+        syn::ReturnType::Default => false,
+    }
+}
+
 fn generate(
-    function: &str,
+    callee: &Expr,
     signature: &Signature,
     parameters: &HashMap<syn::Ident, Value>,
     expected: &HashMap<String, Value>,
+    expectation: &Expectation,
+    tolerance: Option<&Tolerance>,
     path: impl AsRef<Path>,
 ) -> Result<(Vec<syn::Stmt>, syn::Expr, Vec<syn::Stmt>), Box<Error>> {
     let mut code = Code::new();
 
     let arguments = parameterize(&mut code, signature, parameters, &path)?;
 
-    let required = expected.contains_key(keyword::result().key)
+    let required = !matches!(expectation, Expectation::Returns)
+        || expected.contains_key(keyword::result().key)
         || expected
             .iter()
             .any(|(k, _)| k == &keyword::result().variable.to_string());
@@ -253,22 +390,48 @@ fn generate(
         syn::Ident::new("_result", Span::call_site())
     };
 
-    let expression: Expr = syn::parse_str(function).map_err(|e| {
-        Box::new(Error::Untargetable {
-            name: format!(
-                "Failed to parse function name '{function}' as valid Rust expression: {e}. \
-                 Ensure the function name is a valid Rust identifier or path."
-            ),
-        })
-    })?;
-
-    let statement: syn::Stmt = syn::parse_quote! {
-        let #variable = #expression(#(#arguments),*);
+    let call: Expr = syn::parse_quote! { #callee(#(#arguments),*) };
+    let statement: syn::Stmt = match expectation {
+        Expectation::Panics(_) => syn::parse_quote! {
+            let #variable = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| #call));
+        },
+        Expectation::Returns | Expectation::Fails(_) => syn::parse_quote! {
+            let #variable = #call;
+        },
     };
     code.push(statement);
 
-    let actuals = recording(parameters, expected)?;
-    let comparisons = comparison(signature, parameters, expected, &path);
+    match expectation {
+        Expectation::Returns => {}
+        Expectation::Panics(_) => {
+            let panic = keyword::panic().variable;
+            code.push(syn::parse_quote! {
+                let #panic = #variable
+                    .as_ref()
+                    .err()
+                    .map(|payload| function::execution::message(payload.as_ref()));
+            });
+        }
+        Expectation::Fails(_) => {
+            let error = keyword::error().variable;
+            code.push(syn::parse_quote! {
+                let #error = #variable.as_ref().err().map(|error| {
+                    use miette::Diagnostic as _;
+                    error.code().map(|code| code.to_string()).unwrap_or_default()
+                });
+            });
+        }
+    }
+
+    let actuals = recording(parameters, expected, expectation)?;
+    let comparisons = comparison(
+        signature,
+        parameters,
+        expected,
+        expectation,
+        tolerance,
+        &path,
+    );
 
     Ok((code.statements, actuals, comparisons))
 }
@@ -346,9 +509,29 @@ fn parameterize(
 fn recording(
     parameters: &HashMap<syn::Ident, Value>,
     expected: &HashMap<String, Value>,
+    expectation: &Expectation,
 ) -> Result<syn::Expr, Box<Error>> {
     let mut expr: syn::Expr = syn::parse_quote! { function::Actuals::default() };
 
+    match expectation {
+        Expectation::Returns => {}
+        Expectation::Panics(_) => {
+            let key = keyword::panic().key;
+            let variable = keyword::panic().variable;
+            expr = syn::parse_quote! { #expr.record(#key, &#variable)? };
+        }
+        Expectation::Fails(_) => {
+            let key = keyword::error().key;
+            let variable = keyword::error().variable;
+            expr = syn::parse_quote! {
+                #expr.record(
+                    #key,
+                    &#variable.as_ref().map(|code| serde_json::json!({ "code": code })),
+                )?
+            };
+        }
+    }
+
     if expected.contains_key(keyword::result().key) {
         let key = keyword::result().key;
         let variable = keyword::result().variable;
@@ -359,7 +542,7 @@ fn recording(
         if parameter != keyword::result().key {
             let identifier = parameters
                 .keys()
-                .find(|ident| ident.to_string() == *parameter)
+                .find(|ident| keyword::key(ident) == *parameter)
                 .ok_or_else(|| {
                     Box::new(Error::Missing {
                         field: format!("parameter identifier for '{parameter}'"),
@@ -378,18 +561,37 @@ fn comparison(
     signature: &Signature,
     parameters: &HashMap<syn::Ident, Value>,
     expected: &HashMap<String, Value>,
+    expectation: &Expectation,
+    tolerance: Option<&Tolerance>,
     path: impl AsRef<Path>,
 ) -> Vec<syn::Stmt> {
     let mut statements = Vec::new();
 
+    match expectation {
+        Expectation::Returns => {}
+        Expectation::Panics(message) => {
+            let panic = keyword::panic().variable;
+            statements.push(syn::parse_quote! {
+                function::execution::panicked(&actuals, #message, #panic.as_deref())?;
+            });
+        }
+        Expectation::Fails(code) => {
+            let error = keyword::error().variable;
+            statements.push(syn::parse_quote! {
+                function::execution::failed(&actuals, #code, #error.as_deref())?;
+            });
+        }
+    }
+
     if let (Some(value), syn::ReturnType::Type(_, ty)) =
         (expected.get(keyword::result().key), &signature.output)
     {
         let variable = keyword::result().variable;
         let expectation = expression(ty.as_ref(), value, &path);
         let key = keyword::result().key;
+        let matched = matches(&variable, &expectation, tolerance);
         let stmt: syn::Stmt = syn::parse_quote! {
-            if !utility::equal(&#variable, &#expectation) {
+            if !#matched {
                 return Err(Box::new(function::error::Error::mismatch(
                     actuals.clone(),
                     format!("expected {}: {:?}, got: {:?}", #key, #expectation, #variable),
@@ -403,7 +605,7 @@ fn comparison(
         if key == keyword::result().key {
             continue;
         }
-        if let Some((ident, _)) = parameters.iter().find(|(i, _)| i.to_string() == *key) {
+        if let Some((ident, _)) = parameters.iter().find(|(i, _)| keyword::key(i) == *key) {
             let ty = signature.inputs.iter().find_map(|input| match input {
                 FnArg::Typed(pat) => match &*pat.pat {
                     syn::Pat::Ident(i) if i.ident == *ident => Some(pat.ty.as_ref()),
@@ -418,8 +620,9 @@ fn comparison(
                 };
                 let expectation = expression(inner, value, &path);
                 let label = key.as_str();
+                let matched = matches(ident, &expectation, tolerance);
                 let stmt: syn::Stmt = syn::parse_quote! {
-                    if !utility::equal(&#ident, &#expectation) {
+                    if !#matched {
                         return Err(Box::new(function::error::Error::mismatch(
                             actuals.clone(),
                             format!("expected {}: {:?}, got: {:?}", #label, #expectation, #ident),
@@ -433,3 +636,89 @@ fn comparison(
 
     statements
 }
+
+#[must_use]
+pub fn unresolve(unresolved: &Unresolved, function: &str, content: &str, path: &str) -> Box<Error> {
+    let header = format!("\"function\": \"{}\"", function.replace("::", "."));
+    let start = content.find(&header).unwrap_or_default();
+    let locate = |pattern: &str| {
+        content[start..]
+            .find(pattern)
+            .map(|pos| (start + pos, pattern.len()))
+    };
+    match unresolved {
+        Unresolved::Undefined => Box::new(Error::Untargetable {
+            name: function.to_string(),
+        }),
+        Unresolved::Ambiguous(candidates) => {
+            let candidates = candidates
+                .iter()
+                .map(|candidate| candidate.replace("::", "."))
+                .collect::<Vec<_>>();
+            Box::new(Error::test(
+                path,
+                content.to_string(),
+                locate(&header),
+                &format!("Method '{function}' is provided by more than one trait"),
+                &format!(
+                    "Name the trait in the target, one of: [{}]",
+                    candidates.join(", ")
+                ),
+            ))
+        }
+        Unresolved::Missing(name) => Box::new(Error::test(
+            path,
+            content.to_string(),
+            locate(&header),
+            &format!("Missing type argument '{name}' for generic function '{function}'"),
+            &format!("Instantiate every type parameter, e.g. \"types\": {{\"{name}\": \"i32\"}}."),
+        )),
+        Unresolved::Unknown(name, generics) => {
+            let suggestion = similarity::nearest(name, generics).unwrap_or_default();
+            Box::new(Error::test(
+                path,
+                content.to_string(),
+                locate(&format!("\"{name}\"")),
+                &format!("Unknown type parameter '{name}' for function '{function}'"),
+                &format!(
+                    "Type parameters of '{function}': [{}]{suggestion}",
+                    generics.join(", ")
+                ),
+            ))
+        }
+        Unresolved::Invalid(name, error) => Box::new(Error::test(
+            path,
+            content.to_string(),
+            locate(&format!("\"{name}\"")),
+            &format!(
+                "Generic argument '{name}' for function '{function}' is not a Rust type or constant"
+            ),
+            &format!("Failed to parse: {error}."),
+        )),
+    }
+}
+
+#[must_use]
+pub fn matches(actual: &syn::Ident, expected: &Expr, tolerance: Option<&Tolerance>) -> Expr {
+    let Some(tolerance) = tolerance else {
+        return syn::parse_quote! { utility::equal(&#actual, &#expected) };
+    };
+    let absolute = tolerance.absolute.unwrap_or_default();
+    let relative = tolerance.relative.unwrap_or_default();
+    let ulps = tolerance.ulps.unwrap_or_default();
+    let unordered = tolerance.unordered.unwrap_or_default();
+    let subset = tolerance.subset.unwrap_or_default();
+    syn::parse_quote! {
+        utility::approximate(
+            &#actual,
+            &#expected,
+            &utility::Tolerance {
+                absolute: #absolute,
+                relative: #relative,
+                ulps: #ulps,
+                unordered: #unordered,
+                subset: #subset,
+            },
+        )
+    }
+}
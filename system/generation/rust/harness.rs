@@ -80,7 +80,9 @@ pub fn measure(
             let inputs = function::Inputs {
                 parameters: &definition.parameters,
                 returns: &definition.returns,
-                functions: &context.functions,
+                tolerance: definition.tolerance.as_ref(),
+                types: &definition.types,
+                context,
             };
 
             let registration = function::build(
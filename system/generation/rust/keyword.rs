@@ -8,6 +8,26 @@ pub struct Parameters {
     pub variable: syn::Ident,
 }
 
+pub struct Panic {
+    pub key: &'static str,
+    pub variable: syn::Ident,
+}
+
+pub struct Error {
+    pub key: &'static str,
+    pub variable: syn::Ident,
+}
+
+pub struct Reference {
+    pub key: &'static str,
+    pub variable: syn::Ident,
+}
+
+pub struct Receiver {
+    pub key: &'static str,
+    pub variable: syn::Ident,
+}
+
 #[must_use]
 pub fn result() -> Result {
     Result {
@@ -23,3 +43,55 @@ pub fn parameters() -> Parameters {
         variable: syn::Ident::new("parameters", proc_macro2::Span::call_site()),
     }
 }
+
+#[must_use]
+pub fn panic() -> Panic {
+    Panic {
+        key: "panic",
+        variable: syn::Ident::new("panic", proc_macro2::Span::call_site()),
+    }
+}
+
+#[must_use]
+pub fn error() -> Error {
+    Error {
+        key: "error",
+        variable: syn::Ident::new("error", proc_macro2::Span::call_site()),
+    }
+}
+
+#[must_use]
+pub fn reference() -> Reference {
+    Reference {
+        key: "reference",
+        variable: syn::Ident::new("reference", proc_macro2::Span::call_site()),
+    }
+}
+
+#[must_use]
+pub fn receiver() -> Receiver {
+    Receiver {
+        key: "self",
+        variable: syn::Ident::new("self_", proc_macro2::Span::call_site()),
+    }
+}
+
+#[must_use]
+pub fn key(ident: &syn::Ident) -> String {
+    let receiver = receiver();
+    if *ident == receiver.variable {
+        receiver.key.to_string()
+    } else {
+        ident.to_string()
+    }
+}
+
+#[must_use]
+pub fn ident(key: &str) -> syn::Ident {
+    let receiver = receiver();
+    if key == receiver.key {
+        receiver.variable
+    } else {
+        syn::Ident::new(key, proc_macro2::Span::call_site())
+    }
+}
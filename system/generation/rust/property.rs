@@ -0,0 +1,277 @@
+use syn::{Expr, FnArg, Signature, Type};
+
+use component::generation::rust::{
+    error::Error,
+    schema::{Function, Generate, Invariant},
+    types::Callable,
+};
+use context::Context;
+use function::Registration;
+
+struct Binding {
+    ident: syn::Ident,
+    ty: Type,
+    mutable: bool,
+}
+
+impl Binding {
+    fn argument(&self) -> Expr {
+        let ident = &self.ident;
+        match &self.ty {
+            Type::Reference(r) if r.mutability.is_some() => syn::parse_quote! { &mut #ident },
+            Type::Reference(_) => syn::parse_quote! { &#ident },
+            _ => syn::parse_quote! { #ident },
+        }
+    }
+
+    fn statement(&self) -> syn::Stmt {
+        let ident = &self.ident;
+        let key = keyword::key(ident);
+        let parameters = keyword::parameters().variable;
+        let inner = match &self.ty {
+            Type::Reference(r) => r.elem.as_ref(),
+            ty => ty,
+        };
+        if self.mutable {
+            syn::parse_quote! {
+                let mut #ident = function::property::parameter::<#inner>(#parameters, #key)?;
+            }
+        } else {
+            syn::parse_quote! {
+                let #ident = function::property::parameter::<#inner>(#parameters, #key)?;
+            }
+        }
+    }
+}
+
+pub fn build(
+    generate: &Generate,
+    definition: &Function,
+    context: &Context,
+    content: &str,
+    path: &str,
+) -> Result<Registration, Box<Error>> {
+    let target = &definition.function;
+    let tolerance = definition.tolerance.as_ref();
+    let function = &target.qualified;
+    let instance = context
+        .instantiate(function, &definition.types)
+        .map_err(|unresolved| function::unresolve(&unresolved, function, content, path))?;
+    let signature = &instance.signature;
+
+    let bindings = bind(signature);
+    validate(generate, &bindings, function, content, path)?;
+
+    let result = keyword::result();
+    let returns = !matches!(&signature.output, syn::ReturnType::Default);
+
+    let mut statements = bindings.iter().map(Binding::statement).collect::<Vec<_>>();
+    let callee = &instance.callee;
+    let arguments = bindings.iter().map(Binding::argument).collect::<Vec<_>>();
+    let variable = &result.variable;
+    statements.push(if returns {
+        syn::parse_quote! { let #variable = #callee(#(#arguments),*); }
+    } else {
+        syn::parse_quote! { #callee(#(#arguments),*); }
+    });
+
+    let mut actuals: Expr = syn::parse_quote! { function::Actuals::default() };
+    if returns {
+        let key = result.key;
+        actuals = syn::parse_quote! { #actuals.record(#key, &#variable)? };
+    }
+    for binding in bindings.iter().filter(|binding| binding.mutable) {
+        let ident = &binding.ident;
+        let key = keyword::key(ident);
+        actuals = syn::parse_quote! { #actuals.record(#key, &#ident)? };
+    }
+
+    let mut comparisons = Vec::new();
+    let mut relations = Vec::new();
+    for invariant in &generate.invariants {
+        match invariant {
+            Invariant::Relation(relation) => relations.push(relation.clone()),
+            Invariant::Reference(name) => {
+                if !returns {
+                    return Err(Box::new(Error::test(
+                        path,
+                        content.to_string(),
+                        locate(content, function, name),
+                        &format!(
+                            "Reference '{name}' for function '{function}' has no return value to compare"
+                        ),
+                        "Reference invariants compare return values; use a relation over 'returns' instead.",
+                    )));
+                }
+                comparisons.push(reference(
+                    name,
+                    &bindings,
+                    context,
+                    definition,
+                    locate(content, function, name),
+                    content,
+                    path,
+                )?);
+            }
+        }
+    }
+
+    let specification = serde_json::json!({
+        "seed": generate.seed,
+        "count": generate.count,
+        "parameters": generate.parameters,
+        "relations": relations,
+    });
+
+    let mut tags = definition.tags.clone();
+    tags.push("property".to_string());
+
+    Ok(Registration {
+        name: target.qualified.replace("::", "."),
+        tags,
+        parameters: serde_json::to_value(&generate.parameters)
+            .expect("strategies are already Value and always serializable"),
+        expected: serde_json::json!({ "cases": generate.count }),
+        tolerance: tolerance.map(|tolerance| {
+            serde_json::to_value(tolerance).expect("tolerance is always serializable")
+        }),
+        statements,
+        actuals,
+        comparisons,
+        property: Some(specification.to_string()),
+    })
+}
+
+fn bind(signature: &Signature) -> Vec<Binding> {
+    signature
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Typed(typed) => match &*typed.pat {
+                syn::Pat::Ident(ident) => Some(Binding {
+                    ident: ident.ident.clone(),
+                    ty: typed.ty.as_ref().clone(),
+                    mutable: matches!(
+                        typed.ty.as_ref(),
+                        Type::Reference(r) if r.mutability.is_some()
+                    ),
+                }),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+fn validate(
+    generate: &Generate,
+    bindings: &[Binding],
+    function: &str,
+    content: &str,
+    path: &str,
+) -> Result<(), Box<Error>> {
+    let names = bindings
+        .iter()
+        .map(|binding| keyword::key(&binding.ident))
+        .collect::<Vec<_>>();
+    let span = locate(content, function, "\"generate\"");
+
+    if let Some(missing) = names
+        .iter()
+        .find(|name| !generate.parameters.contains_key(*name))
+    {
+        return Err(Box::new(Error::test(
+            path,
+            content.to_string(),
+            span,
+            &format!("Missing strategy for parameter '{missing}' of function '{function}'"),
+            "Every parameter needs a strategy under 'generate.parameters'.",
+        )));
+    }
+
+    if let Some(unknown) = generate
+        .parameters
+        .keys()
+        .find(|name| !names.contains(*name))
+    {
+        let suggestion = similarity::nearest(unknown, &names).unwrap_or_default();
+        return Err(Box::new(Error::test(
+            path,
+            content.to_string(),
+            locate(content, function, &format!("\"{unknown}\"")),
+            &format!("Unknown parameter '{unknown}' in generate block of function '{function}'"),
+            &format!("Strategies must be named after the function's parameters.{suggestion}"),
+        )));
+    }
+
+    Ok(())
+}
+
+fn locate(content: &str, function: &str, pattern: &str) -> Option<(usize, usize)> {
+    let start = content
+        .find(&format!("\"function\": \"{function}\""))
+        .unwrap_or_default();
+    content[start..]
+        .find(pattern)
+        .map(|pos| (start + pos, pattern.len()))
+}
+
+fn reference(
+    name: &str,
+    bindings: &[Binding],
+    context: &Context,
+    definition: &Function,
+    span: Option<(usize, usize)>,
+    content: &str,
+    path: &str,
+) -> Result<syn::Stmt, Box<Error>> {
+    let qualified = Callable::new(name).qualified;
+    if !context.functions.contains_key(&qualified) {
+        let available = context.functions.keys().cloned().collect::<Vec<_>>();
+        let suggestion = similarity::nearest(name, &available).unwrap_or_default();
+        return Err(Box::new(Error::test(
+            path,
+            content.to_string(),
+            span,
+            &format!("Reference function '{name}' is not defined in the template"),
+            &format!("Reference invariants must name a function in the template.{suggestion}"),
+        )));
+    }
+
+    let callee = context
+        .instantiate(&qualified, &definition.types)
+        .map_err(|unresolved| function::unresolve(&unresolved, &qualified, content, path))?
+        .callee;
+    let statements = bindings.iter().map(Binding::statement).collect::<Vec<_>>();
+    let arguments = bindings.iter().map(Binding::argument).collect::<Vec<_>>();
+    let result = keyword::result();
+    let variable = &result.variable;
+    let expected = keyword::reference();
+    let key = expected.key;
+    let ident = &expected.variable;
+    let matched = function::matches(
+        variable,
+        &syn::parse_quote! { #ident },
+        definition.tolerance.as_ref(),
+    );
+
+    Ok(syn::parse_quote! {
+        {
+            let #ident = {
+                #(#statements)*
+                #callee(#(#arguments),*)
+            };
+            if !#matched {
+                let mut actuals = actuals.clone();
+                actuals.insert(
+                    #key.to_string(),
+                    serde_json::to_value(&#ident).unwrap_or_default(),
+                );
+                return Err(Box::new(function::error::Error::mismatch(
+                    actuals,
+                    format!("expected (): {:?} from reference {}, got: {:?}", #ident, #name, #variable),
+                )));
+            }
+        }
+    })
+}
@@ -16,6 +16,7 @@ fn main() -> miette::Result<()> {
             syntax_document,
             translator_component_document,
             snapshot_document,
+            replay_document,
             query_document,
             arena_system_document,
             arena_observation_document,
@@ -37,12 +38,16 @@ fn main() -> miette::Result<()> {
             traversal_document,
             evaluate_document,
             evaluate_observation_document,
+            debug_document,
             translate_document,
             scale_document,
             observation_document,
             lifecycle_document,
             channel_document,
             expression_observation_document,
+            sample_document,
+            redaction_document,
+            metric_document,
             expression_math_document,
             vector_document,
             quaternion_document,
@@ -54,24 +59,40 @@ fn main() -> miette::Result<()> {
             hierarchy_document,
             filtering_document,
             peer_document,
+            record_document,
+            otlp_document,
+            analysis_document,
+            dashboard_document,
+            relay_document,
+            prometheus_document,
+            instrument_document,
             simple_document,
             complex_document,
             library_document,
             returns_document,
+            failure_document,
+            tolerance_document,
+            property_document,
+            method_document,
+            unresolved_document,
             similarity_document,
         ];
 
         html::generate(
             arguments,
             visualize::page(&arguments.root, groups, |body, group| {
-                card::cases(card::source(body, group), group, |returns, unexpected| {
-                    let actual = unexpected?;
-                    let divergences = difference::compare(returns, actual);
-                    if divergences.is_empty() {
-                        return None;
-                    }
-                    json::diff(returns, &divergences, 61).ok()
-                })
+                card::cases(
+                    card::source(body, group),
+                    group,
+                    |returns, unexpected, tolerance| {
+                        let actual = unexpected?;
+                        let divergences = difference::within(returns, actual, tolerance);
+                        if divergences.is_empty() {
+                            return None;
+                        }
+                        json::diff(returns, &divergences, 61).ok()
+                    },
+                )
             }),
         )
     })
@@ -68,6 +68,7 @@ fn bar(body: Body, index: &entry::Index, root: &str) -> Body {
                             &index::molten::spatialize::spatialize(root).href,
                             "Spatialize",
                         )
+                        .link(&index::molten::playground(root).href, "Playground")
                         .separator()
                         .link(&index::molten::info(root).href, "Info")
                         .link(&index::molten::notice(root).href, "Notice")
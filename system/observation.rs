@@ -17,27 +17,11 @@ pub fn initialize(sinks: &[String]) -> miette::Result<trace::Guard> {
         .iter()
         .map(|s| endpoint::resolve(&endpoint::normalize(s)))
         .collect::<error::Result<Vec<_>>>();
-    let resolved = report(resolved)?;
+    let mut resolved = report(resolved)?;
 
-    let (traces, urls): (Vec<_>, Vec<_>) = resolved
-        .into_iter()
-        .partition(|s| !matches!(s, Sink::Http(_)));
-
-    let traces = Some(traces)
-        .filter(|t| !t.is_empty())
-        .unwrap_or_else(|| vec![endpoint::stdout()]);
-
-    let cancellation = CancellationToken::new();
-
-    let mut guard = report(trace::initialize(traces, cancellation.clone()))?;
-
-    for url in urls.into_iter().filter_map(|s| match s {
-        Sink::Http(stream) => Some(stream.url),
-        _ => None,
-    }) {
-        let handle = report(http::Server::new(url, cancellation.clone()).spawn())?;
-        guard.track(handle);
+    if resolved.iter().all(|s| matches!(s, Sink::Http { .. })) {
+        resolved.push(endpoint::stdout());
     }
 
-    Ok(guard)
+    report(trace::initialize(resolved, CancellationToken::new()))
 }
@@ -0,0 +1,284 @@
+use channel::Channel;
+use filterable::Filterable as _;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use stream::{Event, Field, Identifier, Lifecycle, Metadata, Update};
+use tag::Expression;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interval {
+    pub id: Identifier,
+    pub metadata: Metadata,
+    pub channels: Vec<Channel>,
+    pub fields: Vec<Field>,
+    pub begin: u64,
+    pub end: u64,
+    pub children: Vec<usize>,
+}
+
+impl Interval {
+    #[must_use]
+    pub fn duration(&self) -> u64 {
+        self.end.saturating_sub(self.begin)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeline {
+    pub intervals: Vec<Interval>,
+    pub roots: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Statistic {
+    pub name: String,
+    pub count: usize,
+    pub p50: u64,
+    pub p95: u64,
+    pub max: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Step {
+    pub name: String,
+    pub span: u64,
+    pub duration: u64,
+}
+
+#[must_use]
+pub fn timeline(updates: &[Update]) -> Timeline {
+    let mut open = HashMap::<u64, Interval>::new();
+    let mut timeline = Timeline::default();
+
+    for span in updates.iter().cloned().filter_map(|update| match update {
+        Update::Span(span) => Some(span),
+        _ => None,
+    }) {
+        match span.lifecycle {
+            Lifecycle::Begin(begin) => {
+                open.insert(
+                    span.id.span,
+                    Interval {
+                        id: span.id,
+                        metadata: span.metadata,
+                        channels: span.channels,
+                        fields: begin.fields,
+                        begin: begin.timestamp,
+                        end: begin.timestamp,
+                        children: Vec::new(),
+                    },
+                );
+            }
+            Lifecycle::End(end) => {
+                let Some(mut interval) = open.remove(&span.id.span) else {
+                    continue;
+                };
+                interval.end = end.timestamp;
+
+                let index = timeline.intervals.len();
+                match interval.id.parent.and_then(|parent| open.get_mut(&parent)) {
+                    Some(parent) => parent.children.push(index),
+                    None => timeline.roots.push(index),
+                }
+                timeline.intervals.push(interval);
+            }
+        }
+    }
+
+    timeline
+}
+
+#[must_use]
+pub fn statistics(timeline: &Timeline) -> Vec<Statistic> {
+    let mut durations = BTreeMap::<&str, Vec<u64>>::new();
+    for interval in &timeline.intervals {
+        durations
+            .entry(interval.metadata.name.as_str())
+            .or_default()
+            .push(interval.duration());
+    }
+
+    durations
+        .into_iter()
+        .map(|(name, mut samples)| {
+            samples.sort_unstable();
+            Statistic {
+                name: name.to_string(),
+                count: samples.len(),
+                p50: percentile(&samples, 50),
+                p95: percentile(&samples, 95),
+                max: samples.last().copied().unwrap_or(0),
+            }
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn critical(timeline: &Timeline, trace: Option<u64>) -> Vec<Step> {
+    let root = timeline
+        .roots
+        .iter()
+        .map(|&index| &timeline.intervals[index])
+        .filter(|interval| trace.is_none_or(|trace| interval.id.trace == trace))
+        .max_by_key(|interval| interval.duration());
+
+    std::iter::successors(root, |interval| {
+        interval
+            .children
+            .iter()
+            .map(|&index| &timeline.intervals[index])
+            .max_by_key(|child| (child.end, child.duration()))
+    })
+    .map(|interval| Step {
+        name: interval.metadata.name.clone(),
+        span: interval.id.span,
+        duration: interval.duration(),
+    })
+    .collect()
+}
+
+#[must_use]
+pub fn folded(timeline: &Timeline) -> Vec<String> {
+    let mut stacks = BTreeMap::<String, u64>::new();
+    let mut pending = timeline
+        .roots
+        .iter()
+        .map(|&index| (index, String::new()))
+        .collect::<Vec<_>>();
+
+    while let Some((index, prefix)) = pending.pop() {
+        let interval = &timeline.intervals[index];
+        let stack = if prefix.is_empty() {
+            interval.metadata.name.clone()
+        } else {
+            format!("{prefix};{}", interval.metadata.name)
+        };
+
+        let nested = interval
+            .children
+            .iter()
+            .map(|&child| timeline.intervals[child].duration())
+            .sum::<u64>();
+        *stacks.entry(stack.clone()).or_default() += interval.duration().saturating_sub(nested);
+
+        pending.extend(
+            interval
+                .children
+                .iter()
+                .map(|&child| (child, stack.clone())),
+        );
+    }
+
+    stacks
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0)
+        .map(|(stack, weight)| format!("{stack} {weight}"))
+        .collect()
+}
+
+pub struct Filter<'a> {
+    expression: &'a Expression,
+    open: HashMap<u64, Vec<Channel>>,
+}
+
+impl<'a> Filter<'a> {
+    #[must_use]
+    pub fn new(expression: &'a Expression) -> Self {
+        Self {
+            expression,
+            open: HashMap::new(),
+        }
+    }
+
+    pub fn matches<'u>(&mut self, update: &'u Update) -> Option<&'u Event> {
+        match update {
+            Update::Span(span) => {
+                match span.lifecycle {
+                    Lifecycle::Begin(_) => {
+                        self.open.insert(span.id.span, span.channels.clone());
+                    }
+                    Lifecycle::End(_) => {
+                        self.open.remove(&span.id.span);
+                    }
+                }
+                None
+            }
+            Update::Event(event) => {
+                let inherited = event
+                    .parent
+                    .and_then(|parent| self.open.get(&parent))
+                    .filter(|_| event.channels.is_empty());
+                let channels = inherited.unwrap_or(&event.channels);
+                self.expression
+                    .channels(channels, event.metadata.level)
+                    .then_some(event)
+            }
+            Update::Snapshot(_) | Update::Metric(_) => None,
+        }
+    }
+}
+
+#[must_use]
+pub fn grep(updates: &[Update], expression: &Expression) -> Vec<Event> {
+    let mut filter = Filter::new(expression);
+    updates
+        .iter()
+        .filter_map(|update| filter.matches(update))
+        .cloned()
+        .collect()
+}
+
+#[must_use]
+pub fn chrome(updates: &[Update]) -> serde_json::Value {
+    let timeline = timeline(updates);
+    let traces = timeline
+        .intervals
+        .iter()
+        .map(|interval| (interval.id.span, interval.id.trace))
+        .collect::<HashMap<_, _>>();
+
+    let spans = timeline.intervals.iter().map(|interval| {
+        serde_json::json!({
+            "name": interval.metadata.name,
+            "cat": Channel::serialize(&interval.channels),
+            "ph": "X",
+            "ts": interval.begin,
+            "dur": interval.duration(),
+            "pid": 1,
+            "tid": interval.id.trace,
+            "args": arguments(&interval.fields),
+        })
+    });
+
+    let events = updates.iter().filter_map(|update| match update {
+        Update::Event(event) => Some(serde_json::json!({
+            "name": event.metadata.name,
+            "cat": Channel::serialize(&event.channels),
+            "ph": "i",
+            "s": "t",
+            "ts": event.timestamp,
+            "pid": 1,
+            "tid": event.parent.and_then(|parent| traces.get(&parent)).copied().unwrap_or(0),
+            "args": arguments(&event.fields),
+        })),
+        _ => None,
+    });
+
+    serde_json::Value::Array(spans.chain(events).collect())
+}
+
+fn arguments(fields: &[Field]) -> serde_json::Value {
+    fields
+        .iter()
+        .map(|field| (field.name.clone(), field.value.to_string().into()))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+fn percentile(sorted: &[u64], rank: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let index = (sorted.len() * rank).div_ceil(100).saturating_sub(1);
+    sorted[index.min(sorted.len() - 1)]
+}
@@ -2,7 +2,7 @@
 pub struct Argument {
     #[arg(
         long,
-        help = "Sink endpoints (e.g., log:///tmp/trace.jsonl, chrome:///tmp/trace.json, grpc://127.0.0.1:50051)"
+        help = "Sink endpoints (e.g., log:///tmp/trace.jsonl, chrome:///tmp/trace.json, record:///tmp/trace.record, grpc://127.0.0.1:50051, otlp://127.0.0.1:4317)"
     )]
     pub sink: Vec<String>,
 }
@@ -0,0 +1,280 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use channel::Channel;
+use filterable::Filterable as _;
+use stream::{Event, Level, Lifecycle, Snapshot, Update};
+
+const RETAINED: usize = 512;
+const THUMBNAILS: usize = 12;
+const RING: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bar {
+    pub span: u64,
+    pub name: String,
+    pub channels: Vec<Channel>,
+    pub depth: usize,
+    pub begin: u64,
+    pub end: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub nodes: usize,
+    pub edges: usize,
+    pub worlds: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct Dashboard {
+    open: HashMap<u64, Bar>,
+    closed: VecDeque<Bar>,
+    events: VecDeque<Event>,
+    snapshots: VecDeque<Snapshot>,
+    latest: u64,
+}
+
+impl Dashboard {
+    pub fn receive(&mut self, message: &str) -> bool {
+        serde_json::from_str::<Update>(message)
+            .map(|update| self.ingest(update))
+            .is_ok()
+    }
+
+    pub fn ingest(&mut self, update: Update) {
+        match update {
+            Update::Span(span) => match span.lifecycle {
+                Lifecycle::Begin(begin) => {
+                    self.latest = self.latest.max(begin.timestamp);
+                    let depth = span
+                        .id
+                        .parent
+                        .and_then(|parent| self.open.get(&parent))
+                        .map_or(0, |parent| parent.depth + 1);
+                    self.open.insert(
+                        span.id.span,
+                        Bar {
+                            span: span.id.span,
+                            name: span.metadata.name,
+                            channels: span.channels,
+                            depth,
+                            begin: begin.timestamp,
+                            end: None,
+                        },
+                    );
+                }
+                Lifecycle::End(end) => {
+                    self.latest = self.latest.max(end.timestamp);
+                    if let Some(mut bar) = self.open.remove(&span.id.span) {
+                        bar.end = Some(end.timestamp);
+                        retain(&mut self.closed, bar, RETAINED);
+                    }
+                }
+            },
+            Update::Event(event) => {
+                self.latest = self.latest.max(event.timestamp);
+                retain(&mut self.events, event, RETAINED);
+            }
+            Update::Snapshot(snapshot) => {
+                self.latest = self.latest.max(snapshot.timestamp);
+                retain(&mut self.snapshots, snapshot, THUMBNAILS);
+            }
+            Update::Metric(metric) => {
+                self.latest = self.latest.max(metric.timestamp);
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn bars(&self) -> Vec<&Bar> {
+        let mut bars = self
+            .closed
+            .iter()
+            .chain(self.open.values())
+            .collect::<Vec<_>>();
+        bars.sort_by_key(|bar| (bar.begin, bar.depth));
+        bars
+    }
+
+    pub fn events(&self, filter: &str) -> Result<Vec<&Event>, expression::Sourced> {
+        let expression = match filter.trim() {
+            "" => None,
+            text => Some(parse::parse(text)?),
+        };
+
+        Ok(self
+            .events
+            .iter()
+            .rev()
+            .filter(|event| {
+                expression.as_ref().is_none_or(|expression| {
+                    expression.channels(self.channels(event), event.metadata.level)
+                })
+            })
+            .collect())
+    }
+
+    #[must_use]
+    pub fn timeline(&self) -> String {
+        let bars = self.bars();
+        let Some(origin) = bars.iter().map(|bar| bar.begin).min() else {
+            return String::new();
+        };
+        let extent = self.latest.saturating_sub(origin).max(1);
+
+        let mut html = String::from("<ol>");
+        for bar in bars {
+            let end = bar.end.unwrap_or(self.latest);
+            let offset = permille(bar.begin.saturating_sub(origin), extent);
+            let width = permille(end.saturating_sub(bar.begin), extent).max(1);
+            write!(
+                html,
+                "<li style=\"--depth:{};margin-left:{}.{}%;width:{}.{}%\" title=\"{} µs\"{}>{}</li>",
+                bar.depth,
+                offset / 10,
+                offset % 10,
+                width / 10,
+                width % 10,
+                end.saturating_sub(bar.begin),
+                if bar.end.is_none() { " data-open" } else { "" },
+                escape::escape(&bar.name)
+            )
+            .unwrap();
+        }
+        html.push_str("</ol>");
+        html
+    }
+
+    #[must_use]
+    pub fn log(&self, filter: &str) -> String {
+        let events = match self.events(filter) {
+            Ok(events) => events,
+            Err(e) => return format!("<p>{}</p>", escape::escape(&e.to_string())),
+        };
+
+        let mut html = String::from("<ol>");
+        for event in events {
+            let fields = event
+                .fields
+                .iter()
+                .map(|field| format!("{}={}", field.name, field.value))
+                .collect::<Vec<_>>()
+                .join(" ");
+            write!(
+                html,
+                "<li data-level=\"{}\"><time>{}</time> <code>{}</code> {} <span>{}</span></li>",
+                level(event.metadata.level),
+                event.timestamp,
+                escape::escape(&event.metadata.target),
+                escape::escape(&Channel::serialize(self.channels(event))),
+                escape::escape(&fields)
+            )
+            .unwrap();
+        }
+        html.push_str("</ol>");
+        html
+    }
+
+    #[must_use]
+    pub fn snapshots(&self) -> String {
+        let mut html = String::from("<ol>");
+        for snapshot in self.snapshots.iter().rev() {
+            write!(
+                html,
+                "<li><figure>{}<figcaption>{} · {}</figcaption></figure></li>",
+                thumbnail(snapshot),
+                escape::escape(&snapshot.trigger),
+                snapshot.timestamp
+            )
+            .unwrap();
+        }
+        html.push_str("</ol>");
+        html
+    }
+
+    fn channels<'a>(&'a self, event: &'a Event) -> &'a [Channel] {
+        if !event.channels.is_empty() {
+            return &event.channels;
+        }
+        event
+            .parent
+            .and_then(|parent| {
+                self.open
+                    .get(&parent)
+                    .or_else(|| self.closed.iter().rev().find(|bar| bar.span == parent))
+            })
+            .map_or(&event.channels, |bar| &bar.channels)
+    }
+}
+
+#[must_use]
+pub fn summary(snapshot: &Snapshot) -> Option<Summary> {
+    let state = serde_json::from_slice::<serde_json::Value>(&snapshot.state).ok()?;
+    let length = |key: &str| {
+        state
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map_or(0, Vec::len)
+    };
+    Some(Summary {
+        nodes: length("nodes"),
+        edges: length("edges"),
+        worlds: state
+            .get("worlds")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0),
+    })
+}
+
+#[must_use]
+pub fn thumbnail(snapshot: &Snapshot) -> String {
+    let Some(summary) = summary(snapshot) else {
+        return "<svg viewBox=\"0 0 64 64\" role=\"img\"><text x=\"32\" y=\"36\" text-anchor=\"middle\">compressed</text></svg>".to_string();
+    };
+
+    let count = summary.nodes.min(RING);
+    let points = (0..count)
+        .map(|index| {
+            let angle = std::f64::consts::TAU * f64::from(u32::try_from(index).unwrap_or(0))
+                / f64::from(u32::try_from(count).unwrap_or(1));
+            (32.0 + 24.0 * angle.cos(), 32.0 + 24.0 * angle.sin())
+        })
+        .collect::<Vec<_>>();
+
+    let mut svg = format!(
+        "<svg viewBox=\"0 0 64 64\" role=\"img\" aria-label=\"{} nodes, {} edges, {} worlds\">",
+        summary.nodes, summary.edges, summary.worlds
+    );
+    for (x, y) in &points {
+        write!(svg, "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"1.5\"/>").unwrap();
+    }
+    write!(
+        svg,
+        "<text x=\"32\" y=\"35\" text-anchor=\"middle\">{}·{}</text></svg>",
+        summary.nodes, summary.edges
+    )
+    .unwrap();
+    svg
+}
+
+fn level(level: Level) -> &'static str {
+    match level {
+        Level::Trace => "trace",
+        Level::Debug => "debug",
+        Level::Info => "info",
+        Level::Warn => "warn",
+        Level::Error => "error",
+    }
+}
+
+fn retain<T>(queue: &mut VecDeque<T>, item: T, capacity: usize) {
+    if queue.len() == capacity {
+        queue.pop_front();
+    }
+    queue.push_back(item);
+}
+
+fn permille(value: u64, extent: u64) -> u64 {
+    value.saturating_mul(1000) / extent
+}
@@ -0,0 +1,141 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use interface::Dashboard;
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element, EventSource, MessageEvent};
+
+struct View {
+    container: Element,
+    dashboard: RefCell<Dashboard>,
+    scheduled: Cell<bool>,
+}
+
+pub fn initialize(document: &Document) {
+    let Ok(containers) = document.query_selector_all(&attribute::dashboard().selector()) else {
+        return;
+    };
+
+    for index in 0..containers.length() {
+        let Some(node) = containers.get(index) else {
+            continue;
+        };
+        let container: &Element = node.unchecked_ref();
+
+        if container.get_attribute(attribute::bound().name()).is_some() {
+            continue;
+        }
+        let _ = container.set_attribute(attribute::bound().name(), "");
+
+        bind(container);
+    }
+}
+
+fn find(container: &Element, selector: &str) -> Option<Element> {
+    container.query_selector(selector).ok().flatten()
+}
+
+fn bind(container: &Element) {
+    let Some(address) = container.get_attribute(attribute::dashboard().name()) else {
+        return;
+    };
+    let Ok(source) = EventSource::new(&address) else {
+        status(container, "unavailable");
+        return;
+    };
+
+    let view = Rc::new(View {
+        container: container.clone(),
+        dashboard: RefCell::new(Dashboard::default()),
+        scheduled: Cell::new(false),
+    });
+
+    let callback = {
+        let view = Rc::clone(&view);
+        Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(message) = event.data().as_string() else {
+                return;
+            };
+            if view.dashboard.borrow_mut().receive(&message) {
+                schedule(&view);
+            }
+        })
+    };
+    source.set_onmessage(Some(callback.as_ref().unchecked_ref()));
+    callback.forget();
+
+    let callback = {
+        let container = container.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            status(&container, "connected");
+        })
+    };
+    source.set_onopen(Some(callback.as_ref().unchecked_ref()));
+    callback.forget();
+
+    let callback = {
+        let container = container.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            status(&container, "disconnected");
+        })
+    };
+    source.set_onerror(Some(callback.as_ref().unchecked_ref()));
+    callback.forget();
+
+    if let Some(filter) = find(container, &attribute::filter().selector()) {
+        let callback = {
+            let view = Rc::clone(&view);
+            Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+                log(&view);
+            })
+        };
+        let _ = filter.add_event_listener_with_callback("input", callback.as_ref().unchecked_ref());
+        callback.forget();
+    }
+}
+
+fn status(container: &Element, text: &str) {
+    if let Some(target) = find(container, &attribute::status().selector()) {
+        target.set_text_content(Some(text));
+    }
+}
+
+fn schedule(view: &Rc<View>) {
+    if view.scheduled.replace(true) {
+        return;
+    }
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let callback = {
+        let view = Rc::clone(view);
+        Closure::once_into_js(move || {
+            view.scheduled.set(false);
+            render(&view);
+        })
+    };
+    let _ = window.request_animation_frame(callback.unchecked_ref());
+}
+
+fn render(view: &View) {
+    let dashboard = view.dashboard.borrow();
+    if let Some(target) = find(&view.container, &attribute::timeline().selector()) {
+        target.set_inner_html(&dashboard.timeline());
+    }
+    if let Some(target) = find(&view.container, &attribute::snapshots().selector()) {
+        target.set_inner_html(&dashboard.snapshots());
+    }
+    drop(dashboard);
+    log(view);
+}
+
+fn log(view: &View) {
+    let filter = find(&view.container, &attribute::filter().selector())
+        .and_then(|input| js_sys::Reflect::get(&input, &JsValue::from_str("value")).ok())
+        .and_then(|value| value.as_string())
+        .unwrap_or_default();
+    if let Some(target) = find(&view.container, &attribute::log().selector()) {
+        target.set_inner_html(&view.dashboard.borrow().log(&filter));
+    }
+}
@@ -0,0 +1,3 @@
+fn main() -> miette::Result<()> {
+    html::execute(|arguments| html::generate(arguments, dashboard::page(&arguments.root)))
+}
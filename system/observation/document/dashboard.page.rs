@@ -0,0 +1,49 @@
+use body::Body;
+
+#[must_use]
+pub fn page(root: &str) -> page::Page {
+    navigation::layout(
+        "Dashboard",
+        &index::observation::dashboard(root),
+        root,
+        |c| {
+            c.title("Dashboard")
+            .subtitle("Live observation in the browser")
+            .rule()
+            .paragraph(|p| {
+                p.text("Spans, events and snapshots stream from the ")
+                    .code("http://")
+                    .text(" sink serving this page. Filter the log with a channel expression such as ")
+                    .code("core,query.!debug")
+                    .text(".")
+            })
+            .division(|d| controls(d).compose(panels))
+            .data(attribute::dashboard(), &format!("{root}observation/stream"))
+            .html(&format!(
+                "<script type=\"module\">\nimport init from '{root}resource/system/observation/dashboard.js';\nawait init();\n</script>"
+            ))
+        },
+    )
+}
+
+fn controls(body: Body) -> Body {
+    body.paragraph(|p| p.text("connecting"))
+        .data(attribute::status(), "")
+        .attribute("role", "status")
+        .void("input")
+        .data(attribute::filter(), "")
+        .attribute("type", "search")
+        .attribute("spellcheck", "false")
+        .attribute("placeholder", "Channel expression")
+        .label("Channel filter")
+}
+
+fn panels(body: Body) -> Body {
+    body.chapter("Timeline", |s| {
+        s.division(|d| d).data(attribute::timeline(), "")
+    })
+    .chapter("Log", |s| s.division(|d| d).data(attribute::log(), ""))
+    .chapter("Snapshots", |s| {
+        s.division(|d| d).data(attribute::snapshots(), "")
+    })
+}
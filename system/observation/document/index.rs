@@ -7,3 +7,13 @@ pub fn observation(root: &str) -> index::Index {
         "observation",
     )
 }
+
+#[must_use]
+pub fn dashboard(root: &str) -> index::Index {
+    index::Index::new(
+        root,
+        "system/observation/dashboard.html",
+        index::Context::Vantle,
+        "dashboard",
+    )
+}
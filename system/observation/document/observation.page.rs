@@ -28,8 +28,10 @@ pub fn page(root: &str) -> page::Page {
                             s.bold("Files")
                                 .text(": Local recording via ")
                                 .code("log://")
-                                .text(" and ")
+                                .text(", ")
                                 .code("chrome://")
+                                .text(" and ")
+                                .code("record://")
                                 .text(" URIs")
                         })
                     })
@@ -38,6 +40,18 @@ pub fn page(root: &str) -> page::Page {
                             s.bold("Peers")
                                 .text(": Remote streaming via ")
                                 .code("grpc://")
+                                .text(" URIs, or to OpenTelemetry collectors via ")
+                                .code("otlp://")
+                                .text(" URIs")
+                        })
+                    })
+                    .item(|li| {
+                        li.content(|s| {
+                            s.bold("Browsers")
+                                .text(": A live ")
+                                .link(&index::observation::dashboard(root).href, |l| l.text("dashboard"))
+                                .text(" served by ")
+                                .code("http://")
                                 .text(" URIs")
                         })
                     })
@@ -47,6 +61,63 @@ pub fn page(root: &str) -> page::Page {
                         .link(&index::molten::readme(root).fragment("forge"), |l| l.text("Forge"))
                         .text(" for an example of configuring trace destinations.")
                 })
+                .chapter("Delivery", |ss| {
+                    ss.paragraph(|p| {
+                        p.text("A ")
+                            .code("grpc://")
+                            .text(" sink serves its traces to peers that connect to it. With ")
+                            .code("mode=push")
+                            .text(", it instead pushes them to a remote peer, reconnecting with exponential backoff:")
+                    })
+                    .list(|ul| {
+                        ul.glossary("buffer=N", ": Updates held in memory while disconnected")
+                            .glossary("spill=/path", ": Record file for updates beyond the buffer, drained after reconnecting and on the next start")
+                    })
+                    .paragraph(|p| {
+                        p.text("Updates still queued when a connection fails are sent again after reconnecting. Updates lost to backpressure or a full buffer are reported to the peer as a ")
+                            .code("dropped")
+                            .text(" event.")
+                    })
+                    .paragraph(|p| {
+                        p.text("Co-located peers can avoid TCP with ")
+                            .code("unix:///path/to.sock")
+                            .text(", a Unix domain socket, or ")
+                            .code("inproc://name")
+                            .text(", which passes updates over in-process channels without protobuf encoding.")
+                    })
+                })
+                .chapter("Authentication", |ss| {
+                    ss.paragraph(|p| {
+                        p.text("Peers serve unauthenticated plaintext by default. URL parameters enable TLS and bearer tokens; the matching peer ")
+                            .code("Assembler")
+                            .text(" methods configure only the serving side, so outbound connections never reuse a server's credentials:")
+                    })
+                    .list(|ul| {
+                        ul.glossary("certificate=/path&key=/path", ": PEM identity served by a peer, or presented by a client for mutual TLS")
+                            .glossary("authority=/path", ": PEM authority that verifies the server, or on a server, requires client certificates")
+                            .glossary("domain=name", ": Name expected on the server certificate")
+                            .glossary("token=secret", ": Bearer token required by a server or sent by a client, read from a file with @path")
+                            .glossary("plaintext=true", ": Allow a client to send its token over TCP without TLS")
+                    })
+                    .paragraph(|p| {
+                        p.text("Rejected tokens fail with ")
+                            .code("observation::peer::unauthenticated")
+                            .text(", and failed handshakes with ")
+                            .code("observation::peer::handshake")
+                            .text(". Tokens also guard ")
+                            .code("unix://")
+                            .text(" and ")
+                            .code("inproc://")
+                            .text(" peers. Sinks and sources connect in the background; ")
+                            .code("join")
+                            .text(" on their handle returns the error that ended them.")
+                    })
+                    .paragraph(|p| {
+                        p.text("Tokens sent over plain TCP fail with ")
+                            .code("observation::peer::exposed")
+                            .text(" unless the URL opts in. Token, key and salt parameters are stripped from addresses shown in errors and connection listings.")
+                    })
+                })
             })
             .rule()
             .chapter("Trace", |s| {
@@ -69,6 +140,95 @@ pub fn page(root: &str) -> page::Page {
                             .glossary("matching", ": Pattern matching")
                             .glossary("query", ": Graph queries")
                     })
+                    .paragraph(|p| {
+                        p.text("Channel names are hierarchical, e.g. ")
+                            .code("#[trace(channels = [hypergraph.match])]")
+                            .text(".")
+                    })
+                })
+                .chapter("Capture", |ss| {
+                    ss.paragraph(|p| {
+                        p.text("Further options record a function's inputs and outcome on its span:")
+                    })
+                    .list(|ul| {
+                        ul.glossary("fields = [source, rule]", ": Serialize the named arguments")
+                            .glossary("skip = [secret]", ": Serialize every argument except those named")
+                            .glossary("ret", ": Emit the return value as a returns event")
+                            .glossary("err", ": Emit an Err as an error event with its diagnostic code")
+                    })
+                    .paragraph(|p| {
+                        p.text("Values are serialized through ")
+                            .code("record")
+                            .text(", so builds without transparent observation compile them out. For example, ")
+                            .code("#[trace(channels = [core], fields = [first, second], ret, err)]")
+                            .text(".")
+                    })
+                })
+                .chapter("Expressions", |ss| {
+                    ss.paragraph(|p| {
+                        p.text("The ")
+                            .code("channels")
+                            .text(" query parameter of a sink selects spans and events with an expression:")
+                    })
+                    .list(|ul| {
+                        ul.glossary("a&b", ": Both a and b")
+                            .glossary("a.b", ": The channel a.b, or both a and b")
+                            .glossary("a,b", ": Either a or b")
+                            .glossary("!a", ": Not a")
+                            .glossary("hypergraph.*", ": hypergraph and every channel beneath it")
+                            .glossary("core:>=2", ": core with a weight of at least 2")
+                            .glossary("level>=debug", ": Spans and events at debug or above")
+                    })
+                    .paragraph(|p| {
+                        p.text("For example, ")
+                            .code("hypergraph.*,level>=info")
+                            .text(" keeps debug detail for hypergraph and info for everything else, given a sink ")
+                            .code("level")
+                            .text(" of debug.")
+                    })
+                })
+                .chapter("Redaction", |ss| {
+                    ss.paragraph(|p| {
+                        p.text("The ")
+                            .code("redact")
+                            .text(" query parameter rewrites span and event fields before they leave the process:")
+                    })
+                    .list(|ul| {
+                        ul.glossary("password:drop", ": Remove the field")
+                            .glossary("token*:hash", ": Replace matching fields with a keyed hash")
+                            .glossary("source:truncate(64)", ": Keep the first 64 characters")
+                    })
+                    .paragraph(|p| {
+                        p.text("Rules are separated by commas, and the first matching rule wins. A value of ")
+                            .code("@path")
+                            .text(" reads one rule per line from a file. The ")
+                            .code("limit")
+                            .text(" parameter elides serialized values larger than the given number of bytes.")
+                    })
+                    .paragraph(|p| {
+                        p.text("Hashes are keyed with a random secret chosen at startup, so they are stable within a process but cannot be reversed by hashing guesses. Processes that share a ")
+                            .code("salt")
+                            .text(" parameter produce the same hash for the same value.")
+                    })
+                })
+                .chapter("Metrics", |ss| {
+                    ss.paragraph(|p| {
+                        p.text("Metrics are declared with channels and aggregated in-process by each sink:")
+                    })
+                    .list(|ul| {
+                        ul.glossary("record::counter!(channels = [hypergraph], passes = n)", ": Running total, incremented by 1 when no value is given")
+                            .glossary("record::gauge!(channels = [hypergraph], nodes = n)", ": Latest value")
+                            .glossary("record::histogram!(channels = [arena], size = n)", ": Distribution over power-of-four buckets")
+                    })
+                    .paragraph(|p| {
+                        p.text("Aggregates are streamed to peers as metric updates, at most one every 250 milliseconds per metric and channel set, with the latest value sent when the sink closes. An ")
+                            .code("http://")
+                            .text(" sink also serves the latest values in Prometheus text format at ")
+                            .code("/metrics")
+                            .text(", labelled with weighted channels and with counters suffixed ")
+                            .code("_total")
+                            .text(".")
+                    })
                 })
             })
         },
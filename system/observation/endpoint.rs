@@ -1,13 +1,28 @@
 use expression::Expression;
 use layer::Backpressure;
 use parse::parse;
+use redaction::Redaction;
+use sample::Sample;
+use std::path::PathBuf;
 use tracing::level_filters::LevelFilter;
 use url::Url;
 
+const BUFFER: usize = 4096;
+
 pub struct Stream {
     pub url: Url,
     pub level: LevelFilter,
     pub channels: Expression,
+    pub redaction: Redaction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Delivery {
+    Serve,
+    Push {
+        buffer: usize,
+        spill: Option<PathBuf>,
+    },
 }
 
 pub enum Sink {
@@ -18,9 +33,27 @@ pub enum Sink {
     Chrome(Stream),
     Grpc {
         stream: Stream,
+        delivery: Delivery,
         backpressure: Backpressure,
+        sample: Sample,
+    },
+    Record {
+        stream: Stream,
+        rotation: Option<u64>,
+        backpressure: Backpressure,
+        sample: Sample,
+    },
+    Http {
+        stream: Stream,
+        backpressure: Backpressure,
+        sample: Sample,
+    },
+    Otlp {
+        stream: Stream,
+        service: String,
+        backpressure: Backpressure,
+        sample: Sample,
     },
-    Http(Stream),
 }
 
 #[must_use]
@@ -30,6 +63,7 @@ pub fn stdout() -> Sink {
             url: Url::parse("log://1").expect("static url"),
             level: LevelFilter::INFO,
             channels: Expression::Any,
+            redaction: Redaction::default(),
         },
         ansi: true,
     }
@@ -49,9 +83,20 @@ pub fn resolve(address: &str) -> error::Result<Sink> {
         source,
     })?;
 
+    let sample = sample(&url).map_err(|source| error::Error::Sample {
+        policy: url
+            .query_pairs()
+            .find(|(k, _)| k == "sample")
+            .map_or_else(String::new, |(_, v)| v.to_string()),
+        source,
+    })?;
+
+    let redaction = redaction(&url)?.limit(limit(&url)).salt(salt(&url));
+
     let stream = Stream {
         level: level(&url),
         channels,
+        redaction,
         url,
     };
 
@@ -61,11 +106,29 @@ pub fn resolve(address: &str) -> error::Result<Sink> {
             stream,
         }),
         "chrome" => Ok(Sink::Chrome(stream)),
-        "grpc" => Ok(Sink::Grpc {
+        "grpc" | "unix" | "inproc" => Ok(Sink::Grpc {
+            delivery: delivery(&stream.url),
+            backpressure: backpressure(&stream.url),
+            sample,
+            stream,
+        }),
+        "record" => Ok(Sink::Record {
+            rotation: rotation(&stream.url),
             backpressure: backpressure(&stream.url),
+            sample,
+            stream,
+        }),
+        "http" | "https" => Ok(Sink::Http {
+            backpressure: backpressure(&stream.url),
+            sample,
+            stream,
+        }),
+        "otlp" => Ok(Sink::Otlp {
+            service: service(&stream.url),
+            backpressure: backpressure(&stream.url),
+            sample,
             stream,
         }),
-        "http" | "https" => Ok(Sink::Http(stream)),
         scheme => Err(error::Error::Scheme {
             scheme: scheme.to_string(),
         }
@@ -109,6 +172,77 @@ fn channels(url: &Url) -> Result<Expression, expression::Sourced> {
         .map_or_else(|| Ok(Expression::Any), |(_, v)| parse(&v))
 }
 
+fn sample(url: &Url) -> Result<Sample, sample::Error> {
+    url.query_pairs()
+        .find(|(k, _)| k == "sample")
+        .map_or_else(|| Ok(Sample::All), |(_, v)| Sample::parse(&v))
+}
+
+fn redaction(url: &Url) -> error::Result<Redaction> {
+    let Some((_, rules)) = url.query_pairs().find(|(k, _)| k == "redact") else {
+        return Ok(Redaction::default());
+    };
+
+    let source = match rules.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path).map_err(|source| error::Error::Rules {
+            path: path.to_string(),
+            source,
+        })?,
+        None => rules.to_string(),
+    };
+
+    Redaction::parse(&source).map_err(|source| {
+        error::Error::Redaction {
+            rules: rules.to_string(),
+            source,
+        }
+        .into()
+    })
+}
+
+fn limit(url: &Url) -> Option<usize> {
+    url.query_pairs()
+        .find(|(k, _)| k == "limit")
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+}
+
+fn salt(url: &Url) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == "salt")
+        .map(|(_, v)| v.into_owned())
+}
+
+fn delivery(url: &Url) -> Delivery {
+    let mode = url.query_pairs().find(|(k, _)| k == "mode");
+    if mode.is_none_or(|(_, v)| v != "push") {
+        return Delivery::Serve;
+    }
+
+    Delivery::Push {
+        buffer: url
+            .query_pairs()
+            .find(|(k, _)| k == "buffer")
+            .and_then(|(_, v)| v.parse::<usize>().ok())
+            .unwrap_or(BUFFER),
+        spill: url
+            .query_pairs()
+            .find(|(k, _)| k == "spill")
+            .map(|(_, v)| PathBuf::from(v.as_ref())),
+    }
+}
+
+fn rotation(url: &Url) -> Option<u64> {
+    url.query_pairs()
+        .find(|(k, _)| k == "rotate")
+        .and_then(|(_, v)| v.parse::<u64>().ok())
+}
+
+fn service(url: &Url) -> String {
+    url.query_pairs()
+        .find(|(k, _)| k == "service")
+        .map_or_else(|| "vantle".to_string(), |(_, v)| v.to_string())
+}
+
 fn backpressure(url: &Url) -> Backpressure {
     url.query_pairs().find(|(k, _)| k == "backpressure").map_or(
         Backpressure::default(),
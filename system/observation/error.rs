@@ -16,6 +16,102 @@ pub enum Error {
         source: std::net::AddrParseError,
     },
 
+    #[error("Unsupported peer address: {address}")]
+    #[diagnostic(
+        code(observation::peer::transport),
+        help("Use grpc://host:port, unix:///path/to.sock or inproc://name")
+    )]
+    Transport { address: String },
+
+    #[error("Failed to bind socket: {path}")]
+    #[diagnostic(
+        code(observation::server::bind),
+        help("Check that the port is free, or that the socket directory exists and is writable")
+    )]
+    Bind {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("In-process peer already serving: {name}")]
+    #[diagnostic(
+        code(observation::server::registered),
+        help("Give each in-process peer a distinct inproc:// name")
+    )]
+    Registered { name: String },
+
+    #[error("Failed to read credential: {path}")]
+    #[diagnostic(
+        code(observation::peer::credential),
+        help("Check that the certificate, key or token file exists and is readable")
+    )]
+    Credential {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Incomplete TLS identity: {address}")]
+    #[diagnostic(
+        code(observation::peer::identity),
+        help("Provide both a certificate and a key, which mutual TLS also requires")
+    )]
+    Identity { address: String },
+
+    #[error("Invalid TLS configuration: {address}")]
+    #[diagnostic(
+        code(observation::peer::tls),
+        help("Check that the certificate, key and authority are PEM encoded")
+    )]
+    Tls {
+        address: String,
+        #[source]
+        source: tonic::transport::Error,
+    },
+
+    #[error("Invalid bearer token")]
+    #[diagnostic(
+        code(observation::peer::token),
+        help("Tokens may only contain visible ASCII characters")
+    )]
+    Token,
+
+    #[error("Secure connection failed: {address}: {details}")]
+    #[diagnostic(
+        code(observation::peer::handshake),
+        help("Check the authority, the domain name and any client identity")
+    )]
+    Handshake { address: String, details: String },
+
+    #[error("Connection dropped: {address}: {details}")]
+    #[diagnostic(
+        code(observation::peer::plaintext),
+        help("The peer may require TLS; pass authority=/path/to/ca.pem")
+    )]
+    Plaintext { address: String, details: String },
+
+    #[error("Refusing to send a token without TLS: {address}")]
+    #[diagnostic(
+        code(observation::peer::exposed),
+        help("Pass authority=/path/to/ca.pem for TLS, or opt in with plaintext=true")
+    )]
+    Exposed { address: String },
+
+    #[error("Peer rejected credentials: {address}")]
+    #[diagnostic(
+        code(observation::peer::unauthenticated),
+        help("Use the token the remote peer serves with, e.g. token=@/path/to/token")
+    )]
+    Unauthenticated { address: String },
+
+    #[error("Record outside the peer directory: {path}")]
+    #[diagnostic(
+        code(observation::peer::confined),
+        help("Only records stored in the serving peer's directory can be replayed")
+    )]
+    Confined { path: String },
+
     #[error("No source address configured")]
     #[diagnostic(
         code(observation::client::source),
@@ -82,6 +178,24 @@ pub enum Error {
         source: serde_json::Error,
     },
 
+    #[error("Invalid record frame: {path}")]
+    #[diagnostic(
+        code(observation::record::frame),
+        help("The record may have been truncated while it was being written")
+    )]
+    Frame {
+        path: String,
+        #[source]
+        source: prost::DecodeError,
+    },
+
+    #[error("Record frame of {length} bytes exceeds the limit: {path}")]
+    #[diagnostic(
+        code(observation::record::oversized),
+        help("The record is corrupted; frames are never written this large")
+    )]
+    Oversized { path: String, length: u64 },
+
     #[error("Server initialization failed")]
     #[diagnostic(code(observation::server::transport), help("Check port availability"))]
     Server {
@@ -95,4 +209,14 @@ pub enum Error {
         help("Verify the handle was returned from sink() or source()")
     )]
     Handle { handle: u64 },
+
+    #[error("Remote call failed: {source}")]
+    #[diagnostic(
+        code(observation::peer::status),
+        help("Check that the remote peer serves the requested service")
+    )]
+    Status {
+        #[source]
+        source: Box<tonic::Status>,
+    },
 }
@@ -1,25 +1,58 @@
+use std::collections::BTreeMap;
+use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use axum::Router;
-use axum::extract::Request;
+use axum::extract::{Request, State};
 use axum::middleware;
-use axum::response::Response;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use tokio::sync::broadcast;
+use tokio::sync::mpsc::Receiver;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
 use tokio_util::sync::CancellationToken;
 use tower_http::services::ServeDir;
 use tower_http::trace::TraceLayer;
 use tracing::Instrument;
 use url::Url;
 
+pub const STREAM: &str = "/observation/stream";
+pub const METRICS: &str = "/metrics";
+
+const CAPACITY: usize = 1024;
+
+type Metrics = Arc<Mutex<BTreeMap<(String, Vec<stream::channel::Channel>), stream::Metric>>>;
+
+#[derive(Clone)]
+struct Shared {
+    live: broadcast::Sender<stream::Update>,
+    metrics: Metrics,
+}
+
 pub struct Server {
     url: Url,
     cancellation: CancellationToken,
+    updates: Option<Receiver<stream::Update>>,
 }
 
 impl Server {
     #[must_use]
     pub fn new(url: Url, cancellation: CancellationToken) -> Self {
-        Self { url, cancellation }
+        Self {
+            url,
+            cancellation,
+            updates: None,
+        }
+    }
+
+    #[must_use]
+    pub fn live(mut self, updates: Receiver<stream::Update>) -> Self {
+        self.updates = Some(updates);
+        self
     }
 
     pub fn spawn(self) -> error::Result<tokio::task::JoinHandle<()>> {
@@ -42,9 +75,22 @@ impl Server {
 
         let span = tracing::info_span!("http", channels = "http");
 
+        let cancellation = self.cancellation;
+        let (live, _) = broadcast::channel(CAPACITY);
+        let shared = Shared {
+            live,
+            metrics: Metrics::default(),
+        };
+        if let Some(updates) = self.updates {
+            tokio::spawn(forward(updates, shared.clone(), cancellation.clone()));
+        }
+
         let handle = tokio::spawn(
             async move {
                 let application = Router::new()
+                    .route(STREAM, get(subscribe))
+                    .route(METRICS, get(scrape))
+                    .with_state(shared)
                     .fallback_service(ServeDir::new(&directory))
                     .layer(middleware::from_fn(nocache))
                     .layer(TraceLayer::new_for_http());
@@ -59,7 +105,7 @@ impl Server {
 
                 tracing::info!("serving {} at http://{address}", directory.display());
 
-                let shutdown = self.cancellation.cancelled_owned();
+                let shutdown = cancellation.cancelled_owned();
                 if let Err(e) = axum::serve(listener, application)
                     .with_graceful_shutdown(shutdown)
                     .await
@@ -74,6 +120,46 @@ impl Server {
     }
 }
 
+async fn forward(mut updates: Receiver<stream::Update>, shared: Shared, token: CancellationToken) {
+    loop {
+        tokio::select! {
+            () = token.cancelled() => break,
+            received = updates.recv() => {
+                let Some(update) = received else { break };
+                if let stream::Update::Metric(metric) = &update {
+                    shared
+                        .metrics
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .insert((metric.name.clone(), metric.channels.clone()), metric.clone());
+                }
+                let _ = shared.live.send(update);
+            }
+        }
+    }
+}
+
+async fn subscribe(
+    State(shared): State<Shared>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = BroadcastStream::new(shared.live.subscribe()).filter_map(|received| {
+        received
+            .ok()
+            .and_then(|update| Event::default().json_data(update).ok())
+            .map(Ok)
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+async fn scrape(State(shared): State<Shared>) -> Response {
+    let metrics = shared
+        .metrics
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let text = encode::prometheus::exposition(metrics.values());
+    ([("content-type", encode::prometheus::CONTENT)], text).into_response()
+}
+
 async fn nocache(request: Request, next: middleware::Next) -> Response {
     let mut response = next.run(request).await;
     response
@@ -1,17 +1,109 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
 use quote::quote;
-use syn::{ItemFn, parse_macro_input};
+use syn::{FnArg, Ident, ItemFn, Pat, parse_macro_input};
 use trace as arguments;
 
+fn parameters(input: &ItemFn) -> Vec<Ident> {
+    input
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|argument| match argument {
+            FnArg::Typed(typed) => match typed.pat.as_ref() {
+                Pat::Ident(pattern) => Some(pattern.ident.clone()),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+fn captured(args: &arguments::Arguments, input: &ItemFn) -> syn::Result<Vec<Ident>> {
+    let parameters = parameters(input);
+
+    let named = args.fields.iter().flatten().chain(&args.skip);
+    for name in named {
+        if !parameters.contains(name) {
+            return Err(syn::Error::new(name.span(), "unknown argument"));
+        }
+    }
+
+    if !args.captures() {
+        return Ok(vec![]);
+    }
+
+    Ok(args
+        .fields
+        .clone()
+        .unwrap_or(parameters)
+        .into_iter()
+        .filter(|name| !args.skip.contains(name))
+        .collect())
+}
+
+fn outcome(args: &arguments::Arguments, input: &ItemFn) -> Tokens {
+    let block = &input.block;
+    if !args.ret && !args.err {
+        return quote! { #block };
+    }
+
+    let invoke = if input.sig.asyncness.is_some() {
+        quote! { async move #block.await }
+    } else {
+        quote! { (move || #block)() }
+    };
+
+    let returned = quote! {
+        ::tracing::debug!(returns = %::record::serialize::json(value))
+    };
+    let failed = quote! {
+        ::tracing::error!(
+            code = %::record::diagnostic::code(error),
+            error = %::record::diagnostic::message(error)
+        )
+    };
+
+    let recorded = match (args.ret, args.err) {
+        (true, true) => quote! {
+            match &result {
+                Ok(value) => #returned,
+                Err(error) => #failed,
+            }
+        },
+        (true, false) => quote! {
+            let value = &result;
+            #returned;
+        },
+        _ => quote! {
+            if let Err(error) = &result {
+                #failed;
+            }
+        },
+    };
+
+    quote! {{
+        #[allow(clippy::redundant_closure_call)]
+        let result = #invoke;
+        #recorded
+        result
+    }}
+}
+
 #[proc_macro_attribute]
 pub fn trace(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as arguments::Arguments);
     let input = parse_macro_input!(item as ItemFn);
 
+    let captured = match captured(&args, &input) {
+        Ok(captured) => captured,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     let mut specs = args
         .channels
         .iter()
-        .map(|c| (c.name.to_string(), c.weight))
+        .map(|c| (c.name(), c.weight))
         .collect::<Vec<_>>();
     specs.sort_by(|a, b| a.0.cmp(&b.0));
 
@@ -24,12 +116,16 @@ pub fn trace(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attrs = &input.attrs;
     let vis = &input.vis;
     let sig = &input.sig;
-    let block = &input.block;
+    let body = outcome(&args, &input);
 
     let expanded = quote! {
         #(#attrs)*
-        #[::tracing::instrument(level = "debug", skip_all, fields(channels = #channels))]
-        #vis #sig #block
+        #[::tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(channels = #channels #(, #captured = %::record::serialize::json(&#captured))*)
+        )]
+        #vis #sig #body
     };
 
     TokenStream::from(expanded)
@@ -1,14 +1,35 @@
+use proc_macro2::Span;
 use syn::parse::{Parse, ParseStream};
 use syn::{Ident, LitInt, Token};
 
 pub struct Specification {
-    pub name: Ident,
+    pub path: Vec<Ident>,
     pub weight: u8,
 }
 
+impl Specification {
+    #[must_use]
+    pub fn name(&self) -> String {
+        self.path
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.path[0].span()
+    }
+}
+
 impl Parse for Specification {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let name: Ident = input.parse()?;
+        let mut path = vec![input.parse::<Ident>()?];
+        while input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            path.push(input.parse()?);
+        }
         let weight = if input.peek(Token![:]) {
             input.parse::<Token![:]>()?;
             let lit: LitInt = input.parse()?;
@@ -16,6 +37,6 @@ impl Parse for Specification {
         } else {
             1
         };
-        Ok(Specification { name, weight })
+        Ok(Specification { path, weight })
     }
 }
@@ -2,42 +2,96 @@ use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{Ident, Token, bracketed};
 
+#[derive(Default)]
 pub struct Arguments {
     pub channels: Vec<channel::Specification>,
+    pub fields: Option<Vec<Ident>>,
+    pub skip: Vec<Ident>,
+    pub ret: bool,
+    pub err: bool,
 }
 
-impl Parse for Arguments {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.is_empty() {
-            return Ok(Arguments { channels: vec![] });
-        }
+impl Arguments {
+    #[must_use]
+    pub fn captures(&self) -> bool {
+        self.fields.is_some() || !self.skip.is_empty()
+    }
+}
 
-        let ident: Ident = input.parse()?;
-        if ident != "channels" {
-            return Err(syn::Error::new(ident.span(), "expected 'channels'"));
-        }
+fn list<T: Parse>(input: ParseStream) -> syn::Result<Vec<T>> {
+    input.parse::<Token![=]>()?;
+
+    let content;
+    bracketed!(content in input);
+
+    let items: Punctuated<T, Token![,]> = content.parse_terminated(T::parse, Token![,])?;
+    Ok(items.into_iter().collect())
+}
 
-        input.parse::<Token![=]>()?;
+fn unique<T, K: Eq + std::hash::Hash>(
+    items: &[T],
+    key: impl Fn(&T) -> (K, proc_macro2::Span),
+    message: &str,
+) -> syn::Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for item in items {
+        let (name, span) = key(item);
+        if !seen.insert(name) {
+            return Err(syn::Error::new(span, message));
+        }
+    }
+    Ok(())
+}
 
-        let content;
-        bracketed!(content in input);
+impl Parse for Arguments {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut arguments = Arguments::default();
+        let mut given = std::collections::HashSet::new();
 
-        let specifications: Punctuated<channel::Specification, Token![,]> =
-            content.parse_terminated(channel::Specification::parse, Token![,])?;
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if !given.insert(ident.to_string()) {
+                return Err(syn::Error::new(ident.span(), "duplicate option"));
+            }
 
-        let channels = specifications.into_iter().collect::<Vec<_>>();
+            match ident.to_string().as_str() {
+                "channels" => {
+                    arguments.channels = list(input)?;
+                    unique(
+                        &arguments.channels,
+                        |s| (s.name(), s.span()),
+                        "duplicate channel",
+                    )?;
+                }
+                "fields" => {
+                    let fields = list::<Ident>(input)?;
+                    unique(&fields, |f| (f.to_string(), f.span()), "duplicate field")?;
+                    arguments.fields = Some(fields);
+                }
+                "skip" => {
+                    arguments.skip = list(input)?;
+                    unique(
+                        &arguments.skip,
+                        |f| (f.to_string(), f.span()),
+                        "duplicate field",
+                    )?;
+                }
+                "ret" => arguments.ret = true,
+                "err" => arguments.err = true,
+                _ => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "expected 'channels', 'fields', 'skip', 'ret' or 'err'",
+                    ));
+                }
+            }
 
-        let mut seen = std::collections::HashSet::new();
-        for specification in &channels {
-            let name = specification.name.to_string();
-            if !seen.insert(name.clone()) {
-                return Err(syn::Error::new(
-                    specification.name.span(),
-                    "duplicate channel",
-                ));
+            if input.is_empty() {
+                break;
             }
+            input.parse::<Token![,]>()?;
         }
 
-        Ok(Arguments { channels })
+        Ok(arguments)
     }
 }
@@ -0,0 +1,198 @@
+use clap::{Parser, Subcommand};
+use miette::{Diagnostic, Result};
+use std::path::{Path, PathBuf};
+use stream::Update;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum Error {
+    #[error("invalid channel expression: {expression}")]
+    #[diagnostic(
+        code(observe::channels),
+        help(
+            "use . or & (and), , (or), ! (not), () (group), name.* (subtree), name:>=N (weight), level>=L — e.g. hypergraph.*,level>=info"
+        )
+    )]
+    Channels {
+        expression: String,
+        #[source]
+        source: expression::Sourced,
+    },
+
+    #[error("unable to write output to {path}")]
+    #[diagnostic(code(observe::output), help("check that the output path is writable"))]
+    Output {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("unable to serialize {what}")]
+    #[diagnostic(
+        code(observe::serialize),
+        help("the recording holds values that cannot be rendered as json")
+    )]
+    Serialize {
+        what: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Record(#[from] error::Error),
+}
+
+#[derive(Parser)]
+#[command(name = "observe")]
+#[command(about = "Analyze recorded observation files", long_about = None)]
+struct Arguments {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Duration statistics per span name")]
+    Statistics {
+        #[arg(help = "Recorded observation file")]
+        record: PathBuf,
+    },
+
+    #[command(about = "Critical path of the longest trace")]
+    Critical {
+        #[arg(help = "Recorded observation file")]
+        record: PathBuf,
+
+        #[arg(long, help = "Restrict to a trace identifier")]
+        trace: Option<u64>,
+    },
+
+    #[command(about = "Folded stacks for flamegraph tools")]
+    Fold {
+        #[arg(help = "Recorded observation file")]
+        record: PathBuf,
+    },
+
+    #[command(about = "Stream events matching a channel expression as JSON lines")]
+    Grep {
+        #[arg(help = "Recorded observation file")]
+        record: PathBuf,
+
+        #[arg(help = "Channel expression (e.g., core,query.level>=info)")]
+        channels: String,
+    },
+
+    #[command(about = "Convert to the Chrome trace format")]
+    Chrome {
+        #[arg(help = "Recorded observation file")]
+        record: PathBuf,
+
+        #[arg(long, help = "Write the trace to a file instead of stdout")]
+        output: Option<PathBuf>,
+    },
+}
+
+fn main() -> Result<()> {
+    command::execute(
+        |_: &Arguments| Ok(()),
+        |arguments, _runtime| match arguments.command {
+            Command::Statistics { ref record } => statistics(record),
+            Command::Critical { ref record, trace } => critical(record, trace),
+            Command::Fold { ref record } => fold(record),
+            Command::Grep {
+                ref record,
+                ref channels,
+            } => grep(record, channels),
+            Command::Chrome {
+                ref record,
+                ref output,
+            } => chrome(record, output.as_deref()),
+        },
+    )
+}
+
+fn load(path: &Path) -> Result<Vec<Update>> {
+    Ok(record::read(path).map_err(Error::from)?)
+}
+
+fn statistics(path: &Path) -> Result<()> {
+    let statistics = analysis::statistics(&analysis::timeline(&load(path)?));
+    let width = statistics
+        .iter()
+        .map(|statistic| statistic.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    println!(
+        "{:<width$} {:>8} {:>12} {:>12} {:>12}",
+        "name", "count", "p50 (µs)", "p95 (µs)", "max (µs)"
+    );
+    for statistic in statistics {
+        println!(
+            "{:<width$} {:>8} {:>12} {:>12} {:>12}",
+            statistic.name, statistic.count, statistic.p50, statistic.p95, statistic.max
+        );
+    }
+    Ok(())
+}
+
+fn critical(path: &Path, trace: Option<u64>) -> Result<()> {
+    let steps = analysis::critical(&analysis::timeline(&load(path)?), trace);
+    for (depth, step) in steps.iter().enumerate() {
+        println!(
+            "{:indent$}{} [{}] {} µs",
+            "",
+            step.name,
+            step.span,
+            step.duration,
+            indent = depth * 2
+        );
+    }
+    Ok(())
+}
+
+fn fold(path: &Path) -> Result<()> {
+    for line in analysis::folded(&analysis::timeline(&load(path)?)) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn grep(path: &Path, channels: &str) -> Result<()> {
+    let expression = parse::parse(channels).map_err(|source| Error::Channels {
+        expression: channels.to_string(),
+        source,
+    })?;
+
+    let mut filter = analysis::Filter::new(&expression);
+    for update in record::open(path).map_err(Error::from)? {
+        let update = update.map_err(Error::from)?;
+        if let Some(event) = filter.matches(&update) {
+            let rendered = serde_json::to_string(event).map_err(|source| Error::Serialize {
+                what: "event",
+                source,
+            })?;
+            println!("{rendered}");
+        }
+    }
+    Ok(())
+}
+
+fn chrome(path: &Path, output: Option<&Path>) -> Result<()> {
+    let trace = analysis::chrome(&load(path)?);
+    let rendered = serde_json::to_string(&trace).map_err(|source| Error::Serialize {
+        what: "chrome trace",
+        source,
+    })?;
+
+    match output {
+        Some(output) => std::fs::write(output, rendered).map_err(|source| Error::Output {
+            path: output.display().to_string(),
+            source,
+        })?,
+        None => println!("{rendered}"),
+    }
+    Ok(())
+}
@@ -2,23 +2,49 @@ pub use error;
 
 use assemble::Assemble;
 use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use hyper_util::rt::TokioIo;
 use proto::observation::sink_client::SinkClient;
 use proto::observation::sink_server::{Sink, SinkServer};
 use proto::observation::source_client::SourceClient;
 use proto::observation::source_server::{Source, SourceServer};
-use proto::observation::{Acknowledge, Command, Record, Update};
+use proto::observation::{Acknowledge, Command, Playback, Record, Update};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
 use std::net::SocketAddr;
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Component, Path, PathBuf};
 use std::pin::Pin;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::sync::broadcast;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::Duration;
+use tokio::net::{UnixListener, UnixStream};
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::{ReceiverStream, UnixListenerStream};
 use tokio_stream::{Stream, StreamExt};
 use tokio_util::sync::CancellationToken;
-use tonic::{Request, Response, Status, Streaming};
+use tonic::metadata::MetadataValue;
+use tonic::service::Interceptor;
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::{
+    Certificate, Channel, ClientTlsConfig, Endpoint, Identity, ServerTlsConfig, Uri,
+};
+use tonic::{Code, Request, Response, Status, Streaming};
 use url::Url;
 
+const LOCAL: &str = "[::]:50051";
+const SECRETS: [&str; 3] = ["token", "key", "salt"];
+
 type Emission = Pin<Box<dyn Stream<Item = Result<Update, Status>> + Send>>;
+type Updates = Pin<Box<dyn Stream<Item = error::Result<stream::Update>> + Send>>;
+type History = Arc<Mutex<VecDeque<stream::Update>>>;
+
+pub type Trigger = Arc<dyn Fn() -> Capture + Send + Sync>;
+pub type Capture = Box<dyn FnMut(bool) -> Option<stream::Update> + Send>;
+pub type Seeker = Arc<dyn Fn(u64, &Sender<stream::Update>) + Send + Sync>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Role {
@@ -29,6 +55,23 @@ pub enum Role {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Handle(u64);
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    Tcp(String),
+    Unix(PathBuf),
+    Inproc(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Credentials {
+    pub certificate: Option<PathBuf>,
+    pub key: Option<PathBuf>,
+    pub authority: Option<PathBuf>,
+    pub domain: Option<String>,
+    pub token: Option<String>,
+    pub plaintext: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct Descriptor {
     pub handle: Handle,
@@ -40,6 +83,172 @@ struct Connection {
     address: Url,
     role: Role,
     shutdown: CancellationToken,
+    commands: Option<Sender<stream::Command>>,
+    task: Option<JoinHandle<error::Result<()>>>,
+}
+
+#[derive(Clone)]
+struct Registration {
+    inbox: Sender<stream::Update>,
+    collector: Arc<Collector>,
+    emitter: Arc<Emitter>,
+    gate: Gate,
+}
+
+#[derive(Clone)]
+struct Gate {
+    digest: Option<[u8; 32]>,
+}
+
+struct Registered(String);
+
+impl Drop for Registered {
+    fn drop(&mut self) {
+        registry().remove(&self.0);
+    }
+}
+
+impl std::fmt::Display for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transport::Tcp(endpoint) => write!(f, "{endpoint}"),
+            Transport::Unix(path) => write!(f, "unix://{}", path.display()),
+            Transport::Inproc(name) => write!(f, "inproc://{name}"),
+        }
+    }
+}
+
+impl Credentials {
+    #[must_use]
+    pub fn or(self, defaults: &Credentials) -> Credentials {
+        Credentials {
+            certificate: self.certificate.or_else(|| defaults.certificate.clone()),
+            key: self.key.or_else(|| defaults.key.clone()),
+            authority: self.authority.or_else(|| defaults.authority.clone()),
+            domain: self.domain.or_else(|| defaults.domain.clone()),
+            token: self.token.or_else(|| defaults.token.clone()),
+            plaintext: self.plaintext || defaults.plaintext,
+        }
+    }
+
+    #[must_use]
+    pub fn secure(&self) -> bool {
+        self.certificate.is_some() || self.authority.is_some()
+    }
+
+    pub fn bearer(
+        &self,
+        transport: &Transport,
+    ) -> error::Result<Option<MetadataValue<tonic::metadata::Ascii>>> {
+        if self.token.is_some()
+            && !self.secure()
+            && !self.plaintext
+            && matches!(transport, Transport::Tcp(_))
+        {
+            return Err(error::Error::Exposed {
+                address: transport.to_string(),
+            });
+        }
+
+        self.token
+            .as_ref()
+            .map(|token| {
+                format!("Bearer {token}")
+                    .parse()
+                    .map_err(|_| error::Error::Token)
+            })
+            .transpose()
+    }
+
+    fn identity(&self, address: &str) -> error::Result<Option<Identity>> {
+        match (&self.certificate, &self.key) {
+            (Some(certificate), Some(key)) => {
+                Ok(Some(Identity::from_pem(read(certificate)?, read(key)?)))
+            }
+            (None, None) => Ok(None),
+            _ => Err(error::Error::Identity {
+                address: address.to_string(),
+            }),
+        }
+    }
+
+    fn authority(&self) -> error::Result<Option<Certificate>> {
+        self.authority
+            .as_ref()
+            .map(|authority| read(authority).map(Certificate::from_pem))
+            .transpose()
+    }
+
+    fn server(&self, address: &str) -> error::Result<Option<ServerTlsConfig>> {
+        let Some(identity) = self.identity(address)? else {
+            return match self.authority {
+                Some(_) => Err(error::Error::Identity {
+                    address: address.to_string(),
+                }),
+                None => Ok(None),
+            };
+        };
+
+        let config = ServerTlsConfig::new().identity(identity);
+        Ok(Some(match self.authority()? {
+            Some(authority) => config.client_ca_root(authority),
+            None => config,
+        }))
+    }
+
+    fn client(&self, address: &str) -> error::Result<Option<ClientTlsConfig>> {
+        if !self.secure() {
+            return Ok(None);
+        }
+
+        let mut config = ClientTlsConfig::new();
+        if let Some(authority) = self.authority()? {
+            config = config.ca_certificate(authority);
+        }
+        if let Some(domain) = &self.domain {
+            config = config.domain_name(domain.clone());
+        }
+        if let Some(identity) = self.identity(address)? {
+            config = config.identity(identity);
+        }
+        Ok(Some(config))
+    }
+}
+
+impl Gate {
+    fn new(credentials: &Credentials) -> Self {
+        Self {
+            digest: credentials.token.as_deref().map(digest),
+        }
+    }
+
+    fn admits(&self, token: Option<&str>) -> bool {
+        let Some(expected) = &self.digest else {
+            return true;
+        };
+        let presented = digest(token.unwrap_or_default());
+        let difference = presented
+            .iter()
+            .zip(expected)
+            .fold(0, |difference, (a, b)| difference | (a ^ b));
+        difference == 0 && token.is_some()
+    }
+}
+
+impl Interceptor for Gate {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+
+        if self.admits(token) {
+            Ok(request)
+        } else {
+            Err(Status::unauthenticated("missing or invalid bearer token"))
+        }
+    }
 }
 
 pub struct Assembler {
@@ -47,6 +256,11 @@ pub struct Assembler {
     sinks: Vec<Url>,
     sources: Vec<Url>,
     capacity: usize,
+    retention: usize,
+    directory: Option<PathBuf>,
+    trigger: Option<Trigger>,
+    seeker: Option<Seeker>,
+    credentials: Credentials,
 }
 
 impl Assembler {
@@ -57,6 +271,11 @@ impl Assembler {
             sinks: Vec::new(),
             sources: Vec::new(),
             capacity: 65536,
+            retention: 4096,
+            directory: None,
+            trigger: None,
+            seeker: None,
+            credentials: Credentials::default(),
         }
     }
 
@@ -83,6 +302,55 @@ impl Assembler {
         self.capacity = capacity;
         self
     }
+
+    #[must_use]
+    pub fn retention(mut self, retention: usize) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    #[must_use]
+    pub fn trigger(mut self, trigger: Trigger) -> Self {
+        self.trigger = Some(trigger);
+        self
+    }
+
+    #[must_use]
+    pub fn seeker(mut self, seeker: Seeker) -> Self {
+        self.seeker = Some(seeker);
+        self
+    }
+
+    #[must_use]
+    pub fn directory(mut self, directory: PathBuf) -> Self {
+        self.directory = Some(directory);
+        self
+    }
+
+    #[must_use]
+    pub fn identity(mut self, certificate: PathBuf, key: PathBuf) -> Self {
+        self.credentials.certificate = Some(certificate);
+        self.credentials.key = Some(key);
+        self
+    }
+
+    #[must_use]
+    pub fn authority(mut self, authority: PathBuf) -> Self {
+        self.credentials.authority = Some(authority);
+        self
+    }
+
+    #[must_use]
+    pub fn domain(mut self, domain: String) -> Self {
+        self.credentials.domain = Some(domain);
+        self
+    }
+
+    #[must_use]
+    pub fn token(mut self, token: String) -> Self {
+        self.credentials.token = Some(token);
+        self
+    }
 }
 
 impl Assemble for Assembler {
@@ -93,16 +361,25 @@ impl Assemble for Assembler {
             .address
             .unwrap_or_else(|| Url::parse("grpc://127.0.0.1:0").expect("valid default URL"));
 
+        let directory = self.directory.unwrap_or_else(std::env::temp_dir);
+
         let (outgoing, _) = broadcast::channel::<stream::Update>(self.capacity);
         let (incoming, receiver) = mpsc::channel::<stream::Update>(self.capacity);
 
         let peer = Peer {
             address,
+            directory,
+            retention: self.retention,
+            trigger: self.trigger,
+            seeker: self.seeker,
+            credentials: self.credentials,
+            history: Arc::new(Mutex::new(VecDeque::with_capacity(self.retention))),
             connections: DashMap::new(),
             sequence: AtomicU64::new(0),
             outgoing: Arc::new(outgoing),
             incoming,
             receiver: tokio::sync::Mutex::new(receiver),
+            listening: watch::Sender::new(None),
         };
 
         for url in self.sinks {
@@ -125,11 +402,18 @@ impl Default for Assembler {
 
 pub struct Peer {
     address: Url,
+    directory: PathBuf,
+    retention: usize,
+    trigger: Option<Trigger>,
+    seeker: Option<Seeker>,
+    credentials: Credentials,
+    history: History,
     connections: DashMap<Handle, Connection>,
     sequence: AtomicU64,
     outgoing: Arc<broadcast::Sender<stream::Update>>,
     incoming: Sender<stream::Update>,
     receiver: tokio::sync::Mutex<Receiver<stream::Update>>,
+    listening: watch::Sender<Option<Url>>,
 }
 
 impl Peer {
@@ -138,90 +422,129 @@ impl Peer {
         &self.address
     }
 
+    pub async fn listening(&self) -> Url {
+        let mut listening = self.listening.subscribe();
+        let bound = listening
+            .wait_for(Option::is_some)
+            .await
+            .ok()
+            .and_then(|bound| bound.clone());
+        bound.unwrap_or_else(|| self.address.clone())
+    }
+
     pub fn sink(&self, url: Url) -> error::Result<Handle> {
+        let transport = transport(&url)?;
+        let credentials = credentials(&url)?;
+        let bearer = credentials.bearer(&transport)?;
         let handle = Handle(self.sequence.fetch_add(1, Ordering::Relaxed));
         let shutdown = CancellationToken::new();
 
+        let mut updates = subscribe(&self.outgoing, shutdown.clone());
+        let address = url.clone();
+
+        let task = tokio::spawn(async move {
+            match transport {
+                Transport::Inproc(name) => {
+                    let inbox = inbox(&name, &credentials)?;
+                    while let Some(update) = updates.next().await {
+                        inbox
+                            .send(update)
+                            .await
+                            .map_err(|_| error::Error::Connection {
+                                details: format!("in-process peer {name} stopped receiving"),
+                            })?;
+                    }
+                    Ok(())
+                }
+                transport => {
+                    let channel = channel(&transport, &credentials).await?;
+                    let updates = updates.map(encode::proto::update);
+                    SinkClient::new(channel)
+                        .send(authorize(updates, bearer.as_ref()))
+                        .await
+                        .map(|_| ())
+                        .map_err(|source| rejected(&address, &credentials, source))
+                }
+            }
+        });
+
         let connection = Connection {
-            address: url.clone(),
+            address: redacted(&url),
             role: Role::Sink,
-            shutdown: shutdown.clone(),
+            shutdown,
+            commands: None,
+            task: Some(task),
         };
 
         self.connections.insert(handle, connection);
 
-        let outgoing = self.outgoing.clone();
-        let endpoint = endpoint(&url);
-
-        tokio::spawn(async move {
-            let Ok(mut client) = SinkClient::connect(endpoint).await else {
-                return;
-            };
-
-            let mut subscription = outgoing.subscribe();
-            let stream = async_stream::stream! {
-                loop {
-                    tokio::select! {
-                        () = shutdown.cancelled() => break,
-                        received = subscription.recv() => {
-                            match received {
-                                Ok(update) => yield encode::proto::update(update),
-                                Err(_) => break,
-                            }
-                        }
-                    }
-                }
-            };
-
-            let _ = client.send(stream).await;
-        });
-
         Ok(handle)
     }
 
     pub fn source(&self, url: Url) -> error::Result<Handle> {
+        let transport = transport(&url)?;
+        let credentials = credentials(&url)?;
+        let bearer = credentials.bearer(&transport)?;
         let handle = Handle(self.sequence.fetch_add(1, Ordering::Relaxed));
         let shutdown = CancellationToken::new();
-
-        let connection = Connection {
-            address: url.clone(),
-            role: Role::Source,
-            shutdown: shutdown.clone(),
-        };
-
-        self.connections.insert(handle, connection);
+        let (commands, instructions) = mpsc::channel::<stream::Command>(self.retention.max(1));
 
         let incoming = self.incoming.clone();
-        let endpoint = endpoint(&url);
+        let cancelled = shutdown.clone();
+        let address = url.clone();
 
-        tokio::spawn(async move {
-            let Ok(mut client) = SourceClient::connect(endpoint).await else {
-                return;
-            };
+        let task = tokio::spawn(async move {
+            let instructions = ReceiverStream::new(instructions);
+            let mut updates: Updates = match transport {
+                Transport::Inproc(name) => {
+                    let registration = registered(&name, &credentials)?;
+                    Box::pin(registration.emitter.emission(instructions).map(Ok))
+                }
+                transport => {
+                    let channel = channel(&transport, &credentials).await?;
+                    let commands = instructions.map(encode::proto::command);
+                    let response = SourceClient::new(channel)
+                        .emit(authorize(commands, bearer.as_ref()))
+                        .await
+                        .map_err(|source| rejected(&address, &credentials, source))?;
 
-            let commands = tokio_stream::empty::<Command>();
-            let Ok(response) = client.emit(commands).await else {
-                return;
+                    Box::pin(
+                        response
+                            .into_inner()
+                            .filter_map(move |received| match received {
+                                Ok(update) => decode::proto::update(update).map(Ok),
+                                Err(source) => Some(Err(rejected(&address, &credentials, source))),
+                            }),
+                    )
+                }
             };
 
-            let mut stream = response.into_inner();
             loop {
                 tokio::select! {
-                    () = shutdown.cancelled() => break,
-                    received = stream.next() => {
+                    () = cancelled.cancelled() => break,
+                    received = updates.next() => {
                         match received {
-                            Some(Ok(update)) => {
-                                if let Some(converted) = decode::proto::update(update) {
-                                    let _ = incoming.send(converted).await;
-                                }
+                            Some(update) => {
+                                let _ = incoming.send(update?).await;
                             }
-                            _ => break,
+                            None => break,
                         }
                     }
                 }
             }
+            Ok(())
         });
 
+        let connection = Connection {
+            address: redacted(&url),
+            role: Role::Source,
+            shutdown,
+            commands: Some(commands),
+            task: Some(task),
+        };
+
+        self.connections.insert(handle, connection);
+
         Ok(handle)
     }
 
@@ -235,7 +558,45 @@ impl Peer {
         }
     }
 
+    pub async fn join(&self, handle: Handle) -> error::Result<()> {
+        let task = self
+            .connections
+            .get_mut(&handle)
+            .ok_or(error::Error::Handle { handle: handle.0 })?
+            .task
+            .take();
+
+        match task {
+            Some(task) => task.await.map_err(|source| error::Error::Connection {
+                details: source.to_string(),
+            })?,
+            None => Ok(()),
+        }
+    }
+
+    pub fn command(&self, handle: Handle, command: stream::Command) -> error::Result<()> {
+        let connection = self
+            .connections
+            .get(&handle)
+            .ok_or(error::Error::Handle { handle: handle.0 })?;
+
+        let commands = connection
+            .commands
+            .as_ref()
+            .ok_or_else(|| error::Error::Connection {
+                details: "commands are only accepted by source connections".to_string(),
+            })?;
+
+        commands
+            .try_send(command)
+            .map_err(|source| error::Error::Connection {
+                details: source.to_string(),
+            })
+    }
+
     pub fn send(&self, update: stream::Update) -> error::Result<()> {
+        retain(&self.history, self.retention, update.clone());
+
         self.outgoing
             .send(update)
             .map_err(|_| error::Error::Connection {
@@ -248,6 +609,86 @@ impl Peer {
         self.receiver.lock().await.recv().await
     }
 
+    pub async fn store(
+        &self,
+        url: Url,
+        updates: Vec<stream::Update>,
+    ) -> error::Result<stream::Record> {
+        let transport = transport(&url)?;
+        let credentials = credentials(&url)?;
+        if let Transport::Inproc(name) = &transport {
+            return registered(name, &credentials)?.collector.persist(updates);
+        }
+
+        let bearer = credentials.bearer(&transport)?;
+        let mut client = SinkClient::new(channel(&transport, &credentials).await?);
+
+        let outgoing = tokio_stream::iter(updates.into_iter().map(encode::proto::update));
+        let record = client
+            .store(authorize(outgoing, bearer.as_ref()))
+            .await
+            .map_err(|source| rejected(&url, &credentials, source))?
+            .into_inner();
+
+        Ok(stream::Record {
+            path: record.path,
+            count: record.count,
+            duration: record.duration,
+        })
+    }
+
+    pub async fn replay(
+        &self,
+        url: Url,
+        record: stream::Record,
+        paced: bool,
+    ) -> error::Result<Vec<stream::Update>> {
+        let transport = transport(&url)?;
+        let credentials = credentials(&url)?;
+        if let Transport::Inproc(name) = &transport {
+            let registration = registered(name, &credentials)?;
+            let path = confined(&registration.emitter.directory, &record.path)?;
+
+            let mut updates = Vec::new();
+            let mut stream = Box::pin(playback(record::open(&path)?, paced));
+            while let Some(update) = stream.next().await {
+                updates.push(update?);
+            }
+            return Ok(updates);
+        }
+
+        let bearer = credentials.bearer(&transport)?;
+        let mut client = SourceClient::new(channel(&transport, &credentials).await?);
+
+        let path = record.path.clone();
+        let playback = Playback {
+            record: Some(Record {
+                path: record.path,
+                count: record.count,
+                duration: record.duration,
+            }),
+            paced,
+        };
+
+        let status = |source: Status| match source.code() {
+            Code::PermissionDenied => error::Error::Confined { path: path.clone() },
+            _ => rejected(&url, &credentials, source),
+        };
+
+        let mut incoming = client
+            .replay(authorize(playback, bearer.as_ref()))
+            .await
+            .map_err(status)?
+            .into_inner();
+
+        let mut updates = Vec::new();
+        while let Some(update) = incoming.message().await.map_err(status)? {
+            updates.extend(decode::proto::update(update));
+        }
+
+        Ok(updates)
+    }
+
     #[must_use]
     pub fn connections(&self) -> Vec<Descriptor> {
         self.connections
@@ -261,27 +702,108 @@ impl Peer {
     }
 
     pub async fn serve(&self) -> error::Result<()> {
-        let address = socket(&self.address)?;
+        let collector = Arc::new(Collector::new(
+            self.incoming.clone(),
+            self.directory.clone(),
+        ));
+        let emitter = Arc::new(Emitter::new(
+            self.outgoing.clone(),
+            self.history.clone(),
+            self.directory.clone(),
+            self.retention,
+            self.trigger.clone(),
+            self.seeker.clone(),
+        ));
 
-        let collector = Collector::new(self.incoming.clone());
-        let emitter = Emitter::new(self.outgoing.clone());
+        let server = |source| error::Error::Server { source };
+        let credentials = credentials(&self.address)?.or(&self.credentials);
+        let gate = Gate::new(&credentials);
+        let address = redacted(&self.address).to_string();
 
-        tonic::transport::Server::builder()
-            .add_service(SinkServer::new(collector))
-            .add_service(SourceServer::new(emitter))
-            .serve(address)
-            .await
-            .map_err(|source| error::Error::Server { source })
+        match transport(&self.address)? {
+            Transport::Tcp(_) => {
+                let router = router(collector, emitter, gate, &credentials, &address)?;
+                let socket = socket(&self.address)?;
+                let incoming = TcpIncoming::bind(socket)
+                    .map_err(|source| error::Error::Bind {
+                        path: socket.to_string(),
+                        source,
+                    })?
+                    .with_nodelay(Some(true));
+
+                let mut bound = self.address.clone();
+                if let Ok(local) = incoming.local_addr() {
+                    let _ = bound.set_port(Some(local.port()));
+                }
+                self.listening.send_replace(Some(bound));
+
+                router.serve_with_incoming(incoming).await.map_err(server)
+            }
+            Transport::Unix(path) => {
+                unlink(&path)?;
+                let listener = UnixListener::bind(&path).map_err(|source| error::Error::Bind {
+                    path: path.display().to_string(),
+                    source,
+                })?;
+                self.listening.send_replace(Some(self.address.clone()));
+
+                router(collector, emitter, gate, &credentials, &address)?
+                    .serve_with_incoming(UnixListenerStream::new(listener))
+                    .await
+                    .map_err(server)
+            }
+            Transport::Inproc(name) => {
+                match registry().entry(name.clone()) {
+                    Entry::Occupied(_) => return Err(error::Error::Registered { name }),
+                    Entry::Vacant(vacant) => {
+                        vacant.insert(Registration {
+                            inbox: self.incoming.clone(),
+                            collector,
+                            emitter,
+                            gate,
+                        });
+                    }
+                }
+
+                let _registered = Registered(name);
+                self.listening.send_replace(Some(self.address.clone()));
+                std::future::pending::<()>().await;
+                Ok(())
+            }
+        }
     }
 }
 
 struct Collector {
     sender: Sender<stream::Update>,
+    directory: PathBuf,
+    sequence: AtomicU64,
 }
 
 impl Collector {
-    fn new(sender: Sender<stream::Update>) -> Self {
-        Self { sender }
+    fn new(sender: Sender<stream::Update>, directory: PathBuf) -> Self {
+        Self {
+            sender,
+            directory,
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    fn writer(&self) -> error::Result<record::Writer> {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        let path = self
+            .directory
+            .join(format!("{}-{sequence}.record", stream::timestamp()));
+
+        record::Assembler::new(path).assemble()
+    }
+
+    fn persist(&self, updates: Vec<stream::Update>) -> error::Result<stream::Record> {
+        let mut writer = self.writer()?;
+        for update in updates {
+            writer.append(update)?;
+        }
+        writer.finish()
     }
 }
 
@@ -307,21 +829,228 @@ impl Sink for Collector {
         Ok(Response::new(Acknowledge {}))
     }
 
-    async fn store(
-        &self,
-        _request: Request<Streaming<Update>>,
-    ) -> Result<Response<Record>, Status> {
-        Err(Status::unimplemented("store not supported"))
+    async fn store(&self, request: Request<Streaming<Update>>) -> Result<Response<Record>, Status> {
+        let mut incoming = request.into_inner();
+
+        let internal = |error: error::Error| Status::internal(error.to_string());
+        let mut writer = self.writer().map_err(internal)?;
+
+        while let Some(update) = incoming.message().await? {
+            if let Some(update) = decode::proto::update(update) {
+                writer.append(update).map_err(internal)?;
+            }
+        }
+
+        let stored = writer.finish().map_err(internal)?;
+
+        Ok(Response::new(Record {
+            path: stored.path,
+            count: stored.count,
+            duration: stored.duration,
+        }))
     }
 }
 
 struct Emitter {
     broadcast: Arc<broadcast::Sender<stream::Update>>,
+    history: History,
+    directory: PathBuf,
+    retention: usize,
+    trigger: Option<Trigger>,
+    seeker: Option<Seeker>,
 }
 
 impl Emitter {
-    fn new(broadcast: Arc<broadcast::Sender<stream::Update>>) -> Self {
-        Self { broadcast }
+    fn new(
+        broadcast: Arc<broadcast::Sender<stream::Update>>,
+        history: History,
+        directory: PathBuf,
+        retention: usize,
+        trigger: Option<Trigger>,
+        seeker: Option<Seeker>,
+    ) -> Self {
+        Self {
+            broadcast,
+            history,
+            directory,
+            retention,
+            trigger,
+            seeker,
+        }
+    }
+
+    fn emission<C>(&self, mut commands: C) -> impl Stream<Item = stream::Update> + Send + 'static
+    where
+        C: Stream<Item = stream::Command> + Send + Unpin + 'static,
+    {
+        let mut receiver = self.broadcast.subscribe();
+        let (direct, mut directed) = mpsc::channel::<stream::Update>(self.retention.max(1));
+
+        let history = self.history.clone();
+        let seeker = self.seeker.clone();
+        let capture = self.trigger.as_ref().map(|trigger| trigger());
+        let mut session = Session::new(self.retention, capture);
+
+        async_stream::stream! {
+            let mut open = true;
+            loop {
+                let emitted = tokio::select! {
+                    command = commands.next(), if open => {
+                        if let Some(command) = command {
+                            session.instruct(
+                                command,
+                                &history,
+                                seeker.as_ref(),
+                                &direct,
+                            )
+                        } else {
+                            open = false;
+                            Vec::new()
+                        }
+                    },
+                    received = receiver.recv() => match received {
+                        Ok(update) => session.admit(update).into_iter().collect::<Vec<_>>(),
+                        Err(broadcast::error::RecvError::Lagged(_)) => Vec::new(),
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    Some(update) = directed.recv() => {
+                        session.admit(update).into_iter().collect::<Vec<_>>()
+                    }
+                };
+
+                for update in emitted {
+                    yield update;
+                }
+            }
+        }
+    }
+}
+
+struct Session {
+    paused: bool,
+    buffer: VecDeque<(stream::Update, bool)>,
+    capacity: usize,
+    targets: Vec<String>,
+    level: stream::Level,
+    capture: Option<Capture>,
+    stale: bool,
+}
+
+impl Session {
+    fn new(capacity: usize, capture: Option<Capture>) -> Self {
+        Self {
+            paused: false,
+            buffer: VecDeque::new(),
+            capacity,
+            targets: Vec::new(),
+            level: stream::Level::Trace,
+            capture,
+            stale: false,
+        }
+    }
+
+    fn accepts(&self, update: &stream::Update) -> bool {
+        let metadata = match update {
+            stream::Update::Span(span) => &span.metadata,
+            stream::Update::Event(event) => &event.metadata,
+            stream::Update::Snapshot(_) | stream::Update::Metric(_) => return true,
+        };
+
+        metadata.level >= self.level
+            && (self.targets.is_empty()
+                || self
+                    .targets
+                    .iter()
+                    .any(|target| metadata.target.starts_with(target.as_str())))
+    }
+
+    fn admit(&mut self, update: stream::Update) -> Option<stream::Update> {
+        self.hold(update, false)
+    }
+
+    fn hold(&mut self, update: stream::Update, captured: bool) -> Option<stream::Update> {
+        if !self.accepts(&update) {
+            return None;
+        }
+
+        if !self.paused {
+            return Some(update);
+        }
+
+        if self.buffer.len() >= self.capacity
+            && let Some((_, true)) = self.buffer.pop_front()
+        {
+            self.stale = true;
+            self.buffer.retain(|(_, captured)| !captured);
+        }
+        if captured && (self.stale || self.capacity == 0) {
+            self.stale = true;
+        } else if self.capacity > 0 {
+            self.buffer.push_back((update, captured));
+        }
+        None
+    }
+
+    fn capture(&mut self) -> Option<stream::Update> {
+        let stale = self.stale;
+        let update = self.capture.as_mut().and_then(|capture| capture(stale))?;
+        self.stale = false;
+        Some(update)
+    }
+
+    fn instruct(
+        &mut self,
+        command: stream::Command,
+        history: &History,
+        seeker: Option<&Seeker>,
+        direct: &Sender<stream::Update>,
+    ) -> Vec<stream::Update> {
+        match command {
+            stream::Command::Pause => {
+                self.paused = true;
+                Vec::new()
+            }
+            stream::Command::Resume => {
+                self.paused = false;
+                let mut resumed = self
+                    .buffer
+                    .drain(..)
+                    .map(|(update, _)| update)
+                    .collect::<Vec<_>>();
+                if self.stale {
+                    resumed.extend(self.capture());
+                }
+                resumed
+            }
+            stream::Command::Filter { targets, level } => {
+                self.targets = targets;
+                self.level = level;
+                Vec::new()
+            }
+            stream::Command::Seek(timestamp) => {
+                if let Some(seeker) = seeker {
+                    seeker(timestamp, direct);
+                }
+
+                let retained = history
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .iter()
+                    .filter(|update| record::timestamp(update) >= timestamp)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                retained
+                    .into_iter()
+                    .filter_map(|update| self.admit(update))
+                    .collect::<Vec<_>>()
+            }
+            stream::Command::Snapshot => self
+                .capture()
+                .and_then(|update| self.hold(update, true))
+                .into_iter()
+                .collect::<Vec<_>>(),
+        }
     }
 }
 
@@ -332,11 +1061,15 @@ impl Source for Emitter {
 
     async fn emit(
         &self,
-        _request: Request<Streaming<Command>>,
+        request: Request<Streaming<Command>>,
     ) -> Result<Response<Self::EmitStream>, Status> {
-        let receiver = self.broadcast.subscribe();
-        let stream = tokio_stream::wrappers::BroadcastStream::new(receiver)
-            .filter_map(Result::ok)
+        let commands = request
+            .into_inner()
+            .map_while(Result::ok)
+            .filter_map(decode::proto::command);
+
+        let stream = self
+            .emission(commands)
             .map(|update| Ok(encode::proto::update(update)));
 
         Ok(Response::new(Box::pin(stream)))
@@ -344,9 +1077,329 @@ impl Source for Emitter {
 
     async fn replay(
         &self,
-        _request: Request<Record>,
+        request: Request<Playback>,
     ) -> Result<Response<Self::ReplayStream>, Status> {
-        Err(Status::unimplemented("replay not supported"))
+        let playback = request.into_inner();
+        let stored = playback
+            .record
+            .ok_or_else(|| Status::invalid_argument("missing record"))?;
+
+        let path = confined(&self.directory, &stored.path).map_err(|error| match error {
+            error::Error::Confined { .. } => Status::permission_denied(error.to_string()),
+            error => Status::not_found(error.to_string()),
+        })?;
+        let reader = record::open(&path).map_err(|error| Status::not_found(error.to_string()))?;
+
+        let stream = self::playback(reader, playback.paced).map(|update| {
+            update
+                .map(encode::proto::update)
+                .map_err(|error| Status::data_loss(error.to_string()))
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+fn playback(
+    reader: record::Reader,
+    paced: bool,
+) -> impl Stream<Item = error::Result<stream::Update>> + Send + 'static {
+    async_stream::stream! {
+        let mut previous = None;
+        for update in reader {
+            let update = match update {
+                Ok(update) => update,
+                Err(error) => {
+                    yield Err(error);
+                    break;
+                }
+            };
+            let current = record::timestamp(&update);
+            if let Some(previous) = previous.filter(|_| paced) {
+                let delay = Duration::from_micros(current.saturating_sub(previous));
+                tokio::time::sleep(delay).await;
+            }
+            previous = Some(current);
+            yield Ok(update);
+        }
+    }
+}
+
+fn subscribe(
+    outgoing: &broadcast::Sender<stream::Update>,
+    shutdown: CancellationToken,
+) -> impl Stream<Item = stream::Update> + Send + Unpin + 'static {
+    let mut subscription = outgoing.subscribe();
+    Box::pin(async_stream::stream! {
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break,
+                received = subscription.recv() => {
+                    match received {
+                        Ok(update) => yield update,
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn router(
+    collector: Arc<Collector>,
+    emitter: Arc<Emitter>,
+    gate: Gate,
+    credentials: &Credentials,
+    address: &str,
+) -> error::Result<tonic::transport::server::Router> {
+    let mut server = tonic::transport::Server::builder();
+    if let Some(tls) = credentials.server(address)? {
+        server = server.tls_config(tls).map_err(|source| error::Error::Tls {
+            address: address.to_string(),
+            source,
+        })?;
+    }
+
+    Ok(server
+        .add_service(InterceptedService::new(
+            SinkServer::from_arc(collector),
+            gate.clone(),
+        ))
+        .add_service(InterceptedService::new(
+            SourceServer::from_arc(emitter),
+            gate,
+        )))
+}
+
+fn digest(token: &str) -> [u8; 32] {
+    Sha256::digest(token.as_bytes()).into()
+}
+
+fn registry() -> &'static DashMap<String, Registration> {
+    static REGISTRY: OnceLock<DashMap<String, Registration>> = OnceLock::new();
+    REGISTRY.get_or_init(DashMap::new)
+}
+
+fn registered(name: &str, credentials: &Credentials) -> error::Result<Registration> {
+    let registration = registry()
+        .get(name)
+        .map(|registration| registration.clone())
+        .ok_or_else(|| error::Error::Connection {
+            details: format!("no in-process peer is serving {name}"),
+        })?;
+
+    if !registration.gate.admits(credentials.token.as_deref()) {
+        return Err(error::Error::Unauthenticated {
+            address: format!("inproc://{name}"),
+        });
+    }
+    Ok(registration)
+}
+
+pub fn inbox(name: &str, credentials: &Credentials) -> error::Result<Sender<stream::Update>> {
+    registered(name, credentials).map(|registration| registration.inbox)
+}
+
+pub fn authorize<T>(
+    message: T,
+    bearer: Option<&MetadataValue<tonic::metadata::Ascii>>,
+) -> Request<T> {
+    let mut request = Request::new(message);
+    if let Some(bearer) = bearer {
+        request
+            .metadata_mut()
+            .insert("authorization", bearer.clone());
+    }
+    request
+}
+
+pub fn credentials(url: &Url) -> error::Result<Credentials> {
+    let mut credentials = Credentials::default();
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "certificate" => credentials.certificate = Some(PathBuf::from(value.as_ref())),
+            "key" => credentials.key = Some(PathBuf::from(value.as_ref())),
+            "authority" => credentials.authority = Some(PathBuf::from(value.as_ref())),
+            "domain" => credentials.domain = Some(value.into_owned()),
+            "plaintext" => credentials.plaintext = value == "true",
+            "token" => {
+                let token = match value.strip_prefix('@') {
+                    Some(path) => String::from_utf8_lossy(&read(Path::new(path))?)
+                        .trim()
+                        .to_string(),
+                    None => value.into_owned(),
+                };
+                credentials.token = Some(token);
+            }
+            _ => {}
+        }
+    }
+    Ok(credentials)
+}
+
+#[must_use]
+pub fn redacted(url: &Url) -> Url {
+    let mut redacted = url.clone();
+    let retained = url
+        .query_pairs()
+        .filter(|(key, _)| !SECRETS.contains(&key.as_ref()))
+        .collect::<Vec<_>>();
+
+    if retained.is_empty() {
+        redacted.set_query(None);
+    } else {
+        redacted.query_pairs_mut().clear().extend_pairs(retained);
+    }
+    redacted
+}
+
+pub fn transport(url: &Url) -> error::Result<Transport> {
+    let unsupported = || error::Error::Transport {
+        address: redacted(url).to_string(),
+    };
+
+    match url.scheme() {
+        "unix" if !url.path().is_empty() => Ok(Transport::Unix(PathBuf::from(url.path()))),
+        "unix" => Err(unsupported()),
+        "inproc" => url
+            .host_str()
+            .filter(|name| !name.is_empty())
+            .map(|name| Transport::Inproc(name.to_string()))
+            .ok_or_else(unsupported),
+        _ => Ok(Transport::Tcp(endpoint(url))),
+    }
+}
+
+pub async fn channel(transport: &Transport, credentials: &Credentials) -> error::Result<Channel> {
+    let address = transport.to_string();
+    let tls = credentials.client(&address)?;
+    let scheme = if tls.is_some() { "https" } else { "http" };
+
+    let connection = |source: tonic::transport::Error| match &tls {
+        Some(_) => error::Error::Handshake {
+            address: address.clone(),
+            details: cause(&source),
+        },
+        None => error::Error::Connection {
+            details: source.to_string(),
+        },
+    };
+
+    let authority = match transport {
+        Transport::Tcp(endpoint) => endpoint.strip_prefix("http://").unwrap_or(endpoint),
+        Transport::Unix(_) => LOCAL,
+        Transport::Inproc(name) => {
+            return Err(error::Error::Connection {
+                details: format!("in-process peer {name} is reached without a channel"),
+            });
+        }
+    };
+
+    let mut endpoint =
+        Endpoint::from_shared(format!("{scheme}://{authority}")).map_err(|source| {
+            error::Error::Connection {
+                details: source.to_string(),
+            }
+        })?;
+    if let Some(config) = &tls {
+        endpoint = endpoint
+            .tls_config(config.clone())
+            .map_err(|source| error::Error::Tls {
+                address: address.clone(),
+                source,
+            })?;
+    }
+
+    match transport {
+        Transport::Unix(path) => {
+            let path = path.clone();
+            endpoint
+                .connect_with_connector(tower::service_fn(move |_: Uri| {
+                    let path = path.clone();
+                    async move {
+                        Ok::<_, std::io::Error>(TokioIo::new(UnixStream::connect(path).await?))
+                    }
+                }))
+                .await
+                .map_err(connection)
+        }
+        _ => endpoint.connect().await.map_err(connection),
+    }
+}
+
+fn unlink(path: &Path) -> error::Result<()> {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    let bind = |source| error::Error::Bind {
+        path: path.display().to_string(),
+        source,
+    };
+
+    if !metadata.file_type().is_socket() {
+        return Err(bind(std::io::ErrorKind::AddrInUse.into()));
+    }
+    std::fs::remove_file(path).map_err(bind)
+}
+
+fn confined(directory: &Path, path: &str) -> error::Result<PathBuf> {
+    let outside = || error::Error::Confined {
+        path: path.to_string(),
+    };
+    let root = directory.canonicalize().map_err(|_| outside())?;
+    let joined = directory.join(path);
+
+    match joined.canonicalize() {
+        Ok(resolved) if resolved.starts_with(&root) => Ok(resolved),
+        // A missing record only reports as missing when it could have been inside.
+        Err(source)
+            if joined.starts_with(directory)
+                && !joined
+                    .components()
+                    .any(|component| component == Component::ParentDir) =>
+        {
+            Err(error::Error::Open {
+                path: path.to_string(),
+                source,
+            })
+        }
+        Ok(_) | Err(_) => Err(outside()),
+    }
+}
+
+fn read(path: &Path) -> error::Result<Vec<u8>> {
+    std::fs::read(path).map_err(|source| error::Error::Credential {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+fn cause(error: &dyn std::error::Error) -> String {
+    let mut cause = error;
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+    cause.to_string()
+}
+
+fn rejected(url: &Url, credentials: &Credentials, source: Status) -> error::Error {
+    let address = redacted(url).to_string();
+    let transport = std::error::Error::source(&source)
+        .is_some_and(<dyn std::error::Error>::is::<tonic::transport::Error>);
+
+    match source.code() {
+        Code::Unauthenticated => error::Error::Unauthenticated { address },
+        _ if transport && credentials.secure() => error::Error::Handshake {
+            address,
+            details: cause(&source),
+        },
+        _ if transport => error::Error::Plaintext {
+            address,
+            details: cause(&source),
+        },
+        _ => error::Error::Status {
+            source: Box::new(source),
+        },
     }
 }
 
@@ -365,3 +1418,13 @@ fn socket(url: &Url) -> error::Result<SocketAddr> {
         .parse()
         .map_err(|source| error::Error::Address { address, source })
 }
+
+fn retain(history: &History, retention: usize, update: stream::Update) {
+    let mut history = history.lock().unwrap_or_else(PoisonError::into_inner);
+    if history.len() >= retention {
+        history.pop_front();
+    }
+    if retention > 0 {
+        history.push_back(update);
+    }
+}
@@ -1,81 +1,533 @@
 pub use error;
 
+use assemble::Assemble;
+use prost::Message;
+use proto::observation as wire;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::Path;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 
 use stream::{Record, Update};
 
-pub fn write<P: AsRef<Path>>(path: P, updates: &[Update]) -> error::Result<Record> {
-    let location = path.as_ref().to_string_lossy().into_owned();
+const MAGIC: &[u8; 4] = b"VREC";
+const FOOTER: &[u8; 4] = b"VIDX";
+const TRAILER: usize = 20;
+const ENTRY: usize = 16;
+const FRAME: u64 = 64 << 20;
 
-    let file = File::create(path.as_ref()).map_err(|source| error::Error::Create {
-        path: location.clone(),
-        source,
-    })?;
+type Index = Vec<(u64, u64)>;
 
-    let mut writer = BufWriter::new(file);
+pub struct Assembler {
+    path: PathBuf,
+    rotation: Option<u64>,
+    interval: u64,
+}
+
+impl Assembler {
+    #[must_use]
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            rotation: None,
+            interval: 64,
+        }
+    }
+
+    #[must_use]
+    pub fn rotation(mut self, bytes: u64) -> Self {
+        self.rotation = Some(bytes);
+        self
+    }
+
+    #[must_use]
+    pub fn interval(mut self, frames: u64) -> Self {
+        self.interval = frames.max(1);
+        self
+    }
+}
+
+impl Assemble for Assembler {
+    type Output = error::Result<Writer>;
+
+    fn assemble(self) -> Self::Output {
+        for stale in segments(&self.path).into_iter().skip(1) {
+            std::fs::remove_file(&stale).map_err(|source| error::Error::Create {
+                path: stale.to_string_lossy().into_owned(),
+                source,
+            })?;
+        }
+        let (location, file) = create(&self.path)?;
+
+        Ok(Writer {
+            base: self.path,
+            rotation: self.rotation,
+            interval: self.interval,
+            segment: 0,
+            location,
+            file,
+            offset: MAGIC.len() as u64,
+            frames: 0,
+            index: Vec::new(),
+            count: 0,
+            first: None,
+            last: 0,
+        })
+    }
+}
+
+pub struct Writer {
+    base: PathBuf,
+    rotation: Option<u64>,
+    interval: u64,
+    segment: usize,
+    location: String,
+    file: BufWriter<File>,
+    offset: u64,
+    frames: u64,
+    index: Index,
+    count: u64,
+    first: Option<u64>,
+    last: u64,
+}
+
+impl Writer {
+    pub fn append(&mut self, update: Update) -> error::Result<()> {
+        if self
+            .rotation
+            .is_some_and(|limit| self.frames > 0 && self.offset >= limit)
+        {
+            self.rotate()?;
+        }
+
+        let stamp = timestamp(&update);
+        if self.frames.is_multiple_of(self.interval) {
+            self.index.push((stamp, self.offset));
+        }
+
+        let frame = encode::proto::update(update).encode_length_delimited_to_vec();
+        self.file
+            .write_all(&frame)
+            .map_err(|source| error::Error::Write {
+                path: self.location.clone(),
+                source,
+            })?;
+
+        self.offset += frame.len() as u64;
+        self.frames += 1;
+        self.count += 1;
+        self.first.get_or_insert(stamp);
+        self.last = stamp;
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> error::Result<()> {
+        self.file.flush().map_err(|source| error::Error::Write {
+            path: self.location.clone(),
+            source,
+        })
+    }
+
+    #[must_use]
+    pub fn segment(&self) -> &str {
+        &self.location
+    }
+
+    pub fn finish(mut self) -> error::Result<Record> {
+        self.seal()?;
+
+        Ok(Record {
+            path: self.base.to_string_lossy().into_owned(),
+            count: self.count,
+            duration: self
+                .first
+                .map_or(0, |first| self.last.saturating_sub(first)),
+        })
+    }
+
+    fn rotate(&mut self) -> error::Result<()> {
+        self.seal()?;
+
+        self.segment += 1;
+        let (location, file) = create(&segment(&self.base, self.segment))?;
+        self.location = location;
+        self.file = file;
+        self.offset = MAGIC.len() as u64;
+        self.frames = 0;
+        self.index.clear();
+
+        Ok(())
+    }
+
+    fn seal(&mut self) -> error::Result<()> {
+        let mut footer = Vec::with_capacity(self.index.len() * ENTRY + TRAILER);
+        for (stamp, offset) in &self.index {
+            footer.extend_from_slice(&stamp.to_le_bytes());
+            footer.extend_from_slice(&offset.to_le_bytes());
+        }
+        footer.extend_from_slice(&(self.index.len() as u64).to_le_bytes());
+        footer.extend_from_slice(&self.offset.to_le_bytes());
+        footer.extend_from_slice(FOOTER);
+
+        self.file
+            .write_all(&footer)
+            .map_err(|source| error::Error::Write {
+                path: self.location.clone(),
+                source,
+            })?;
+
+        self.flush()
+    }
+}
+
+enum Frames {
+    Framed {
+        reader: BufReader<File>,
+        position: u64,
+        end: Option<u64>,
+        index: Index,
+    },
+    Legacy(std::vec::IntoIter<Update>),
+}
+
+pub struct Reader {
+    location: String,
+    frames: Frames,
+    from: u64,
+    segments: Vec<PathBuf>,
+    current: usize,
+}
+
+impl Reader {
+    pub fn seek(&mut self, timestamp: u64) -> error::Result<()> {
+        self.from = timestamp;
+
+        let mut chosen = 0;
+        for (number, path) in self.segments.iter().enumerate().skip(1) {
+            match load(path)?.1 {
+                Frames::Framed { index, .. }
+                    if index.first().is_some_and(|(stamp, _)| *stamp <= timestamp) =>
+                {
+                    chosen = number;
+                }
+                _ => break,
+            }
+        }
+        if chosen != self.current {
+            (self.location, self.frames) = load(&self.segments[chosen])?;
+            self.current = chosen;
+        }
+
+        if let Frames::Framed {
+            reader,
+            position,
+            index,
+            ..
+        } = &mut self.frames
+        {
+            let start = index
+                .iter()
+                .take_while(|(stamp, _)| *stamp <= timestamp)
+                .last()
+                .map_or(MAGIC.len() as u64, |(_, offset)| *offset);
+
+            reader
+                .seek(SeekFrom::Start(start))
+                .map_err(|source| error::Error::Read {
+                    path: self.location.clone(),
+                    source,
+                })?;
+            *position = start;
+        }
+
+        Ok(())
+    }
+
+    fn frame(&mut self) -> Option<error::Result<Update>> {
+        let Frames::Framed {
+            reader,
+            position,
+            end,
+            ..
+        } = &mut self.frames
+        else {
+            return None;
+        };
+
+        let truncated = |end: &mut Option<u64>, position: u64, source| {
+            *end = Some(position);
+            Some(Err(error::Error::Read {
+                path: self.location.clone(),
+                source,
+            }))
+        };
+
+        loop {
+            if end.is_some_and(|end| *position >= end) {
+                return None;
+            }
+
+            let (length, width) = match delimiter(reader) {
+                Ok(Some(delimiter)) => delimiter,
+                Ok(None) => return None,
+                Err(source) => return truncated(end, *position, source),
+            };
+            if length > FRAME {
+                *end = Some(*position);
+                return Some(Err(error::Error::Oversized {
+                    path: self.location.clone(),
+                    length,
+                }));
+            }
+
+            let mut buffer = vec![0; usize::try_from(length).unwrap_or_default()];
+            if let Err(source) = reader.read_exact(&mut buffer) {
+                return truncated(end, *position, source);
+            }
+            *position += width + length;
+
+            let decoded = wire::Update::decode(buffer.as_slice())
+                .map(decode::proto::update)
+                .map_err(|source| error::Error::Frame {
+                    path: self.location.clone(),
+                    source,
+                })
+                .transpose();
+
+            if decoded.is_some() {
+                return decoded;
+            }
+        }
+    }
+
+    fn advance(&mut self) -> Option<error::Result<()>> {
+        let path = self.segments.get(self.current + 1)?;
+        self.current += 1;
+        Some(load(path).map(|(location, frames)| {
+            self.location = location;
+            self.frames = frames;
+        }))
+    }
+}
+
+impl Iterator for Reader {
+    type Item = error::Result<Update>;
 
-    let count = updates.len() as u64;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let update = if let Frames::Legacy(updates) = &mut self.frames {
+                updates.next().map(Ok)
+            } else {
+                self.frame()
+            };
 
-    let stamp = |u: &Update| match u {
+            let Some(update) = update else {
+                if let Err(error) = self.advance()? {
+                    return Some(Err(error));
+                }
+                continue;
+            };
+
+            if !matches!(&update, Ok(update) if timestamp(update) < self.from) {
+                return Some(update);
+            }
+        }
+    }
+}
+
+pub fn open<P: AsRef<Path>>(path: P) -> error::Result<Reader> {
+    let mut segments = segments(path.as_ref());
+    if segments.is_empty() {
+        segments.push(path.as_ref().to_path_buf());
+    }
+
+    let (location, frames) = load(&segments[0])?;
+    Ok(Reader {
+        location,
+        frames,
+        from: 0,
+        segments,
+        current: 0,
+    })
+}
+
+pub fn write<P: AsRef<Path>>(path: P, updates: &[Update]) -> error::Result<Record> {
+    let mut writer = Assembler::new(path.as_ref()).assemble()?;
+    for update in updates {
+        writer.append(update.clone())?;
+    }
+    writer.finish()
+}
+
+pub fn read<P: AsRef<Path>>(path: P) -> error::Result<Vec<Update>> {
+    open(path)?.collect()
+}
+
+#[must_use]
+pub fn segments(base: &Path) -> Vec<PathBuf> {
+    (0..usize::MAX)
+        .map(|number| segment(base, number))
+        .take_while(|path| path.exists())
+        .collect::<Vec<_>>()
+}
+
+#[must_use]
+pub fn timestamp(update: &Update) -> u64 {
+    match update {
         Update::Span(s) => match &s.lifecycle {
             stream::Lifecycle::Begin(b) => b.timestamp,
             stream::Lifecycle::End(e) => e.timestamp,
         },
         Update::Event(e) => e.timestamp,
         Update::Snapshot(s) => s.timestamp,
+        Update::Metric(m) => m.timestamp,
+    }
+}
+
+fn segment(base: &Path, number: usize) -> PathBuf {
+    if number == 0 {
+        return base.to_path_buf();
+    }
+
+    let stem = base
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let name = match base.extension() {
+        Some(extension) => format!("{stem}.{number}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{number}"),
     };
 
-    let duration = updates
-        .first()
-        .zip(updates.last())
-        .map_or(0, |(first, last)| stamp(last).saturating_sub(stamp(first)));
+    base.with_file_name(name)
+}
 
-    let serialized = serde_json::to_vec(updates).map_err(|source| error::Error::Format {
+fn create(path: &Path) -> error::Result<(String, BufWriter<File>)> {
+    let location = path.to_string_lossy().into_owned();
+
+    let file = File::create(path).map_err(|source| error::Error::Create {
         path: location.clone(),
         source,
     })?;
 
+    let mut writer = BufWriter::new(file);
     writer
-        .write_all(&serialized)
+        .write_all(MAGIC)
         .map_err(|source| error::Error::Write {
             path: location.clone(),
             source,
         })?;
 
-    writer.flush().map_err(|source| error::Error::Write {
+    Ok((location, writer))
+}
+
+fn load(path: &Path) -> error::Result<(String, Frames)> {
+    let location = path.to_string_lossy().into_owned();
+    let failed = |source| error::Error::Read {
+        path: location.clone(),
+        source,
+    };
+
+    let mut file = File::open(path).map_err(|source| error::Error::Open {
         path: location.clone(),
         source,
     })?;
 
-    Ok(Record {
-        path: location,
-        count,
-        duration,
-    })
+    let mut magic = [0; 4];
+    let framed = match file.read_exact(&mut magic) {
+        Ok(()) => &magic == MAGIC,
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => false,
+        Err(source) => return Err(failed(source)),
+    };
+
+    if !framed {
+        file.seek(SeekFrom::Start(0)).map_err(failed)?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents).map_err(failed)?;
+
+        let updates: Vec<Update> =
+            serde_json::from_slice(&contents).map_err(|source| error::Error::Format {
+                path: location.clone(),
+                source,
+            })?;
+
+        return Ok((location, Frames::Legacy(updates.into_iter())));
+    }
+
+    let (end, index) = footer(&mut file).map_err(failed)?.unzip();
+    file.seek(SeekFrom::Start(MAGIC.len() as u64))
+        .map_err(failed)?;
+
+    let frames = Frames::Framed {
+        reader: BufReader::new(file),
+        position: MAGIC.len() as u64,
+        end,
+        index: index.unwrap_or_default(),
+    };
+    Ok((location, frames))
 }
 
-pub fn read<P: AsRef<Path>>(path: P) -> error::Result<Vec<Update>> {
-    let location = path.as_ref().to_string_lossy().into_owned();
+fn delimiter(reader: &mut impl Read) -> std::io::Result<Option<(u64, u64)>> {
+    let mut value = 0u64;
+    for width in 0..10 {
+        let mut byte = [0; 1];
+        match reader.read_exact(&mut byte) {
+            Err(error) if width == 0 && error.kind() == ErrorKind::UnexpectedEof => {
+                return Ok(None);
+            }
+            result => result?,
+        }
+        value |= u64::from(byte[0] & 0x7f) << (7 * width);
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some((value, width + 1)));
+        }
+    }
+    Err(ErrorKind::InvalidData.into())
+}
 
-    let file = File::open(path).map_err(|source| error::Error::Open {
-        path: location.clone(),
-        source,
-    })?;
+fn footer(file: &mut File) -> std::io::Result<Option<(u64, Index)>> {
+    let length = file.metadata()?.len();
+    let header = MAGIC.len() as u64;
+    let trailing = TRAILER as u64;
+    if length < header + trailing {
+        return Ok(None);
+    }
 
-    let mut reader = BufReader::new(file);
-    let mut contents = Vec::new();
+    let mut trailer = [0; TRAILER];
+    file.seek(SeekFrom::Start(length - trailing))?;
+    file.read_exact(&mut trailer)?;
 
-    reader
-        .read_to_end(&mut contents)
-        .map_err(|source| error::Error::Read {
-            path: location.clone(),
-            source,
-        })?;
+    if &trailer[16..] != FOOTER {
+        return Ok(None);
+    }
 
-    serde_json::from_slice(&contents).map_err(|source| error::Error::Format {
-        path: location,
-        source,
-    })
+    let word = |range: std::ops::Range<usize>| {
+        u64::from_le_bytes(trailer[range].try_into().unwrap_or_default())
+    };
+    let entries = word(0..8);
+    let end = word(8..16);
+
+    let size = entries.checked_mul(ENTRY as u64);
+    if end < header || size.and_then(|size| end.checked_add(size)) != Some(length - trailing) {
+        return Ok(None);
+    }
+
+    let mut raw = vec![
+        0;
+        size.and_then(|size| usize::try_from(size).ok())
+            .unwrap_or_default()
+    ];
+    file.seek(SeekFrom::Start(end))?;
+    file.read_exact(&mut raw)?;
+
+    let index = raw
+        .chunks_exact(ENTRY)
+        .map(|entry| {
+            let stamp = u64::from_le_bytes(entry[..8].try_into().unwrap_or_default());
+            let offset = u64::from_le_bytes(entry[8..].try_into().unwrap_or_default());
+            (stamp, offset)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Some((end, index)))
 }
@@ -1,11 +1,12 @@
 pub use error;
 
-use endpoint::{Sink, Stream};
+use endpoint::{Delivery, Sink, Stream};
 use expression::Expression;
 use filter::Filter;
 use filterable::Filterable as _;
 use std::io::{LineWriter, Write};
-use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex};
 use tokio_util::sync::CancellationToken;
 use tracing_chrome::{ChromeLayer, FlushGuard};
 use tracing_subscriber::prelude::*;
@@ -14,9 +15,19 @@ use tracing_subscriber::{Registry, fmt};
 type LogWriter = Mutex<LineWriter<Box<dyn Write + Send>>>;
 
 enum Output {
-    Log(fmt::Layer<Registry, fmt::format::DefaultFields, fmt::format::Format, LogWriter>),
+    Log(fmt::Layer<Registry, log::Fields, fmt::format::Format, LogWriter>),
     Chrome(ChromeLayer<Registry>),
     Grpc(layer::Streamer),
+    Record(layer::Streamer),
+    Otlp(layer::Streamer),
+    Http(layer::Streamer),
+}
+
+enum Relay {
+    Grpc(url::Url, Delivery, Arc<AtomicU64>),
+    Record(std::path::PathBuf, Option<u64>),
+    Otlp(url::Url, String, Arc<AtomicU64>),
+    Http(url::Url),
 }
 
 macro_rules! delegate {
@@ -31,6 +42,15 @@ macro_rules! delegate {
             Self::Grpc(output) => {
                 tracing_subscriber::Layer::<Registry>::$method(output, $($arg),*)
             }
+            Self::Record(output) => {
+                tracing_subscriber::Layer::<Registry>::$method(output, $($arg),*)
+            }
+            Self::Otlp(output) => {
+                tracing_subscriber::Layer::<Registry>::$method(output, $($arg),*)
+            }
+            Self::Http(output) => {
+                tracing_subscriber::Layer::<Registry>::$method(output, $($arg),*)
+            }
         }
     };
 }
@@ -176,16 +196,17 @@ pub fn initialize(sinks: Vec<Sink>, cancellation: CancellationToken) -> error::R
                             url,
                             level,
                             channels,
+                            redaction,
                         },
                     ansi,
                 } => {
                     expressions.push(channels.clone());
                     let layer = match url.host_str() {
-                        Some("1") => log::stdout(*ansi),
-                        Some("2") => log::stderr(*ansi),
+                        Some("1") => log::stdout(*ansi, redaction.clone()),
+                        Some("2") => log::stderr(*ansi, redaction.clone()),
                         _ => {
                             let path = std::path::PathBuf::from(url.path());
-                            log::file(&path, *ansi)?
+                            log::file(&path, *ansi, redaction.clone())?
                         }
                     };
                     layers.push(Output::Log(layer).with_filter(*level));
@@ -194,6 +215,7 @@ pub fn initialize(sinks: Vec<Sink>, cancellation: CancellationToken) -> error::R
                     url,
                     level,
                     channels,
+                    ..
                 }) => {
                     expressions.push(channels.clone());
                     let path = std::path::PathBuf::from(url.path());
@@ -207,16 +229,93 @@ pub fn initialize(sinks: Vec<Sink>, cancellation: CancellationToken) -> error::R
                             url,
                             level,
                             channels,
+                            redaction,
                         },
+                    delivery,
                     backpressure,
+                    sample,
                 } => {
                     expressions.push(channels.clone());
-                    let (streamer, receiver) =
-                        grpc::layer(channels.clone().predicate(), *backpressure);
+                    let (streamer, receiver) = grpc::layer(
+                        channels.clone().predicate(),
+                        *backpressure,
+                        sample.clone(),
+                        redaction.clone(),
+                    );
+                    let dropped = streamer.counter();
                     layers.push(Output::Grpc(streamer).with_filter(*level));
-                    endpoints.push((url.clone(), receiver));
+                    endpoints.push((
+                        Relay::Grpc(url.clone(), delivery.clone(), dropped),
+                        receiver,
+                    ));
+                }
+                Sink::Record {
+                    stream:
+                        Stream {
+                            url,
+                            level,
+                            channels,
+                            redaction,
+                        },
+                    rotation,
+                    backpressure,
+                    sample,
+                } => {
+                    expressions.push(channels.clone());
+                    let (streamer, receiver) = capture::layer(
+                        channels.clone().predicate(),
+                        *backpressure,
+                        sample.clone(),
+                        redaction.clone(),
+                    );
+                    layers.push(Output::Record(streamer).with_filter(*level));
+                    let path = std::path::PathBuf::from(url.path());
+                    endpoints.push((Relay::Record(path, *rotation), receiver));
+                }
+                Sink::Otlp {
+                    stream:
+                        Stream {
+                            url,
+                            level,
+                            channels,
+                            redaction,
+                        },
+                    service,
+                    backpressure,
+                    sample,
+                } => {
+                    expressions.push(channels.clone());
+                    let (streamer, receiver) = otlp::layer(
+                        channels.clone().predicate(),
+                        *backpressure,
+                        sample.clone(),
+                        redaction.clone(),
+                    );
+                    let dropped = streamer.counter();
+                    layers.push(Output::Otlp(streamer).with_filter(*level));
+                    endpoints.push((Relay::Otlp(url.clone(), service.clone(), dropped), receiver));
+                }
+                Sink::Http {
+                    stream:
+                        Stream {
+                            url,
+                            level,
+                            channels,
+                            redaction,
+                        },
+                    backpressure,
+                    sample,
+                } => {
+                    expressions.push(channels.clone());
+                    let (streamer, receiver) = live::layer(
+                        channels.clone().predicate(),
+                        *backpressure,
+                        sample.clone(),
+                        redaction.clone(),
+                    );
+                    layers.push(Output::Http(streamer).with_filter(*level));
+                    endpoints.push((Relay::Http(url.clone()), receiver));
                 }
-                Sink::Http(_) => {}
             }
             Ok((layers, endpoints))
         },
@@ -232,8 +331,23 @@ pub fn initialize(sinks: Vec<Sink>, cancellation: CancellationToken) -> error::R
             details: e.to_string(),
         })?;
 
-    for (url, receiver) in endpoints {
-        guard.track(grpc::spawn(url, receiver, cancellation.clone())?);
+    for (relay, receiver) in endpoints {
+        let handle = match relay {
+            Relay::Grpc(url, Delivery::Serve, _) => {
+                grpc::spawn(url, receiver, cancellation.clone())?
+            }
+            Relay::Grpc(url, Delivery::Push { buffer, spill }, dropped) => {
+                grpc::forward(url, buffer, spill, dropped, receiver, cancellation.clone())?
+            }
+            Relay::Record(path, rotation) => {
+                capture::spawn(path, rotation, receiver, cancellation.clone())?
+            }
+            Relay::Otlp(url, service, dropped) => {
+                otlp::spawn(url, service, dropped, receiver, cancellation.clone())?
+            }
+            Relay::Http(url) => live::spawn(url, receiver, cancellation.clone())?,
+        };
+        guard.track(handle);
     }
 
     Ok(guard)
@@ -6,6 +6,7 @@ pub fn update(update: wire::Update) -> Option<stream::Update> {
         wire::update::Payload::Span(s) => Some(stream::Update::Span(span(s)?)),
         wire::update::Payload::Event(e) => Some(stream::Update::Event(event(e)?)),
         wire::update::Payload::Snapshot(s) => Some(stream::Update::Snapshot(snapshot(s))),
+        wire::update::Payload::Metric(m) => Some(stream::Update::Metric(metric(m)?)),
     }
 }
 
@@ -59,6 +60,29 @@ pub fn snapshot(snapshot: wire::Snapshot) -> stream::Snapshot {
     }
 }
 
+#[must_use]
+pub fn metric(metric: wire::Metric) -> Option<stream::Metric> {
+    let measure = match metric.measure? {
+        wire::metric::Measure::Counter(total) => stream::Measure::Counter(total),
+        wire::metric::Measure::Gauge(current) => stream::Measure::Gauge(current),
+        wire::metric::Measure::Histogram(histogram) => {
+            stream::Measure::Histogram(stream::Histogram {
+                bounds: histogram.bounds,
+                counts: histogram.counts,
+                count: histogram.count,
+                sum: histogram.sum,
+            })
+        }
+    };
+
+    Some(stream::Metric {
+        name: metric.name,
+        channels: metric.channels.into_iter().map(channel).collect::<Vec<_>>(),
+        timestamp: metric.timestamp,
+        measure,
+    })
+}
+
 #[must_use]
 pub fn metadata(meta: wire::Metadata) -> stream::Metadata {
     stream::Metadata {
@@ -101,3 +125,20 @@ pub fn field(field: wire::Field) -> Option<stream::Field> {
         value,
     })
 }
+
+#[must_use]
+pub fn command(command: wire::Command) -> Option<stream::Command> {
+    match command.instruction? {
+        wire::command::Instruction::Pause(_) => Some(stream::Command::Pause),
+        wire::command::Instruction::Resume(_) => Some(stream::Command::Resume),
+        wire::command::Instruction::Seek(s) => Some(stream::Command::Seek(s.timestamp)),
+        wire::command::Instruction::Filter(f) => Some(stream::Command::Filter {
+            targets: f.targets,
+            level: match wire::Level::try_from(f.level) {
+                Ok(wire::Level::Undefined) | Err(_) => stream::Level::Trace,
+                Ok(_) => level(f.level),
+            },
+        }),
+        wire::command::Instruction::Snapshot(_) => Some(stream::Command::Snapshot),
+    }
+}
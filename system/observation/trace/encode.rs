@@ -1 +1,3 @@
+pub use otlp;
+pub use prometheus;
 pub use proto;
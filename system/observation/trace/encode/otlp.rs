@@ -0,0 +1,151 @@
+use otlp::opentelemetry::proto::collector::trace::v1 as wire;
+
+#[must_use]
+pub fn trace(trace: u64) -> Vec<u8> {
+    let mut identifier = vec![0; 8];
+    identifier.extend_from_slice(&trace.to_be_bytes());
+    identifier
+}
+
+#[must_use]
+pub fn span(span: u64) -> Vec<u8> {
+    span.to_be_bytes().to_vec()
+}
+
+#[must_use]
+pub fn nanoseconds(timestamp: u64) -> u64 {
+    timestamp.saturating_mul(1000)
+}
+
+#[must_use]
+pub fn attribute(field: stream::Field) -> wire::KeyValue {
+    let value = match field.value {
+        stream::Value::Signed(v) => wire::any_value::Value::IntValue(v),
+        stream::Value::Unsigned(v) => i64::try_from(v).map_or_else(
+            |_| wire::any_value::Value::StringValue(v.to_string()),
+            wire::any_value::Value::IntValue,
+        ),
+        stream::Value::Boolean(v) => wire::any_value::Value::BoolValue(v),
+        stream::Value::Text(v) => wire::any_value::Value::StringValue(v),
+        stream::Value::Serialized(v) => wire::any_value::Value::BytesValue(v),
+    };
+    wire::KeyValue {
+        key: field.name,
+        value: Some(wire::AnyValue { value: Some(value) }),
+    }
+}
+
+#[must_use]
+pub fn text(key: &str, value: String) -> wire::KeyValue {
+    wire::KeyValue {
+        key: key.to_string(),
+        value: Some(wire::AnyValue {
+            value: Some(wire::any_value::Value::StringValue(value)),
+        }),
+    }
+}
+
+#[must_use]
+pub fn channel(channel: stream::channel::Channel) -> wire::KeyValue {
+    wire::KeyValue {
+        key: format!("channel.{}", channel.name),
+        value: Some(wire::AnyValue {
+            value: Some(wire::any_value::Value::IntValue(i64::from(channel.weight))),
+        }),
+    }
+}
+
+#[must_use]
+pub fn metadata(metadata: stream::Metadata) -> Vec<wire::KeyValue> {
+    vec![
+        text("code.namespace", metadata.target),
+        text("level", format!("{:?}", metadata.level).to_uppercase()),
+    ]
+}
+
+#[must_use]
+pub fn event(event: stream::Event) -> wire::span::Event {
+    let mut attributes = metadata(event.metadata.clone());
+    attributes.extend(event.channels.into_iter().map(channel));
+    attributes.extend(event.fields.into_iter().map(attribute));
+
+    wire::span::Event {
+        time_unix_nano: nanoseconds(event.timestamp),
+        name: event.metadata.name,
+        attributes,
+        dropped_attributes_count: 0,
+    }
+}
+
+#[must_use]
+pub fn completed(
+    span: stream::Span,
+    begin: stream::Begin,
+    end: stream::End,
+    events: Vec<stream::Event>,
+) -> wire::Span {
+    let status = events
+        .iter()
+        .any(|event| event.metadata.level == stream::Level::Error)
+        .then(|| wire::Status {
+            message: String::new(),
+            code: wire::status::StatusCode::Error as i32,
+        });
+
+    let mut attributes = metadata(span.metadata.clone());
+    attributes.extend(span.channels.into_iter().map(channel));
+    attributes.extend(begin.fields.into_iter().map(attribute));
+
+    wire::Span {
+        trace_id: trace(span.id.trace),
+        span_id: self::span(span.id.span),
+        parent_span_id: span.id.parent.map(self::span).unwrap_or_default(),
+        name: span.metadata.name,
+        kind: wire::span::SpanKind::Internal as i32,
+        start_time_unix_nano: nanoseconds(begin.timestamp),
+        end_time_unix_nano: nanoseconds(end.timestamp),
+        attributes,
+        events: events.into_iter().map(event).collect::<Vec<_>>(),
+        status,
+        ..wire::Span::default()
+    }
+}
+
+#[must_use]
+pub fn orphan(trace: u64, identifier: u64, event: stream::Event) -> wire::Span {
+    let timestamp = nanoseconds(event.timestamp);
+    let parent = event.parent.map(span).unwrap_or_default();
+
+    wire::Span {
+        trace_id: self::trace(trace),
+        span_id: span(identifier),
+        parent_span_id: parent,
+        name: event.metadata.name.clone(),
+        kind: wire::span::SpanKind::Internal as i32,
+        start_time_unix_nano: timestamp,
+        end_time_unix_nano: timestamp,
+        events: vec![self::event(event)],
+        ..wire::Span::default()
+    }
+}
+
+#[must_use]
+pub fn request(service: &str, spans: Vec<wire::Span>) -> wire::ExportTraceServiceRequest {
+    wire::ExportTraceServiceRequest {
+        resource_spans: vec![wire::ResourceSpans {
+            resource: Some(wire::Resource {
+                attributes: vec![text("service.name", service.to_string())],
+                dropped_attributes_count: 0,
+            }),
+            scope_spans: vec![wire::ScopeSpans {
+                scope: Some(wire::InstrumentationScope {
+                    name: "vantle".to_string(),
+                    ..wire::InstrumentationScope::default()
+                }),
+                spans,
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
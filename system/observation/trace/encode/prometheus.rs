@@ -0,0 +1,78 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+pub const CONTENT: &str = "text/plain; version=0.0.4";
+
+#[must_use]
+pub fn exposition<'a, I: IntoIterator<Item = &'a stream::Metric>>(metrics: I) -> String {
+    let mut metrics = metrics.into_iter().collect::<Vec<_>>();
+    metrics.sort_by(|a, b| (&a.name, &a.channels).cmp(&(&b.name, &b.channels)));
+
+    let mut text = String::new();
+    let mut declared = None;
+    for metric in metrics {
+        let name = family(metric);
+        if declared.as_ref() != Some(&name) {
+            let _ = writeln!(text, "# TYPE {name} {}", kind(&metric.measure));
+            declared = Some(name.clone());
+        }
+        sample(&mut text, &name, metric);
+    }
+    text
+}
+
+#[must_use]
+pub fn family(metric: &stream::Metric) -> Cow<'_, str> {
+    match metric.measure {
+        stream::Measure::Counter(_) if !metric.name.ends_with("_total") => {
+            Cow::Owned(format!("{}_total", metric.name))
+        }
+        _ => Cow::Borrowed(&metric.name),
+    }
+}
+
+#[must_use]
+pub fn kind(measure: &stream::Measure) -> &'static str {
+    match measure {
+        stream::Measure::Counter(_) => "counter",
+        stream::Measure::Gauge(_) => "gauge",
+        stream::Measure::Histogram(_) => "histogram",
+    }
+}
+
+#[must_use]
+pub fn labels(channels: &[stream::channel::Channel]) -> String {
+    format!(
+        "channels=\"{}\"",
+        stream::channel::Channel::serialize(channels)
+    )
+}
+
+fn sample(text: &mut String, name: &str, metric: &stream::Metric) {
+    let labels = labels(&metric.channels);
+    match &metric.measure {
+        stream::Measure::Counter(total) => {
+            let _ = writeln!(text, "{name}{{{labels}}} {total}");
+        }
+        stream::Measure::Gauge(current) => {
+            let _ = writeln!(text, "{name}{{{labels}}} {current}");
+        }
+        stream::Measure::Histogram(histogram) => {
+            let bounds = histogram
+                .bounds
+                .iter()
+                .map(ToString::to_string)
+                .chain(std::iter::once("+Inf".to_string()));
+            let mut cumulative = 0;
+            for (bound, count) in bounds.zip(&histogram.counts) {
+                cumulative += count;
+                let _ = writeln!(
+                    text,
+                    "{name}_bucket{{{labels},le=\"{bound}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(text, "{name}_sum{{{labels}}} {}", histogram.sum);
+            let _ = writeln!(text, "{name}_count{{{labels}}} {}", histogram.count);
+        }
+    }
+}
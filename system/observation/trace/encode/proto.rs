@@ -6,6 +6,7 @@ pub fn update(update: stream::Update) -> wire::Update {
         stream::Update::Span(s) => wire::update::Payload::Span(span(s)),
         stream::Update::Event(e) => wire::update::Payload::Event(event(e)),
         stream::Update::Snapshot(s) => wire::update::Payload::Snapshot(snapshot(s)),
+        stream::Update::Metric(m) => wire::update::Payload::Metric(metric(m)),
     };
     wire::Update {
         payload: Some(payload),
@@ -64,6 +65,29 @@ pub fn snapshot(snapshot: stream::Snapshot) -> wire::Snapshot {
     }
 }
 
+#[must_use]
+pub fn metric(metric: stream::Metric) -> wire::Metric {
+    let measure = match metric.measure {
+        stream::Measure::Counter(total) => wire::metric::Measure::Counter(total),
+        stream::Measure::Gauge(current) => wire::metric::Measure::Gauge(current),
+        stream::Measure::Histogram(histogram) => {
+            wire::metric::Measure::Histogram(wire::Histogram {
+                bounds: histogram.bounds,
+                counts: histogram.counts,
+                count: histogram.count,
+                sum: histogram.sum,
+            })
+        }
+    };
+
+    wire::Metric {
+        name: metric.name,
+        channels: metric.channels.into_iter().map(channel).collect::<Vec<_>>(),
+        timestamp: metric.timestamp,
+        measure: Some(measure),
+    }
+}
+
 #[must_use]
 pub fn metadata(metadata: stream::Metadata) -> wire::Metadata {
     wire::Metadata {
@@ -98,3 +122,22 @@ pub fn field(field: stream::Field) -> wire::Field {
         value: Some(value),
     }
 }
+
+#[must_use]
+pub fn command(command: stream::Command) -> wire::Command {
+    let instruction = match command {
+        stream::Command::Pause => wire::command::Instruction::Pause(true),
+        stream::Command::Resume => wire::command::Instruction::Resume(true),
+        stream::Command::Seek(timestamp) => wire::command::Instruction::Seek(wire::Seek { timestamp }),
+        stream::Command::Filter { targets, level: l } => {
+            wire::command::Instruction::Filter(wire::Filter {
+                targets,
+                level: level(l) as i32,
+            })
+        }
+        stream::Command::Snapshot => wire::command::Instruction::Snapshot(true),
+    };
+    wire::Command {
+        instruction: Some(instruction),
+    }
+}
@@ -8,7 +8,9 @@ pub enum Error {
     #[error("invalid channel expression in sink URI: {expression}")]
     #[diagnostic(
         code(trace::sink::channels),
-        help("use . (and), , (or), ! (not), () (group) — e.g. core,http.!debug")
+        help(
+            "use . or & (and), , (or), ! (not), () (group), name.* (subtree), name:>=N (weight), level>=L — e.g. hypergraph.*,level>=info"
+        )
     )]
     Channels {
         expression: String,
@@ -16,6 +18,19 @@ pub enum Error {
         source: expression::Sourced,
     },
 
+    #[error("invalid sampling policy in sink URI: {policy}")]
+    #[diagnostic(
+        code(trace::sink::sample),
+        help(
+            "use weight>=N, a rate such as 0.1, or per-channel rates such as core:0.01,query:0.5"
+        )
+    )]
+    Sample {
+        policy: String,
+        #[source]
+        source: sample::Error,
+    },
+
     #[error("failed to initialize tracing subscriber: {details}")]
     #[diagnostic(
         code(trace::subscriber),
@@ -23,6 +38,30 @@ pub enum Error {
     )]
     Subscriber { details: String },
 
+    #[error("invalid redaction rules in sink URI: {rules}")]
+    #[diagnostic(
+        code(trace::sink::redact),
+        help(
+            "use pattern:action pairs such as password:drop,token*:hash,source:truncate(64), or @path to a rules file"
+        )
+    )]
+    Redaction {
+        rules: String,
+        #[source]
+        source: redaction::Error,
+    },
+
+    #[error("failed to read redaction rules: {path}")]
+    #[diagnostic(
+        code(trace::sink::rules),
+        help("check that the rules file exists and is readable")
+    )]
+    Rules {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("failed to create trace file: {path}")]
     #[diagnostic(code(trace::file), help("check directory permissions and disk space"))]
     File {
@@ -46,7 +85,7 @@ pub enum Error {
     #[diagnostic(
         code(trace::sink::scheme),
         help(
-            "supported schemes: log, chrome, grpc, http (e.g., log:///tmp/trace.jsonl, http://127.0.0.1:3000)"
+            "supported schemes: log, chrome, grpc, unix, inproc, record, http, otlp (e.g., log:///tmp/trace.jsonl, http://127.0.0.1:3000)"
         )
     )]
     Scheme { scheme: String },
@@ -76,4 +115,15 @@ pub enum Error {
         #[source]
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+
+    #[error("failed to create record at {path}")]
+    #[diagnostic(
+        code(trace::record),
+        help("check directory permissions and disk space")
+    )]
+    Record {
+        path: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
 }
@@ -0,0 +1,71 @@
+use assemble::Assemble;
+use redaction::Redaction;
+use sample::Sample;
+use std::path::PathBuf;
+use stream::Predicate;
+use tokio::sync::mpsc::Receiver;
+use tokio_util::sync::CancellationToken;
+
+pub fn layer(
+    predicate: Predicate,
+    backpressure: layer::Backpressure,
+    sample: Sample,
+    redaction: Redaction,
+) -> (layer::Streamer, Receiver<stream::Update>) {
+    layer::Streamer::assembler(predicate)
+        .backpressure(backpressure)
+        .sample(sample)
+        .redact(redaction)
+        .assemble()
+}
+
+pub fn spawn(
+    path: PathBuf,
+    rotation: Option<u64>,
+    receiver: Receiver<stream::Update>,
+    cancellation: CancellationToken,
+) -> error::Result<tokio::task::JoinHandle<()>> {
+    let assembler = record::Assembler::new(&path);
+    let assembler = match rotation {
+        Some(bytes) => assembler.rotation(bytes),
+        None => assembler,
+    };
+
+    let writer = assembler.assemble().map_err(|e| error::Error::Record {
+        path: path.display().to_string(),
+        source: Box::new(e),
+    })?;
+
+    Ok(tokio::spawn(capture(writer, receiver, cancellation)))
+}
+
+async fn capture(
+    mut writer: record::Writer,
+    mut receiver: Receiver<stream::Update>,
+    token: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            () = token.cancelled() => break,
+            received = receiver.recv() => {
+                let Some(update) = received else { break };
+                if let Err(e) = writer.append(update) {
+                    tracing::warn!("failed to capture observation update: {e}");
+                }
+                if receiver.is_empty() && let Err(e) = writer.flush() {
+                    tracing::warn!("failed to flush observation record: {e}");
+                }
+            }
+        }
+    }
+
+    while let Ok(update) = receiver.try_recv() {
+        if let Err(e) = writer.append(update) {
+            tracing::warn!("failed to capture observation update: {e}");
+        }
+    }
+
+    if let Err(e) = writer.finish() {
+        tracing::warn!("failed to finish observation record: {e}");
+    }
+}
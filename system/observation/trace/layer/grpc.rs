@@ -1,16 +1,53 @@
 use assemble::Assemble;
+use proto::observation::sink_client::SinkClient;
+use redaction::Redaction;
+use sample::Sample;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
 use stream::Predicate;
-use tokio::sync::mpsc::Receiver;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
 use url::Url;
 
+const BACKOFF: Duration = Duration::from_millis(100);
+const CEILING: Duration = Duration::from_secs(10);
+
+type Client = SinkClient<tonic::transport::Channel>;
+type Bearer = tonic::metadata::MetadataValue<tonic::metadata::Ascii>;
+
+enum Outlet {
+    Client(Client),
+    Inbox(Sender<stream::Update>),
+}
+
+async fn connect(
+    transport: &peer::Transport,
+    credentials: &peer::Credentials,
+) -> peer::error::Result<Outlet> {
+    match transport {
+        peer::Transport::Inproc(name) => peer::inbox(name, credentials).map(Outlet::Inbox),
+        transport => peer::channel(transport, credentials)
+            .await
+            .map(|channel| Outlet::Client(SinkClient::new(channel))),
+    }
+}
+
 pub fn layer(
     predicate: Predicate,
     backpressure: layer::Backpressure,
+    sample: Sample,
+    redaction: Redaction,
 ) -> (layer::Streamer, Receiver<stream::Update>) {
     layer::Streamer::assembler(predicate)
         .backpressure(backpressure)
+        .sample(sample)
+        .redact(redaction)
         .assemble()
 }
 
@@ -54,3 +91,382 @@ async fn relay(url: Url, mut receiver: Receiver<stream::Update>, token: Cancella
         }
     }
 }
+
+pub fn forward(
+    url: Url,
+    buffer: usize,
+    spill: Option<PathBuf>,
+    dropped: Arc<AtomicU64>,
+    receiver: Receiver<stream::Update>,
+    cancellation: CancellationToken,
+) -> error::Result<tokio::task::JoinHandle<()>> {
+    if url.scheme() == "grpc" {
+        url.host_str().ok_or_else(|| error::Error::Host {
+            address: peer::redacted(&url).to_string(),
+        })?;
+        url.port().ok_or_else(|| error::Error::Port {
+            address: peer::redacted(&url).to_string(),
+        })?;
+    }
+    let connect = |source| error::Error::Connect {
+        address: peer::redacted(&url).to_string(),
+        source: Box::new(source),
+    };
+    let transport = peer::transport(&url).map_err(connect)?;
+    let credentials = peer::credentials(&url).map_err(connect)?;
+    let bearer = credentials.bearer(&transport).map_err(connect)?;
+
+    let backlog = Backlog {
+        memory: VecDeque::new(),
+        capacity: buffer.max(1),
+        spill: spill.map(Spill::new),
+        dropped,
+        reported: 0,
+    };
+
+    Ok(tokio::spawn(deliver(
+        transport,
+        credentials,
+        bearer,
+        backlog,
+        receiver,
+        cancellation,
+    )))
+}
+
+struct Spill {
+    path: PathBuf,
+    writer: Option<record::Writer>,
+    reader: Option<record::Reader>,
+}
+
+impl Spill {
+    fn new(path: PathBuf) -> Self {
+        let mut spill = Self {
+            path,
+            writer: None,
+            reader: None,
+        };
+
+        if !record::segments(&spill.path).is_empty() {
+            match record::open(&spill.path) {
+                Ok(reader) => spill.reader = Some(reader),
+                Err(e) => {
+                    tracing::warn!("failed to open spill file {}: {e}", spill.path.display());
+                }
+            }
+        }
+        spill
+    }
+
+    fn writer(&mut self) -> Option<&mut record::Writer> {
+        if self.reader.is_some() {
+            return None;
+        }
+
+        if self.writer.is_none() {
+            match record::Assembler::new(&self.path).assemble() {
+                Ok(writer) => self.writer = Some(writer),
+                Err(e) => {
+                    tracing::warn!("failed to open spill file {}: {e}", self.path.display());
+                }
+            }
+        }
+        self.writer.as_mut()
+    }
+
+    fn read(&mut self) -> Option<stream::Update> {
+        let reader = self.reader.as_mut()?;
+        match reader.next() {
+            Some(Ok(update)) => Some(update),
+            Some(Err(e)) => {
+                tracing::warn!("failed to read spill file {}: {e}", self.path.display());
+                self.discard();
+                None
+            }
+            None => {
+                self.discard();
+                None
+            }
+        }
+    }
+
+    fn drain(&mut self) -> Option<stream::Update> {
+        let writer = self.writer.take()?;
+        if let Err(e) = writer.finish() {
+            tracing::warn!("failed to finish spill file {}: {e}", self.path.display());
+        }
+
+        match record::open(&self.path) {
+            Ok(reader) => self.reader = Some(reader),
+            Err(e) => {
+                tracing::warn!("failed to open spill file {}: {e}", self.path.display());
+                return None;
+            }
+        }
+        self.read()
+    }
+
+    fn discard(&mut self) {
+        self.reader = None;
+        for segment in record::segments(&self.path) {
+            let _ = std::fs::remove_file(segment);
+        }
+    }
+
+    fn close(&mut self) {
+        if let Some(writer) = self.writer.take()
+            && let Err(e) = writer.finish()
+        {
+            tracing::warn!("failed to finish spill file {}: {e}", self.path.display());
+        }
+    }
+}
+
+struct Backlog {
+    memory: VecDeque<stream::Update>,
+    capacity: usize,
+    spill: Option<Spill>,
+    dropped: Arc<AtomicU64>,
+    reported: u64,
+}
+
+impl Backlog {
+    fn push(&mut self, update: stream::Update) {
+        let spilling = self.memory.len() >= self.capacity
+            || self
+                .spill
+                .as_ref()
+                .is_some_and(|spill| spill.writer.is_some());
+
+        if spilling && let Some(writer) = self.spill.as_mut().and_then(Spill::writer) {
+            if let Err(e) = writer.append(update) {
+                tracing::warn!("failed to spill observation update: {e}");
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            return;
+        }
+
+        if self.memory.len() >= self.capacity {
+            self.memory.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        self.memory.push_back(update);
+    }
+
+    fn next(&mut self) -> Option<stream::Update> {
+        let dropped = self.dropped.load(Ordering::Relaxed);
+        if dropped > self.reported {
+            self.reported = dropped;
+            return Some(report(dropped));
+        }
+
+        if let Some(update) = self.spill.as_mut().and_then(Spill::read) {
+            return Some(update);
+        }
+        if let Some(update) = self.memory.pop_front() {
+            return Some(update);
+        }
+        self.spill.as_mut().and_then(Spill::drain)
+    }
+
+    fn close(&mut self) {
+        if let Some(spill) = self.spill.as_mut() {
+            spill.close();
+        }
+    }
+}
+
+fn report(dropped: u64) -> stream::Update {
+    stream::Update::Event(stream::Event {
+        parent: None,
+        metadata: stream::Metadata {
+            target: "observation::relay".to_string(),
+            name: "dropped".to_string(),
+            level: stream::Level::Warn,
+        },
+        channels: Vec::new(),
+        timestamp: stream::timestamp(),
+        fields: vec![stream::Field {
+            name: "dropped".to_string(),
+            value: stream::Value::Unsigned(dropped),
+        }],
+    })
+}
+
+struct Relay {
+    bearer: Option<Bearer>,
+    backlog: Backlog,
+    receiver: Receiver<stream::Update>,
+    pending: Option<stream::Update>,
+    inflight: VecDeque<stream::Update>,
+    retry: VecDeque<stream::Update>,
+    open: bool,
+}
+
+impl Relay {
+    fn exhausted(&self) -> bool {
+        !self.open && self.pending.is_none()
+    }
+
+    async fn transmit(&mut self, outlet: Outlet, token: &CancellationToken) -> bool {
+        match outlet {
+            Outlet::Client(mut client) => {
+                let (sender, outgoing) = mpsc::channel(1);
+                let pulled = Arc::new(AtomicUsize::new(0));
+                let counter = Arc::clone(&pulled);
+                let bearer = self.bearer.clone();
+                let call = async move {
+                    let updates = ReceiverStream::new(outgoing).map(move |update| {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                        encode::proto::update(update)
+                    });
+                    if let Err(e) = client.send(peer::authorize(updates, bearer.as_ref())).await {
+                        tracing::warn!("lost connection to observation sink: {e}");
+                    }
+                };
+                self.pump(sender, call, &pulled, token).await
+            }
+            Outlet::Inbox(inbox) => {
+                let (sender, mut outgoing) = mpsc::channel(1);
+                let pulled = Arc::new(AtomicUsize::new(0));
+                let counter = Arc::clone(&pulled);
+                let call = async move {
+                    while let Some(update) = outgoing.recv().await {
+                        if inbox.send(update).await.is_err() {
+                            tracing::warn!("lost in-process observation sink");
+                            return;
+                        }
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    }
+                };
+                self.pump(sender, call, &pulled, token).await
+            }
+        }
+    }
+
+    async fn pump<F: Future<Output = ()>>(
+        &mut self,
+        sender: Sender<stream::Update>,
+        call: F,
+        pulled: &AtomicUsize,
+        token: &CancellationToken,
+    ) -> bool {
+        tokio::pin!(call);
+
+        loop {
+            self.settle(pulled);
+            if self.pending.is_none() {
+                self.pending = self.retry.pop_front().or_else(|| self.backlog.next());
+            }
+            if self.exhausted() {
+                drop(sender);
+                tokio::select! {
+                    () = token.cancelled() => {}
+                    () = &mut call => {}
+                }
+                return true;
+            }
+
+            tokio::select! {
+                () = token.cancelled() => return true,
+                () = &mut call => {
+                    self.requeue(pulled);
+                    return false;
+                }
+                permit = sender.reserve(), if self.pending.is_some() => {
+                    let Ok(permit) = permit else {
+                        self.requeue(pulled);
+                        return false;
+                    };
+                    let update = self.pending.take().expect("pending update");
+                    self.inflight.push_back(update.clone());
+                    permit.send(update);
+                }
+                received = self.receiver.recv(), if self.pending.is_none() && self.open => {
+                    match received {
+                        Some(update) => self.pending = Some(update),
+                        None => self.open = false,
+                    }
+                }
+            }
+        }
+    }
+
+    fn settle(&mut self, pulled: &AtomicUsize) {
+        let taken = pulled.swap(0, Ordering::Relaxed);
+        self.inflight.drain(..taken.min(self.inflight.len()));
+    }
+
+    // Updates the call already pulled may have reached the sink, so only those
+    // still queued for it are retried on the next connection.
+    fn requeue(&mut self, pulled: &AtomicUsize) {
+        self.settle(pulled);
+        let mut retry = std::mem::take(&mut self.inflight);
+        retry.extend(self.pending.take());
+        retry.append(&mut self.retry);
+        self.retry = retry;
+    }
+
+    async fn wait(&mut self, delay: Duration, token: &CancellationToken) -> bool {
+        let sleep = tokio::time::sleep(delay);
+        tokio::pin!(sleep);
+
+        loop {
+            tokio::select! {
+                () = token.cancelled() => return false,
+                () = &mut sleep => return true,
+                received = self.receiver.recv(), if self.open => match received {
+                    Some(update) => self.backlog.push(update),
+                    None => self.open = false,
+                },
+            }
+        }
+    }
+}
+
+async fn deliver(
+    transport: peer::Transport,
+    credentials: peer::Credentials,
+    bearer: Option<Bearer>,
+    backlog: Backlog,
+    receiver: Receiver<stream::Update>,
+    token: CancellationToken,
+) {
+    let mut relay = Relay {
+        bearer,
+        backlog,
+        receiver,
+        pending: None,
+        inflight: VecDeque::new(),
+        retry: VecDeque::new(),
+        open: true,
+    };
+    let mut delay = BACKOFF;
+
+    loop {
+        let connected = tokio::select! {
+            () = token.cancelled() => break,
+            connected = connect(&transport, &credentials) => connected,
+        };
+
+        match connected {
+            Ok(outlet) => {
+                delay = BACKOFF;
+                if relay.transmit(outlet, &token).await {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::debug!("failed to connect to observation sink at {transport}: {e}");
+            }
+        }
+
+        if !relay.wait(delay, &token).await {
+            break;
+        }
+        delay = (delay * 2).min(CEILING);
+    }
+
+    relay.backlog.close();
+}
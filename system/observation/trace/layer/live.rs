@@ -0,0 +1,28 @@
+use assemble::Assemble;
+use redaction::Redaction;
+use sample::Sample;
+use stream::Predicate;
+use tokio::sync::mpsc::Receiver;
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+pub fn layer(
+    predicate: Predicate,
+    backpressure: layer::Backpressure,
+    sample: Sample,
+    redaction: Redaction,
+) -> (layer::Streamer, Receiver<stream::Update>) {
+    layer::Streamer::assembler(predicate)
+        .backpressure(backpressure)
+        .sample(sample)
+        .redact(redaction)
+        .assemble()
+}
+
+pub fn spawn(
+    url: Url,
+    receiver: Receiver<stream::Update>,
+    cancellation: CancellationToken,
+) -> error::Result<tokio::task::JoinHandle<()>> {
+    http::Server::new(url, cancellation).live(receiver).spawn()
+}
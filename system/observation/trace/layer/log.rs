@@ -3,41 +3,108 @@ use std::io::{LineWriter, Write};
 use std::path::Path;
 use std::sync::Mutex;
 
-use tracing_subscriber::fmt;
+use redaction::Redaction;
+use tracing::field::Visit;
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::{self, FormatFields};
 use tracing_subscriber::registry::LookupSpan;
 
 type Writer = Mutex<LineWriter<Box<dyn Write + Send>>>;
 
+pub struct Fields {
+    redaction: Redaction,
+}
+
+impl<'writer> FormatFields<'writer> for Fields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        writer: fmt::format::Writer<'writer>,
+        fields: R,
+    ) -> std::fmt::Result {
+        if self.redaction.is_empty() {
+            return fmt::format::DefaultFields::new().format_fields(writer, fields);
+        }
+
+        let mut visitor = Visitor {
+            writer,
+            redaction: &self.redaction,
+            result: Ok(()),
+            first: true,
+        };
+        fields.record(&mut visitor);
+        visitor.result
+    }
+}
+
+struct Visitor<'a, 'writer> {
+    writer: fmt::format::Writer<'writer>,
+    redaction: &'a Redaction,
+    result: std::fmt::Result,
+    first: bool,
+}
+
+impl Visit for Visitor<'_, '_> {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        if self.result.is_err() {
+            return;
+        }
+
+        let Some(redacted) = self.redaction.field(stream::Field {
+            name: field.name().to_string(),
+            value: stream::Value::Text(format!("{value:?}")),
+        }) else {
+            return;
+        };
+
+        let separator = if self.first { "" } else { " " };
+        self.first = false;
+        self.result = if redacted.name == "message" {
+            write!(self.writer, "{separator}{}", redacted.value)
+        } else {
+            write!(
+                self.writer,
+                "{separator}{}={}",
+                redacted.name, redacted.value
+            )
+        };
+    }
+}
+
 #[must_use]
 pub fn stdout<S>(
     ansi: bool,
-) -> fmt::Layer<S, fmt::format::DefaultFields, fmt::format::Format, Writer>
+    redaction: Redaction,
+) -> fmt::Layer<S, Fields, fmt::format::Format, Writer>
 where
     S: tracing::Subscriber + for<'lookup> LookupSpan<'lookup>,
 {
     let writer: Box<dyn Write + Send> = Box::new(std::io::stdout());
     fmt::layer()
         .with_ansi(ansi)
+        .fmt_fields(Fields { redaction })
         .with_writer(Mutex::new(LineWriter::new(writer)))
 }
 
 #[must_use]
 pub fn stderr<S>(
     ansi: bool,
-) -> fmt::Layer<S, fmt::format::DefaultFields, fmt::format::Format, Writer>
+    redaction: Redaction,
+) -> fmt::Layer<S, Fields, fmt::format::Format, Writer>
 where
     S: tracing::Subscriber + for<'lookup> LookupSpan<'lookup>,
 {
     let writer: Box<dyn Write + Send> = Box::new(std::io::stderr());
     fmt::layer()
         .with_ansi(ansi)
+        .fmt_fields(Fields { redaction })
         .with_writer(Mutex::new(LineWriter::new(writer)))
 }
 
 pub fn file<S>(
     path: &Path,
     ansi: bool,
-) -> error::Result<fmt::Layer<S, fmt::format::DefaultFields, fmt::format::Format, Writer>>
+    redaction: Redaction,
+) -> error::Result<fmt::Layer<S, Fields, fmt::format::Format, Writer>>
 where
     S: tracing::Subscriber + for<'lookup> LookupSpan<'lookup>,
 {
@@ -49,5 +116,6 @@ where
     let writer: Box<dyn Write + Send> = Box::new(file);
     Ok(fmt::layer()
         .with_ansi(ansi)
+        .fmt_fields(Fields { redaction })
         .with_writer(Mutex::new(LineWriter::new(writer))))
 }
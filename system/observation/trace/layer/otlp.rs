@@ -0,0 +1,270 @@
+use assemble::Assemble;
+use otlp::opentelemetry::proto::collector::trace::v1 as wire;
+use redaction::Redaction;
+use sample::Sample;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use stream::Predicate;
+use tokio::sync::mpsc::Receiver;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use url::Url;
+use wire::trace_service_client::TraceServiceClient;
+
+const BATCH: usize = 512;
+const BACKLOG: usize = BATCH * 8;
+const INTERVAL: Duration = Duration::from_secs(1);
+const BACKOFF: Duration = Duration::from_millis(100);
+const CEILING: Duration = Duration::from_secs(10);
+
+type Client = TraceServiceClient<tonic::transport::Channel>;
+
+pub fn layer(
+    predicate: Predicate,
+    backpressure: layer::Backpressure,
+    sample: Sample,
+    redaction: Redaction,
+) -> (layer::Streamer, Receiver<stream::Update>) {
+    layer::Streamer::assembler(predicate)
+        .backpressure(backpressure)
+        .sample(sample)
+        .redact(redaction)
+        .assemble()
+}
+
+pub fn spawn(
+    url: Url,
+    service: String,
+    dropped: Arc<AtomicU64>,
+    receiver: Receiver<stream::Update>,
+    cancellation: CancellationToken,
+) -> error::Result<tokio::task::JoinHandle<()>> {
+    let host = url.host_str().ok_or_else(|| error::Error::Host {
+        address: url.to_string(),
+    })?;
+    let port = url.port().ok_or_else(|| error::Error::Port {
+        address: url.to_string(),
+    })?;
+    let endpoint = format!("http://{host}:{port}");
+
+    Ok(tokio::spawn(export(
+        endpoint,
+        service,
+        Batch::new(dropped),
+        receiver,
+        cancellation,
+    )))
+}
+
+struct Open {
+    span: stream::Span,
+    begin: stream::Begin,
+    events: Vec<stream::Event>,
+}
+
+struct Batch {
+    open: HashMap<u64, Open>,
+    traces: HashMap<u64, u64>,
+    spans: Vec<wire::Span>,
+    orphans: u64,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Batch {
+    fn new(dropped: Arc<AtomicU64>) -> Self {
+        Self {
+            open: HashMap::new(),
+            traces: HashMap::new(),
+            spans: Vec::new(),
+            orphans: 0,
+            dropped,
+        }
+    }
+
+    fn push(&mut self, update: stream::Update) {
+        match update {
+            stream::Update::Span(span) => match span.lifecycle.clone() {
+                stream::Lifecycle::Begin(begin) => {
+                    self.traces.insert(span.id.span, span.id.trace);
+                    self.open.insert(
+                        span.id.span,
+                        Open {
+                            span,
+                            begin,
+                            events: Vec::new(),
+                        },
+                    );
+                }
+                stream::Lifecycle::End(end) => {
+                    // Events may still arrive for closed children, so their trace is
+                    // only forgotten once the root of the trace closes.
+                    if span.id.parent.is_none() {
+                        self.traces.retain(|_, trace| *trace != span.id.trace);
+                    }
+                    if let Some(open) = self.open.remove(&span.id.span) {
+                        self.spans.push(encode::otlp::completed(
+                            open.span,
+                            open.begin,
+                            end,
+                            open.events,
+                        ));
+                    }
+                }
+            },
+            stream::Update::Event(event) => {
+                if let Some(open) = event.parent.and_then(|parent| self.open.get_mut(&parent)) {
+                    open.events.push(event);
+                    return;
+                }
+                let Some(trace) = event
+                    .parent
+                    .and_then(|parent| self.traces.get(&parent).copied())
+                else {
+                    self.discard(1);
+                    return;
+                };
+                self.orphans += 1;
+                let identifier = u64::MAX - self.orphans;
+                self.spans
+                    .push(encode::otlp::orphan(trace, identifier, event));
+            }
+            stream::Update::Snapshot(_) | stream::Update::Metric(_) => {}
+        }
+        self.bound();
+    }
+
+    fn full(&self) -> bool {
+        self.spans.len() >= BATCH
+    }
+
+    fn take(&mut self) -> Vec<wire::Span> {
+        std::mem::take(&mut self.spans)
+    }
+
+    fn restore(&mut self, mut spans: Vec<wire::Span>) {
+        spans.append(&mut self.spans);
+        self.spans = spans;
+        self.bound();
+    }
+
+    fn bound(&mut self) {
+        let excess = self.spans.len().saturating_sub(BACKLOG);
+        if excess > 0 {
+            self.spans.drain(..excess);
+            self.discard(excess);
+        }
+    }
+
+    fn discard(&self, count: usize) {
+        self.dropped.fetch_add(count as u64, Ordering::Relaxed);
+    }
+}
+
+struct Connection {
+    endpoint: String,
+    service: String,
+    client: Option<Client>,
+    delay: Duration,
+    retry: Instant,
+}
+
+impl Connection {
+    async fn deliver(&mut self, batch: &mut Batch) {
+        if Instant::now() < self.retry {
+            return;
+        }
+        let spans = batch.take();
+        if let Err(spans) = flush(&self.endpoint, &self.service, &mut self.client, spans).await {
+            batch.restore(spans);
+            self.retry = Instant::now() + self.delay;
+            self.delay = (self.delay * 2).min(CEILING);
+        } else {
+            self.delay = BACKOFF;
+        }
+    }
+}
+
+async fn export(
+    endpoint: String,
+    service: String,
+    mut batch: Batch,
+    mut receiver: Receiver<stream::Update>,
+    token: CancellationToken,
+) {
+    let mut connection = Connection {
+        endpoint,
+        service,
+        client: None,
+        delay: BACKOFF,
+        retry: Instant::now(),
+    };
+    let mut interval = tokio::time::interval(INTERVAL);
+
+    loop {
+        tokio::select! {
+            () = token.cancelled() => break,
+            _ = interval.tick() => connection.deliver(&mut batch).await,
+            received = receiver.recv() => {
+                let Some(update) = received else { break };
+                batch.push(update);
+                if batch.full() {
+                    connection.deliver(&mut batch).await;
+                }
+            }
+        }
+    }
+
+    while let Ok(update) = receiver.try_recv() {
+        batch.push(update);
+    }
+
+    let spans = batch.take();
+    if let Err(spans) = flush(
+        &connection.endpoint,
+        &connection.service,
+        &mut connection.client,
+        spans,
+    )
+    .await
+    {
+        batch.discard(spans.len());
+    }
+}
+
+async fn flush(
+    endpoint: &str,
+    service: &str,
+    client: &mut Option<Client>,
+    spans: Vec<wire::Span>,
+) -> Result<(), Vec<wire::Span>> {
+    if spans.is_empty() {
+        return Ok(());
+    }
+
+    if client.is_none() {
+        match TraceServiceClient::connect(endpoint.to_string()).await {
+            Ok(connected) => *client = Some(connected),
+            Err(e) => {
+                tracing::warn!("failed to connect to OTLP collector at {endpoint}: {e}");
+                return Err(spans);
+            }
+        }
+    }
+
+    let Some(connected) = client.as_mut() else {
+        return Err(spans);
+    };
+
+    let count = spans.len();
+    if let Err(e) = connected
+        .export(encode::otlp::request(service, spans.clone()))
+        .await
+    {
+        tracing::warn!("failed to export {count} spans to OTLP collector: {e}");
+        *client = None;
+        return Err(spans);
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use channel::Channel;
 use expression::Expression;
 use filterable::Filterable as _;
+use stream::Level;
 
 fn parse(input: String) -> bool {
     ::parse::parse(&input).is_ok()
@@ -12,13 +13,30 @@ fn evaluate(input: String, channels: Vec<String>) -> bool {
         .into_iter()
         .map(|name| Channel { name, weight: 1 })
         .collect();
-    parsed.channels(&channels)
+    parsed.channels(&channels, Level::Info)
 }
 
 fn reject(input: String) -> bool {
     ::parse::parse(&input).is_err()
 }
 
+fn tag(input: String, tags: Vec<String>) -> bool {
+    expression::parse(&input).unwrap().evaluate(&tags)
+}
+
 fn any(input: String) -> bool {
     matches!(::parse::parse(&input).unwrap(), Expression::Any)
 }
+
+fn select(input: String, channels: String, level: String) -> bool {
+    let parsed = ::parse::parse(&input).unwrap();
+    let channels = Channel::parse(&channels).unwrap();
+    let level = match level.as_str() {
+        "trace" => Level::Trace,
+        "debug" => Level::Debug,
+        "warn" => Level::Warn,
+        "error" => Level::Error,
+        _ => Level::Info,
+    };
+    parsed.channels(&channels, level)
+}
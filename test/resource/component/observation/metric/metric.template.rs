@@ -0,0 +1,113 @@
+use layer::Streamer;
+use metric::Kind;
+use stream::{Measure, Metric, Updates};
+
+fn measured<F: FnOnce()>(channel: &str, emit: F) -> Vec<Metric> {
+    let sink = Streamer::assembler(stream::predicate(channel)).open();
+    emit();
+    sink.close().metrics().collect()
+}
+
+fn kind(input: String) -> bool {
+    Kind::parse(&input).is_some()
+}
+
+fn counted(increments: Vec<u64>) -> Option<u64> {
+    let metrics = measured("metric", || {
+        for increment in increments {
+            record::counter!(channels = [metric], passes = increment);
+        }
+    });
+    match metrics.last()?.measure {
+        Measure::Counter(total) => Some(total),
+        _ => None,
+    }
+}
+
+fn ticked(times: usize) -> (usize, Option<u64>) {
+    let metrics = measured("metric", || {
+        for _ in 0..times {
+            record::counter!(channels = [metric], ticks);
+        }
+    });
+    let total = match metrics.last().map(|metric| &metric.measure) {
+        Some(Measure::Counter(total)) => Some(*total),
+        _ => None,
+    };
+    (metrics.len(), total)
+}
+
+fn gauged(values: Vec<i64>) -> Option<i64> {
+    let metrics = measured("metric", || {
+        for value in values {
+            record::gauge!(channels = [metric], nodes = value);
+        }
+    });
+    match metrics.last()?.measure {
+        Measure::Gauge(current) => Some(current),
+        _ => None,
+    }
+}
+
+fn bucketed(values: Vec<u64>) -> Option<(Vec<u64>, u64, u64)> {
+    let metrics = measured("metric", || {
+        for value in values {
+            record::histogram!(channels = [metric], size = value);
+        }
+    });
+    match &metrics.last()?.measure {
+        Measure::Histogram(histogram) => {
+            Some((histogram.counts.clone(), histogram.count, histogram.sum))
+        }
+        _ => None,
+    }
+}
+
+fn separated() -> Vec<(String, Vec<String>, u64)> {
+    let metrics = measured("metric", || {
+        record::counter!(channels = [metric], passes);
+        record::counter!(channels = [metric, core], passes);
+        record::counter!(channels = [metric], passes);
+    });
+    metrics
+        .into_iter()
+        .filter_map(|metric| match metric.measure {
+            Measure::Counter(total) => Some((
+                metric.name,
+                metric.channels.into_iter().map(|c| c.name).collect(),
+                total,
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn filtered(channel: String) -> usize {
+    measured(&channel, || {
+        record::counter!(channels = [metric], passes);
+        record::gauge!(channels = [metric], nodes = 3);
+    })
+    .len()
+}
+
+fn dotted() -> (Vec<(String, u8)>, Vec<String>) {
+    let sink = Streamer::assembler(stream::predicate("metric.pass")).open();
+    record::counter!(channels = [metric.pass: 2], passes);
+    record::event!(channels = [metric.pass, core], step = 1);
+    let updates = sink.close().collect::<Vec<_>>();
+
+    let metrics = updates
+        .clone()
+        .into_iter()
+        .metrics()
+        .flat_map(|metric| metric.channels)
+        .map(|channel| (channel.name, channel.weight))
+        .collect();
+    let events = updates
+        .into_iter()
+        .events()
+        .flat_map(|event| event.channels)
+        .map(|channel| channel.name)
+        .collect();
+    (metrics, events)
+}
@@ -0,0 +1,92 @@
+use layer::Streamer;
+use redaction::Redaction;
+use stream::{Field, Lifecycle, Update, Value};
+
+fn text(name: &str, value: &str) -> Field {
+    Field {
+        name: name.to_string(),
+        value: Value::Text(value.to_string()),
+    }
+}
+
+fn parse(rules: String) -> bool {
+    Redaction::parse(&rules).is_ok()
+}
+
+fn apply(rules: String, name: String, value: String) -> Option<String> {
+    let redaction = Redaction::parse(&rules)
+        .unwrap()
+        .salt(Some("fixture".to_string()));
+    redaction
+        .field(text(&name, &value))
+        .map(|field| field.value.to_string())
+}
+
+fn keyed(first: Option<String>, second: Option<String>) -> (bool, bool) {
+    let hash = |salt: Option<String>| {
+        Redaction::parse("token:hash")
+            .unwrap()
+            .salt(salt)
+            .field(text("token", "abc"))
+            .map(|field| field.value.to_string())
+    };
+    let (first, second) = (hash(first), hash(second));
+    let unkeyed = Some("#e71fa2190541574b".to_string());
+    (first == second, first == unkeyed || second == unkeyed)
+}
+
+fn limit(bound: usize, length: usize) -> String {
+    let redaction = Redaction::default().limit(Some(bound));
+    let field = Field {
+        name: "state".to_string(),
+        value: Value::Serialized(vec![b'0'; length]),
+    };
+    redaction
+        .field(field)
+        .map_or_else(String::new, |field| match field.value {
+            Value::Serialized(bytes) => format!("{} bytes", bytes.len()),
+            other => other.to_string(),
+        })
+}
+
+fn capped(bound: usize, value: String) -> String {
+    Redaction::default()
+        .limit(Some(bound))
+        .field(text("state", &value))
+        .map_or_else(String::new, |field| field.value.to_string())
+}
+
+fn streamed(rules: String) -> Vec<String> {
+    let sink = Streamer::assembler(stream::predicate("redaction"))
+        .redact(
+            Redaction::parse(&rules)
+                .unwrap()
+                .salt(Some("fixture".to_string())),
+        )
+        .open();
+    {
+        let _span = tracing::info_span!(
+            "request",
+            channels = "redaction",
+            password = "secret",
+            user = "ada"
+        )
+        .entered();
+        tracing::info!(channels = "redaction", token = "abc", "handled");
+    }
+
+    let mut fields = sink
+        .close()
+        .flat_map(|update| match update {
+            Update::Span(span) => match span.lifecycle {
+                Lifecycle::Begin(begin) => begin.fields,
+                Lifecycle::End(_) => Vec::new(),
+            },
+            Update::Event(event) => event.fields,
+            Update::Snapshot(_) | Update::Metric(_) => Vec::new(),
+        })
+        .map(|field| format!("{}={}", field.name, field.value))
+        .collect::<Vec<_>>();
+    fields.sort();
+    fields
+}
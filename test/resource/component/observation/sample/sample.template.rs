@@ -0,0 +1,87 @@
+use layer::Streamer;
+use observation::observe::trace;
+use sample::Sample;
+use stream::{Lifecycle, Updates};
+
+#[trace(channels = [sample:3])]
+fn heavy() {
+    light();
+}
+
+#[trace(channels = [sample])]
+fn light() {}
+
+#[trace(channels = [sample])]
+fn outer() {
+    heavy();
+}
+
+fn channels(weights: &[u8]) -> Vec<stream::channel::Channel> {
+    weights
+        .iter()
+        .enumerate()
+        .map(|(index, weight)| stream::channel::Channel {
+            name: format!("channel{index}"),
+            weight: *weight,
+        })
+        .collect()
+}
+
+fn parse(policy: String) -> bool {
+    Sample::parse(&policy).is_ok()
+}
+
+fn weight(minimum: u8, weights: Vec<u8>) -> bool {
+    Sample::Weight(minimum).admit(&channels(&weights), stream::Identifier::root(1, 1))
+}
+
+fn rate(policy: String, count: u64) -> usize {
+    let sample = Sample::parse(&policy).unwrap();
+    let channels = channels(&[1]);
+    (0..count)
+        .filter(|trace| sample.admit(&channels, stream::Identifier::root(*trace, 1)))
+        .count()
+}
+
+fn admitted(policy: String, trace: u64, count: u64) -> Vec<u64> {
+    let sample = Sample::parse(&policy).unwrap();
+    let channels = channels(&[1]);
+    (0..count)
+        .filter(|span| sample.admit(&channels, stream::Identifier::root(trace, *span)))
+        .collect()
+}
+
+fn complete(policy: String, scenario: usize) -> (usize, usize) {
+    let sink = Streamer::assembler(stream::predicate("sample"))
+        .sample(Sample::parse(&policy).unwrap())
+        .open();
+    match scenario {
+        0 => heavy(),
+        1 => light(),
+        2 => outer(),
+        _ => {}
+    }
+    let captured = sink.close().spans().collect::<Vec<_>>();
+    let begins = captured
+        .iter()
+        .filter(|s| matches!(s.lifecycle, Lifecycle::Begin(_)))
+        .count();
+    let ends = captured
+        .iter()
+        .filter(|s| matches!(s.lifecycle, Lifecycle::End(_)))
+        .count();
+    (begins, ends)
+}
+
+fn whole(policy: String, runs: usize) -> (usize, bool) {
+    let mut partial = 0;
+    let mut kept = 0;
+    for _ in 0..runs {
+        match complete(policy.clone(), 2) {
+            (0, 0) => {}
+            (3, 3) => kept += 1,
+            _ => partial += 1,
+        }
+    }
+    (partial, kept > 0 && kept < runs)
+}
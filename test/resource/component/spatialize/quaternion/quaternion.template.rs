@@ -1,3 +1,4 @@
+use matrix::Matrix;
 use quaternion::Quaternion;
 use vector::Vector;
 
@@ -36,3 +37,15 @@ fn rotate(quaternion: [f32; 4], point: [f32; 3]) -> [f32; 3] {
     };
     q.rotate(Vector::from(point)).array()
 }
+
+fn around(axis: Vector, angle: f32) -> Quaternion {
+    Quaternion::axis(axis, angle)
+}
+
+fn turn(quaternion: Quaternion, point: Vector) -> Vector {
+    quaternion.rotate(point)
+}
+
+fn rotation(quaternion: Quaternion) -> Matrix {
+    Matrix::rotation(quaternion)
+}
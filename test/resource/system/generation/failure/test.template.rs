@@ -0,0 +1,74 @@
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+enum Error {
+    #[error("cannot divide {0} by zero")]
+    #[diagnostic(code(failure::zero))]
+    Zero(i32),
+
+    #[error("{0} is not divisible by {1}")]
+    #[diagnostic(code(failure::remainder))]
+    Remainder(i32, i32),
+
+    #[error("no items left to take")]
+    #[diagnostic(code(failure::empty))]
+    Empty,
+}
+
+fn divide(numerator: i32, denominator: i32) -> Result<i32, Error> {
+    if denominator == 0 {
+        return Err(Error::Zero(numerator));
+    }
+    if numerator % denominator != 0 {
+        return Err(Error::Remainder(numerator, denominator));
+    }
+    Ok(numerator / denominator)
+}
+
+fn boxed(numerator: i32, denominator: i32) -> Result<i32, Box<Error>> {
+    divide(numerator, denominator).map_err(Box::new)
+}
+
+fn take(items: &mut Vec<i32>, count: usize) -> Result<(), Error> {
+    for _ in 0..count {
+        items.pop().ok_or(Error::Empty)?;
+    }
+    Ok(())
+}
+
+fn index(items: Vec<i32>, position: usize) -> i32 {
+    items[position]
+}
+
+fn root(value: i32) -> i32 {
+    assert!(value >= 0, "negative input {value}");
+    value.isqrt()
+}
+
+fn reported(comparison: Result<(), Box<function::error::Error>>) -> bool {
+    matches!(
+        comparison.map_err(|error| *error),
+        Err(function::error::Error::Mismatch { .. })
+    )
+}
+
+fn misjudged(value: i32, expected: String) -> bool {
+    let panic = std::panic::catch_unwind(|| root(value))
+        .err()
+        .map(|payload| function::execution::message(payload.as_ref()));
+    reported(function::execution::panicked(
+        &Default::default(),
+        &expected,
+        panic.as_deref(),
+    ))
+}
+
+fn miscoded(numerator: i32, denominator: i32, expected: String) -> bool {
+    use miette::Diagnostic as _;
+    let code = divide(numerator, denominator)
+        .err()
+        .and_then(|error| error.code().map(|code| code.to_string()));
+    reported(function::execution::failed(
+        &Default::default(),
+        &expected,
+        code.as_deref(),
+    ))
+}
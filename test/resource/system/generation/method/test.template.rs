@@ -0,0 +1,160 @@
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vector {
+    #[must_use]
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub fn dot(&self, other: &Vector) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn scale(&mut self, factor: f64) {
+        self.x *= factor;
+        self.y *= factor;
+    }
+
+    #[must_use]
+    pub fn swap(self) -> Self {
+        Self {
+            x: self.y,
+            y: self.x,
+        }
+    }
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Rectangle {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Stack<T> {
+    pub items: Vec<T>,
+}
+
+impl<T: Clone> Stack<T> {
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    #[must_use]
+    pub fn peek(&self) -> Option<T> {
+        self.items.last().cloned()
+    }
+}
+
+#[must_use]
+fn largest<T: PartialOrd + Copy>(items: Vec<T>) -> Option<T> {
+    items.into_iter().fold(None, |largest, item| match largest {
+        Some(largest) if largest >= item => Some(largest),
+        _ => Some(item),
+    })
+}
+
+pub trait Count {
+    fn count(&self) -> usize;
+}
+
+impl<T> Count for Stack<T> {
+    fn count(&self) -> usize {
+        self.items.len()
+    }
+}
+
+#[must_use]
+fn first<const N: usize>(items: [i32; N]) -> Option<i32> {
+    items.first().copied()
+}
+
+pub struct Ring<const N: usize>;
+
+impl<const N: usize> Ring<N> {
+    #[must_use]
+    pub fn capacity() -> usize {
+        N
+    }
+}
+
+mod shapes {
+    pub trait Measure {
+        fn perimeter(&self) -> f64;
+    }
+}
+
+mod geometry {
+    use super::shapes::Measure;
+    use std::ops::Add;
+
+    #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Square {
+        pub side: f64,
+    }
+
+    impl Measure for Square {
+        fn perimeter(&self) -> f64 {
+            4.0 * self.side
+        }
+    }
+
+    impl Add for Square {
+        type Output = Square;
+
+        fn add(self, other: Self) -> Self {
+            Self {
+                side: self.side + other.side,
+            }
+        }
+    }
+
+    pub trait Named {
+        fn name(&self) -> String;
+    }
+
+    pub trait Titled {
+        fn name(&self) -> String;
+    }
+
+    impl Named for Square {
+        fn name(&self) -> String {
+            "square".to_string()
+        }
+    }
+
+    impl Titled for Square {
+        fn name(&self) -> String {
+            format!("Square of side {}", self.side)
+        }
+    }
+
+    impl From<f64> for Square {
+        fn from(side: f64) -> Self {
+            Self { side }
+        }
+    }
+
+    impl From<i32> for Square {
+        fn from(side: i32) -> Self {
+            Self {
+                side: f64::from(side),
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+#[derive(Debug, PartialEq, serde::Deserialize)]
+pub enum Speed {
+    Fast,
+    Slow,
+}
+
+#[must_use]
+fn sort(mut items: Vec<i32>) -> Vec<i32> {
+    items.sort_unstable();
+    items
+}
+
+#[must_use]
+fn insertion(items: Vec<i32>) -> Vec<i32> {
+    let mut sorted = Vec::with_capacity(items.len());
+    for item in items {
+        let position = sorted.partition_point(|other| *other <= item);
+        sorted.insert(position, item);
+    }
+    sorted
+}
+
+#[must_use]
+fn bound(value: i64, low: i64, high: i64) -> i64 {
+    value.clamp(low, high)
+}
+
+#[must_use]
+fn shout(word: String) -> String {
+    word.to_uppercase()
+}
+
+fn push(stack: &mut Vec<i32>, value: i32) {
+    stack.push(value);
+}
+
+#[must_use]
+fn describe(speed: Speed) -> String {
+    match speed {
+        Speed::Fast => "fast".to_string(),
+        Speed::Slow => "slow".to_string(),
+    }
+}
+
+#[must_use]
+fn unique(items: Vec<i32>) -> Vec<i32> {
+    let mut sorted = sort(items);
+    sorted.dedup();
+    sorted
+}
+
+type Sampled = Result<serde_json::Map<String, serde_json::Value>, Box<function::error::Error>>;
+
+fn falsified(
+    specification: &str,
+    sample: impl Fn(&serde_json::Map<String, serde_json::Value>) -> Sampled,
+) -> (serde_json::Value, String) {
+    match function::property::check(specification, sample).map_err(|error| *error) {
+        Err(function::error::Error::Counterexample { case, help, .. }) => {
+            let reason = help.split(" (shrunk").next().unwrap_or_default();
+            (case, reason.to_string())
+        }
+        _ => (serde_json::Value::Null, String::new()),
+    }
+}
+
+#[must_use]
+fn shrunk(violation: String) -> (serde_json::Value, String) {
+    let specification = r#"{
+        "seed": 7,
+        "count": 100,
+        "parameters": {"items": {"vector": {"of": {"range": [-100, 100]}, "length": [0, 16]}}},
+        "relations": ["returns.sorted.length == parameters.items.length"]
+    }"#;
+    falsified(specification, |parameters| {
+        let items: Vec<i32> = function::property::parameter(parameters, "items")?;
+        let sorted = unique(items.clone());
+        let expected = insertion(items.clone());
+        match violation.as_str() {
+            "panicked" => assert!(sorted.len() == items.len(), "dropped duplicates"),
+            "mismatched" if sorted != expected => {
+                let mut actuals = serde_json::Map::new();
+                actuals.insert("()".to_string(), serde_json::json!(sorted));
+                actuals.insert("reference".to_string(), serde_json::json!(expected));
+                return Err(Box::new(function::error::Error::mismatch(
+                    actuals,
+                    "differs from reference insertion".to_string(),
+                )));
+            }
+            _ => {}
+        }
+        let mut returns = serde_json::Map::new();
+        returns.insert("sorted".to_string(), serde_json::json!(sorted));
+        Ok(returns)
+    })
+}
+
+#[must_use]
+fn reached(low: i64, high: i64) -> [i64; 2] {
+    let specification = serde_json::json!({
+        "seed": 13,
+        "count": 500,
+        "parameters": {"value": {"range": [low, high]}}
+    })
+    .to_string();
+    let seen = std::cell::RefCell::new(Vec::new());
+    let _ = function::property::check(&specification, |parameters| {
+        seen.borrow_mut()
+            .push(function::property::parameter::<i64>(parameters, "value")?);
+        Ok(serde_json::Map::new())
+    });
+    let seen = seen.into_inner();
+    [
+        seen.iter().copied().min().unwrap_or_default(),
+        seen.iter().copied().max().unwrap_or_default(),
+    ]
+}
@@ -0,0 +1,86 @@
+use std::collections::{BTreeSet, HashMap};
+
+use difference::Segment;
+use utility::Tolerance;
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[must_use]
+fn sum(values: Vec<f64>) -> f64 {
+    values.iter().sum()
+}
+
+#[must_use]
+fn rotate(point: Point, degrees: f64) -> Point {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    Point {
+        x: point.x * cos - point.y * sin,
+        y: point.x * sin + point.y * cos,
+    }
+}
+
+#[must_use]
+fn next(value: f64) -> f64 {
+    f64::from_bits(value.to_bits() + 1)
+}
+
+fn scale(values: &mut Vec<f64>, factor: f64) {
+    for value in values.iter_mut() {
+        *value *= factor;
+    }
+}
+
+#[must_use]
+fn distinct(items: Vec<i32>) -> Vec<i32> {
+    let mut seen = BTreeSet::new();
+    let mut result = items
+        .into_iter()
+        .filter(|item| seen.insert(*item))
+        .collect::<Vec<i32>>();
+    result.reverse();
+    result
+}
+
+#[must_use]
+fn count(words: Vec<String>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for word in words {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[must_use]
+fn mismatches(expected: Vec<f64>, actual: Vec<f64>, tolerance: Tolerance) -> Vec<usize> {
+    let expected = serde_json::json!(expected);
+    let actual = serde_json::json!(actual);
+    difference::within(&expected, &actual, &tolerance)
+        .into_iter()
+        .filter_map(|(path, _)| match path.as_slice() {
+            [Segment::Index(index)] => Some(*index),
+            _ => None,
+        })
+        .collect()
+}
+
+#[must_use]
+fn infinite(sign: f64) -> bool {
+    let tolerance = Tolerance {
+        absolute: 1.0,
+        ..Tolerance::default()
+    };
+    utility::approximate(&(sign * f64::INFINITY), &(-sign * f64::INFINITY), &tolerance)
+}
+
+#[must_use]
+fn undefined(expected: Option<f64>) -> bool {
+    let tolerance = Tolerance {
+        absolute: 1.0,
+        ..Tolerance::default()
+    };
+    utility::approximate(&Some(f64::NAN), &expected, &tolerance)
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use component::generation::rust::{error::Error, schema::Cases};
+
+const TEMPLATE: &str = r"
+pub struct Stack<T> {
+    pub items: Vec<T>,
+}
+
+impl<T: Clone> Stack<T> {
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+}
+
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+pub trait Titled {
+    fn name(&self) -> String;
+}
+
+pub struct Label;
+
+impl Named for Label {
+    fn name(&self) -> String {
+        String::new()
+    }
+}
+
+impl Titled for Label {
+    fn name(&self) -> String {
+        String::new()
+    }
+}
+
+impl<T> Named for Stack<T> {
+    fn name(&self) -> String {
+        String::new()
+    }
+}
+
+fn first<const N: usize>(items: [i32; N]) -> Option<i32> {
+    items.first().copied()
+}
+";
+
+fn rejected(
+    function: String,
+    types: HashMap<String, String>,
+    parameters: serde_json::Value,
+    returns: serde_json::Value,
+) -> String {
+    let case = serde_json::json!({ "parameters": parameters, "returns": returns });
+    let cases = serde_json::json!({
+        "functions": [{ "function": function, "types": types, "cases": [case] }]
+    });
+    let content = serde_json::to_string_pretty(&cases).unwrap_or_default();
+    let Ok(data) = serde_json::from_value::<Cases>(cases) else {
+        return "malformed".to_string();
+    };
+
+    match rust::generate(TEMPLATE, &data, &content, "cases.json", "test.template.rs") {
+        Ok(_) => "generated".to_string(),
+        Err(error) => match *error {
+            Error::Case { help, .. } => help
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches("Test case issue: ")
+                .to_string(),
+            other => miette::Diagnostic::code(&other)
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+        },
+    }
+}
@@ -0,0 +1,142 @@
+fn span(
+    id: u64,
+    parent: Option<u64>,
+    name: &str,
+    channel: &str,
+    begin: u64,
+    end: u64,
+) -> Vec<stream::Update> {
+    let identifier = stream::Identifier {
+        trace: 1,
+        span: id,
+        parent,
+    };
+    let metadata = stream::Metadata {
+        target: "analysis".to_string(),
+        name: name.to_string(),
+        level: stream::Level::Info,
+    };
+    let channels = vec![stream::channel::Channel {
+        name: channel.to_string(),
+        weight: 1,
+    }];
+    vec![
+        stream::Update::Span(stream::Span {
+            id: identifier,
+            metadata: metadata.clone(),
+            channels: channels.clone(),
+            lifecycle: stream::Lifecycle::Begin(stream::Begin {
+                timestamp: begin,
+                fields: Vec::new(),
+            }),
+        }),
+        stream::Update::Span(stream::Span {
+            id: identifier,
+            metadata,
+            channels,
+            lifecycle: stream::Lifecycle::End(stream::End { timestamp: end }),
+        }),
+    ]
+}
+
+fn event(parent: Option<u64>, channel: Option<&str>, timestamp: u64) -> stream::Update {
+    stream::Update::Event(stream::Event {
+        parent,
+        metadata: stream::Metadata {
+            target: "analysis".to_string(),
+            name: "event".to_string(),
+            level: stream::Level::Info,
+        },
+        channels: channel
+            .map(|name| {
+                vec![stream::channel::Channel {
+                    name: name.to_string(),
+                    weight: 1,
+                }]
+            })
+            .unwrap_or_default(),
+        timestamp,
+        fields: Vec::new(),
+    })
+}
+
+fn nested() -> Vec<stream::Update> {
+    let root = span(1, None, "evaluate", "core", 0, 100);
+    let first = span(2, Some(1), "match", "matching", 10, 30);
+    let second = span(3, Some(1), "rewrite", "core", 40, 90);
+    let leaf = span(4, Some(3), "query", "query", 50, 60);
+    vec![
+        root[0].clone(),
+        first[0].clone(),
+        event(Some(2), None, 20),
+        first[1].clone(),
+        second[0].clone(),
+        leaf[0].clone(),
+        event(Some(4), Some("query"), 55),
+        leaf[1].clone(),
+        second[1].clone(),
+        event(None, Some("core"), 95),
+        root[1].clone(),
+    ]
+}
+
+fn repeated(durations: Vec<u64>) -> Vec<stream::Update> {
+    (0u64..)
+        .zip(durations)
+        .flat_map(|(index, duration)| {
+            let start = index * 1000;
+            span(index + 1, None, "step", "core", start, start + duration)
+        })
+        .collect()
+}
+
+fn statistics(durations: Vec<u64>) -> (usize, u64, u64, u64) {
+    let statistics = analysis::statistics(&analysis::timeline(&repeated(durations)));
+    statistics
+        .first()
+        .map_or((0, 0, 0, 0), |s| (s.count, s.p50, s.p95, s.max))
+}
+
+fn timeline() -> (usize, usize) {
+    let timeline = analysis::timeline(&nested());
+    (timeline.intervals.len(), timeline.roots.len())
+}
+
+fn critical() -> Vec<String> {
+    analysis::critical(&analysis::timeline(&nested()), None)
+        .into_iter()
+        .map(|step| format!("{}:{}", step.name, step.duration))
+        .collect()
+}
+
+fn folded() -> Vec<String> {
+    analysis::folded(&analysis::timeline(&nested()))
+}
+
+fn grep(channels: String) -> Vec<u64> {
+    let expression = parse::parse(&channels).unwrap();
+    analysis::grep(&nested(), &expression)
+        .into_iter()
+        .map(|event| event.timestamp)
+        .collect()
+}
+
+fn streamed(channels: String) -> Vec<u64> {
+    let expression = parse::parse(&channels).unwrap();
+    let path = std::env::temp_dir().join(format!("streamed-{}.record", std::process::id()));
+    record::write(&path, &nested()).unwrap();
+
+    let mut filter = analysis::Filter::new(&expression);
+    record::open(&path)
+        .unwrap()
+        .filter_map(|update| filter.matches(&update.unwrap()).map(|event| event.timestamp))
+        .collect()
+}
+
+fn chrome() -> (usize, usize) {
+    let trace = analysis::chrome(&nested());
+    let entries = trace.as_array().unwrap();
+    let complete = entries.iter().filter(|entry| entry["ph"] == "X").count();
+    let instant = entries.iter().filter(|entry| entry["ph"] == "i").count();
+    (complete, instant)
+}
@@ -0,0 +1,116 @@
+fn metadata(name: &str) -> stream::Metadata {
+    stream::Metadata {
+        target: "dashboard".to_string(),
+        name: name.to_string(),
+        level: stream::Level::Info,
+    }
+}
+
+fn channels(name: &str) -> Vec<stream::channel::Channel> {
+    vec![stream::channel::Channel {
+        name: name.to_string(),
+        weight: 1,
+    }]
+}
+
+fn begin(
+    id: u64,
+    parent: Option<u64>,
+    name: &str,
+    channel: &str,
+    timestamp: u64,
+) -> stream::Update {
+    stream::Update::Span(stream::Span {
+        id: stream::Identifier {
+            trace: 1,
+            span: id,
+            parent,
+        },
+        metadata: metadata(name),
+        channels: channels(channel),
+        lifecycle: stream::Lifecycle::Begin(stream::Begin {
+            timestamp,
+            fields: Vec::new(),
+        }),
+    })
+}
+
+fn end(id: u64, parent: Option<u64>, name: &str, channel: &str, timestamp: u64) -> stream::Update {
+    stream::Update::Span(stream::Span {
+        id: stream::Identifier {
+            trace: 1,
+            span: id,
+            parent,
+        },
+        metadata: metadata(name),
+        channels: channels(channel),
+        lifecycle: stream::Lifecycle::End(stream::End { timestamp }),
+    })
+}
+
+fn event(parent: Option<u64>, channel: Option<&str>, timestamp: u64) -> stream::Update {
+    stream::Update::Event(stream::Event {
+        parent,
+        metadata: metadata("event"),
+        channels: channel.map(channels).unwrap_or_default(),
+        timestamp,
+        fields: Vec::new(),
+    })
+}
+
+fn session() -> interface::Dashboard {
+    let mut dashboard = interface::Dashboard::default();
+    for update in [
+        begin(1, None, "evaluate", "core", 0),
+        begin(2, Some(1), "match", "matching", 10),
+        event(Some(2), None, 20),
+        end(2, Some(1), "match", "matching", 30),
+        begin(3, Some(1), "query", "query", 40),
+        event(Some(3), Some("query"), 45),
+        event(None, Some("core"), 50),
+    ] {
+        let message = serde_json::to_string(&update).unwrap();
+        dashboard.receive(&message);
+    }
+    dashboard
+}
+
+fn receive(message: String) -> bool {
+    interface::Dashboard::default().receive(&message)
+}
+
+fn bars() -> Vec<String> {
+    session()
+        .bars()
+        .into_iter()
+        .map(|bar| format!("{}:{}:{}", bar.name, bar.depth, bar.end.is_some()))
+        .collect()
+}
+
+fn events(filter: String) -> Vec<u64> {
+    session()
+        .events(&filter)
+        .map(|events| events.into_iter().map(|event| event.timestamp).collect())
+        .unwrap_or_default()
+}
+
+fn invalid(filter: String) -> bool {
+    session().events(&filter).is_err()
+}
+
+fn retained(count: u64) -> usize {
+    let mut dashboard = interface::Dashboard::default();
+    for timestamp in 0..count {
+        dashboard.ingest(event(None, Some("core"), timestamp));
+    }
+    dashboard.events("").map_or(0, |events| events.len())
+}
+
+fn summary(state: String) -> Option<(usize, usize, u64)> {
+    let snapshot = stream::Snapshot {
+        timestamp: 0,
+        state: state.into_bytes(),
+        trigger: "manual".to_string(),
+    };
+    interface::summary(&snapshot).map(|summary| (summary.nodes, summary.edges, summary.worlds))
+}
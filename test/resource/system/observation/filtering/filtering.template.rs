@@ -1,3 +1,4 @@
+use filterable::Filterable as _;
 use layer::Streamer;
 use observation::observe::trace;
 use stream::channel::Channel;
@@ -11,8 +12,14 @@ fn beta() {}
 #[trace(channels = [alpha, beta])]
 fn combined() {}
 
+#[trace(channels = [hypergraph.evaluate])]
+fn evaluate() {}
+
+#[trace(channels = [hypergraph.rewrite:2])]
+fn rewrite() {}
+
 fn included(filter: Vec<String>) -> usize {
-    let sink = Streamer::assembler(std::sync::Arc::new(move |channels: &[_]| {
+    let sink = Streamer::assembler(std::sync::Arc::new(move |channels: &[_], _| {
         channels.iter().any(|c| filter.contains(&c.name))
     }))
     .open();
@@ -23,7 +30,7 @@ fn included(filter: Vec<String>) -> usize {
 }
 
 fn excluded(filter: Vec<String>) -> usize {
-    let sink = Streamer::assembler(std::sync::Arc::new(move |channels: &[_]| {
+    let sink = Streamer::assembler(std::sync::Arc::new(move |channels: &[_], _| {
         !channels.iter().any(|c| filter.contains(&c.name))
     }))
     .open();
@@ -34,7 +41,7 @@ fn excluded(filter: Vec<String>) -> usize {
 }
 
 fn weighted(threshold: u8) -> usize {
-    let sink = Streamer::assembler(std::sync::Arc::new(move |channels: &[_]| {
+    let sink = Streamer::assembler(std::sync::Arc::new(move |channels: &[_], _| {
         channels.iter().any(|c| c.weight >= threshold)
     }))
     .open();
@@ -61,3 +68,13 @@ fn serialized(names: Vec<String>, weights: Vec<u8>) -> String {
         .collect();
     Channel::serialize(&channels)
 }
+
+fn selected(expression: String) -> usize {
+    let predicate = ::parse::parse(&expression).unwrap().predicate();
+    let sink = Streamer::assembler(predicate).open();
+    evaluate();
+    rewrite();
+    drop(tracing::info_span!("query", channels = "core").entered());
+    drop(tracing::debug_span!("internal", channels = "core").entered());
+    sink.close().count()
+}
@@ -0,0 +1,87 @@
+use layer::Streamer;
+use miette::Diagnostic;
+use observation::observe::trace;
+use stream::{Lifecycle, Update, Updates};
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+enum Error {
+    #[error("{value} is odd")]
+    #[diagnostic(code(instrument::odd))]
+    Odd { value: usize },
+}
+
+#[trace(channels = [test], fields = [left])]
+fn added(left: usize, right: usize) -> usize {
+    left + right
+}
+
+#[trace(channels = [test], skip = [secret])]
+fn concealed(name: &str, secret: &str) -> usize {
+    name.len() + secret.len()
+}
+
+#[trace(channels = [test], ret)]
+fn doubled(value: usize) -> usize {
+    value * 2
+}
+
+#[trace(channels = [test], ret, err)]
+fn halved(value: usize) -> Result<usize, Error> {
+    if value % 2 == 1 {
+        return Err(Error::Odd { value });
+    }
+    Ok(value / 2)
+}
+
+fn captured<F: FnOnce()>(call: F) -> Vec<Update> {
+    let sink = Streamer::assembler(stream::predicate("test")).open();
+    call();
+    sink.close().collect()
+}
+
+fn arguments(updates: Vec<Update>) -> Vec<(String, String)> {
+    updates
+        .into_iter()
+        .spans()
+        .filter_map(|span| match span.lifecycle {
+            Lifecycle::Begin(begin) => Some(begin.fields),
+            Lifecycle::End(_) => None,
+        })
+        .flatten()
+        .map(|field| (field.name, field.value.to_string()))
+        .collect()
+}
+
+fn fields(updates: Vec<Update>) -> Vec<(String, String)> {
+    updates
+        .into_iter()
+        .events()
+        .flat_map(|event| event.fields)
+        .map(|field| (field.name, field.value.to_string()))
+        .collect()
+}
+
+fn selected(left: usize, right: usize) -> Vec<(String, String)> {
+    arguments(captured(|| {
+        let _ = added(left, right);
+    }))
+}
+
+fn skipped(name: String, secret: String) -> Vec<(String, String)> {
+    arguments(captured(|| {
+        let _ = concealed(&name, &secret);
+    }))
+}
+
+fn returned(value: usize) -> Vec<(String, String)> {
+    fields(captured(|| {
+        let _ = doubled(value);
+    }))
+}
+
+fn failed(value: usize) -> Vec<(String, String)> {
+    fields(captured(|| {
+        let _ = halved(value);
+    }))
+}
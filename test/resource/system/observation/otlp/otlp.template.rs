@@ -0,0 +1,287 @@
+use otlp::opentelemetry::proto::collector::trace::v1 as wire;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+use wire::trace_service_server::{TraceService, TraceServiceServer};
+
+#[derive(Clone, Default)]
+struct Collector {
+    received: Arc<Mutex<Vec<wire::ExportTraceServiceRequest>>>,
+    failures: Arc<AtomicUsize>,
+}
+
+#[tonic::async_trait]
+impl TraceService for Collector {
+    async fn export(
+        &self,
+        request: tonic::Request<wire::ExportTraceServiceRequest>,
+    ) -> Result<tonic::Response<wire::ExportTraceServiceResponse>, tonic::Status> {
+        let failing = self
+            .failures
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| left.checked_sub(1))
+            .is_ok();
+        if failing {
+            return Err(tonic::Status::unavailable("collector unavailable"));
+        }
+        self.received.lock().unwrap().push(request.into_inner());
+        Ok(tonic::Response::new(
+            wire::ExportTraceServiceResponse::default(),
+        ))
+    }
+}
+
+fn metadata(name: &str, level: stream::Level) -> stream::Metadata {
+    stream::Metadata {
+        target: "otlp".to_string(),
+        name: name.to_string(),
+        level,
+    }
+}
+
+fn begin(trace: u64, span: u64, parent: Option<u64>, timestamp: u64) -> stream::Update {
+    stream::Update::Span(stream::Span {
+        id: stream::Identifier {
+            trace,
+            span,
+            parent,
+        },
+        metadata: metadata("span", stream::Level::Info),
+        channels: vec![stream::channel::Channel {
+            name: "core".to_string(),
+            weight: 3,
+        }],
+        lifecycle: stream::Lifecycle::Begin(stream::Begin {
+            timestamp,
+            fields: vec![stream::Field {
+                name: "count".to_string(),
+                value: stream::Value::Unsigned(span),
+            }],
+        }),
+    })
+}
+
+fn end(trace: u64, span: u64, parent: Option<u64>, timestamp: u64) -> stream::Update {
+    stream::Update::Span(stream::Span {
+        id: stream::Identifier {
+            trace,
+            span,
+            parent,
+        },
+        metadata: metadata("span", stream::Level::Info),
+        channels: Vec::new(),
+        lifecycle: stream::Lifecycle::End(stream::End { timestamp }),
+    })
+}
+
+fn event(parent: Option<u64>, level: stream::Level, timestamp: u64) -> stream::Update {
+    stream::Update::Event(stream::Event {
+        parent,
+        metadata: metadata("event", level),
+        channels: Vec::new(),
+        timestamp,
+        fields: vec![stream::Field {
+            name: "message".to_string(),
+            value: stream::Value::Text("observed".to_string()),
+        }],
+    })
+}
+
+fn collect(service: &str, updates: Vec<stream::Update>) -> Vec<wire::ExportTraceServiceRequest> {
+    deliver(service, updates, 0).0
+}
+
+fn deliver(
+    service: &str,
+    updates: Vec<stream::Update>,
+    failures: usize,
+) -> (Vec<wire::ExportTraceServiceRequest>, u64) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let collector = Collector {
+            failures: Arc::new(AtomicUsize::new(failures)),
+            ..Collector::default()
+        };
+        let serving = collector.clone();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(TraceServiceServer::new(serving))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                .await
+        });
+
+        let (sender, receiver) = tokio::sync::mpsc::channel(updates.len().max(1));
+        for update in updates {
+            sender.send(update).await.unwrap();
+        }
+        drop(sender);
+
+        let url = url::Url::parse(&format!("otlp://127.0.0.1:{port}")).unwrap();
+        let dropped = Arc::new(AtomicU64::new(0));
+        exporter::spawn(
+            url,
+            service.to_string(),
+            Arc::clone(&dropped),
+            receiver,
+            CancellationToken::new(),
+        )
+        .unwrap()
+        .await
+        .unwrap();
+
+        let received = collector.received.lock().unwrap().clone();
+        (received, dropped.load(Ordering::Relaxed))
+    })
+}
+
+fn spans(requests: &[wire::ExportTraceServiceRequest]) -> Vec<wire::Span> {
+    requests
+        .iter()
+        .flat_map(|request| &request.resource_spans)
+        .flat_map(|resource| &resource.scope_spans)
+        .flat_map(|scope| scope.spans.clone())
+        .collect()
+}
+
+fn completed(count: u64) -> (usize, usize) {
+    let updates = (1..=count)
+        .flat_map(|index| {
+            [
+                begin(1, index, None, index * 10),
+                event(Some(index), stream::Level::Info, index * 10 + 1),
+                end(1, index, None, index * 10 + 2),
+            ]
+        })
+        .collect();
+
+    let exported = spans(&collect("vantle", updates));
+    let events = exported.iter().map(|span| span.events.len()).sum();
+    (exported.len(), events)
+}
+
+fn hierarchy() -> (bool, bool) {
+    let exported = spans(&collect(
+        "vantle",
+        vec![
+            begin(7, 1, None, 0),
+            begin(7, 2, Some(1), 1),
+            end(7, 2, Some(1), 2),
+            end(7, 1, None, 3),
+        ],
+    ));
+
+    let child = &exported[0];
+    let parent = &exported[1];
+    (
+        child.parent_span_id == parent.span_id,
+        child.trace_id == parent.trace_id && child.trace_id.len() == 16,
+    )
+}
+
+fn attributes() -> Vec<String> {
+    let exported = spans(&collect(
+        "vantle",
+        vec![begin(1, 1, None, 0), end(1, 1, None, 5)],
+    ));
+    let mut keys = exported[0]
+        .attributes
+        .iter()
+        .map(|attribute| attribute.key.clone())
+        .collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+fn timing() -> (u64, u64) {
+    let exported = spans(&collect(
+        "vantle",
+        vec![begin(1, 1, None, 3), end(1, 1, None, 8)],
+    ));
+    (
+        exported[0].start_time_unix_nano,
+        exported[0].end_time_unix_nano,
+    )
+}
+
+fn status(level: String) -> i32 {
+    let level = match level.as_str() {
+        "error" => stream::Level::Error,
+        _ => stream::Level::Info,
+    };
+    let exported = spans(&collect(
+        "vantle",
+        vec![
+            begin(1, 1, None, 0),
+            event(Some(1), level, 1),
+            end(1, 1, None, 2),
+        ],
+    ));
+    exported[0].status.as_ref().map_or(0, |status| status.code)
+}
+
+fn orphan(count: u64) -> (usize, u64) {
+    let updates = (0..count)
+        .map(|index| event(None, stream::Level::Info, index))
+        .collect();
+    let (requests, dropped) = deliver("vantle", updates, 0);
+    (spans(&requests).len(), dropped)
+}
+
+fn late(closed: bool) -> (bool, u64) {
+    let mut updates = vec![
+        begin(7, 1, None, 0),
+        begin(7, 2, Some(1), 1),
+        end(7, 2, Some(1), 2),
+    ];
+    if closed {
+        updates.push(end(7, 1, None, 3));
+        updates.push(event(Some(2), stream::Level::Info, 4));
+    } else {
+        updates.push(event(Some(2), stream::Level::Info, 3));
+        updates.push(end(7, 1, None, 4));
+    }
+    let (requests, dropped) = deliver("vantle", updates, 0);
+    let exported = spans(&requests);
+    let traced = exported
+        .iter()
+        .find(|span| span.events.len() == 1)
+        .is_some_and(|orphan| exported.iter().all(|span| span.trace_id == orphan.trace_id));
+    (traced, dropped)
+}
+
+fn retried(count: u64, failures: usize) -> (usize, u64) {
+    let updates = (1..=count)
+        .flat_map(|index| [begin(1, index, None, index * 10), end(1, index, None, index * 10 + 1)])
+        .collect();
+    let (requests, dropped) = deliver("vantle", updates, failures);
+    (spans(&requests).len(), dropped)
+}
+
+fn snapshot(count: u64) -> usize {
+    let updates = (0..count)
+        .map(|index| {
+            stream::Update::Snapshot(stream::Snapshot {
+                timestamp: index,
+                state: Vec::new(),
+                trigger: "test".to_string(),
+            })
+        })
+        .collect();
+    collect("vantle", updates).len()
+}
+
+fn service(name: String) -> Vec<String> {
+    collect(&name, vec![begin(1, 1, None, 0), end(1, 1, None, 1)])
+        .iter()
+        .flat_map(|request| &request.resource_spans)
+        .filter_map(|resource| resource.resource.as_ref())
+        .flat_map(|resource| &resource.attributes)
+        .filter_map(
+            |attribute| match attribute.value.as_ref()?.value.as_ref()? {
+                wire::any_value::Value::StringValue(value) => Some(value.clone()),
+                _ => None,
+            },
+        )
+        .collect()
+}
@@ -21,3 +21,495 @@ fn default() -> bool {
     let peer = peer::Assembler::default().assemble();
     peer.connections().is_empty()
 }
+
+fn updates(count: u64) -> Vec<stream::Update> {
+    (0..count)
+        .map(|index| {
+            stream::Update::Snapshot(stream::Snapshot {
+                timestamp: index * 1000,
+                state: index.to_le_bytes().to_vec(),
+                trigger: "test".to_string(),
+            })
+        })
+        .collect()
+}
+
+fn located(transport: &str, name: &str) -> Url {
+    let address = match transport {
+        "unix" => {
+            let socket = std::env::temp_dir().join(format!("{}-{name}", std::process::id()));
+            format!("unix://{}", socket.display())
+        }
+        "inproc" => format!("inproc://{name}"),
+        _ => "grpc://127.0.0.1:0".to_string(),
+    };
+    Url::parse(&address).unwrap()
+}
+
+async fn listening(server: &peer::Peer) -> Url {
+    let mut url = server.listening().await;
+    url.set_query(None);
+    url
+}
+
+async fn hosted(url: Url) -> (std::sync::Arc<peer::Peer>, peer::Peer, Url) {
+    let server = std::sync::Arc::new(
+        peer::Assembler::new()
+            .address(url)
+            .directory(std::env::temp_dir())
+            .assemble(),
+    );
+
+    let serving = server.clone();
+    tokio::spawn(async move { serving.serve().await });
+    let url = listening(&server).await;
+
+    let client = peer::Assembler::new().assemble();
+    (server, client, url)
+}
+
+async fn served() -> (std::sync::Arc<peer::Peer>, peer::Peer, Url) {
+    hosted(located("grpc", "served")).await
+}
+
+async fn stored(client: &peer::Peer, url: &Url, count: u64) -> stream::Record {
+    client.store(url.clone(), updates(count)).await.unwrap()
+}
+
+fn store(count: u64) -> (u64, u64) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (_server, client, url) = served().await;
+        let record = stored(&client, &url, count).await;
+        (record.count, record.duration)
+    })
+}
+
+fn replay(count: u64, paced: bool) -> (usize, bool) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (_server, client, url) = served().await;
+        let record = stored(&client, &url, count).await;
+        let duration = std::time::Duration::from_micros(record.duration);
+
+        let start = std::time::Instant::now();
+        let replayed = client.replay(url, record, paced).await.unwrap();
+
+        (replayed.len(), replayed == updates(count) && (!paced || start.elapsed() >= duration))
+    })
+}
+
+fn events() -> Vec<stream::Update> {
+    [
+        ("alpha::parse", stream::Level::Debug),
+        ("alpha::parse", stream::Level::Warn),
+        ("beta::render", stream::Level::Info),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(index, (target, level))| {
+        stream::Update::Event(stream::Event {
+            parent: None,
+            metadata: stream::Metadata {
+                target: target.to_string(),
+                name: format!("event{index}"),
+                level,
+            },
+            channels: Vec::new(),
+            timestamp: index as u64 + 1,
+            fields: Vec::new(),
+        })
+    })
+    .collect()
+}
+
+fn marker() -> stream::Update {
+    stream::Update::Snapshot(stream::Snapshot {
+        timestamp: 0,
+        state: Vec::new(),
+        trigger: "marker".to_string(),
+    })
+}
+
+fn marked(update: &stream::Update) -> bool {
+    matches!(update, stream::Update::Snapshot(snapshot) if snapshot.trigger == "marker")
+}
+
+async fn commanded(
+    url: Url,
+    commands: Vec<stream::Command>,
+) -> (
+    std::sync::Arc<peer::Peer>,
+    peer::Peer,
+    peer::Handle,
+    std::sync::Arc<tokio::sync::Notify>,
+) {
+    let captured = std::sync::Arc::new(tokio::sync::Notify::new());
+    let signal = captured.clone();
+    let trigger: peer::Trigger = std::sync::Arc::new(move || {
+        let signal = signal.clone();
+        Box::new(move |_| {
+            signal.notify_one();
+            Some(marker())
+        })
+    });
+
+    let server = std::sync::Arc::new(
+        peer::Assembler::new()
+            .address(url.clone())
+            .trigger(trigger)
+            .assemble(),
+    );
+    for update in events() {
+        let _ = server.send(update);
+    }
+
+    let serving = server.clone();
+    tokio::spawn(async move { serving.serve().await });
+    let url = listening(&server).await;
+
+    let client = peer::Assembler::new().assemble();
+    let handle = client.source(url).unwrap();
+    for command in commands {
+        client.command(handle, command).unwrap();
+    }
+    client.command(handle, stream::Command::Snapshot).unwrap();
+
+    (server, client, handle, captured)
+}
+
+async fn received(client: &peer::Peer) -> usize {
+    let mut count = 0;
+    while let Some(update) = client.next().await {
+        if marked(&update) {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
+
+async fn ready(client: &peer::Peer) -> usize {
+    let mut count = 0;
+    loop {
+        tokio::select! {
+            biased;
+            Some(_) = client.next() => count += 1,
+            () = std::future::ready(()) => return count,
+        }
+    }
+}
+
+fn filtered(targets: Vec<String>, level: String) -> usize {
+    let level = match level.as_str() {
+        "warn" => stream::Level::Warn,
+        "info" => stream::Level::Info,
+        _ => stream::Level::Trace,
+    };
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let commands = vec![
+            stream::Command::Filter { targets, level },
+            stream::Command::Seek(0),
+        ];
+        let (_server, client, _, _) = commanded(located("grpc", "filtered"), commands).await;
+        received(&client).await
+    })
+}
+
+fn paused() -> (usize, usize) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let commands = vec![stream::Command::Pause, stream::Command::Seek(0)];
+        let (_server, client, handle, captured) =
+            commanded(located("grpc", "paused"), commands).await;
+        captured.notified().await;
+        let held = ready(&client).await;
+
+        client.command(handle, stream::Command::Resume).unwrap();
+        (held, received(&client).await)
+    })
+}
+
+fn transport(input: String) -> String {
+    match peer::transport(&Url::parse(&input).unwrap()) {
+        Ok(transport) => format!("{transport:?}"),
+        Err(_) => "unsupported".to_string(),
+    }
+}
+
+fn carried(transport: String, count: u64) -> (u64, bool) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (_server, client, url) = hosted(located(&transport, "carried.sock")).await;
+        let record = stored(&client, &url, count).await;
+        let stored = record.count;
+
+        let replayed = client.replay(url, record, false).await.unwrap();
+        (stored, replayed == updates(count))
+    })
+}
+
+fn pushed(transport: String, count: u64) -> usize {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (server, client, url) = hosted(located(&transport, "pushed.sock")).await;
+
+        client.sink(url).unwrap();
+        for update in updates(count) {
+            client.send(update).unwrap();
+        }
+        client.send(marker()).unwrap();
+        received(&server).await
+    })
+}
+
+fn streamed(transport: String, targets: Vec<String>) -> usize {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let commands = vec![
+            stream::Command::Filter {
+                targets,
+                level: stream::Level::Trace,
+            },
+            stream::Command::Seek(0),
+        ];
+        let url = located(&transport, "streamed.sock");
+        let (_server, client, _, _) = commanded(url, commands).await;
+        received(&client).await
+    })
+}
+
+fn outcome(result: peer::error::Result<stream::Record>) -> String {
+    match result {
+        Ok(record) => format!("stored {}", record.count),
+        Err(error) => miette::Diagnostic::code(&error)
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn guarded(transport: String, name: String, token: String) -> String {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let mut address = located(&transport, &name);
+        address.query_pairs_mut().append_pair("token", "secret");
+        let (_server, client, mut url) = hosted(address).await;
+
+        if !token.is_empty() {
+            url.query_pairs_mut()
+                .append_pair("token", &token)
+                .append_pair("plaintext", "true");
+        }
+        outcome(client.store(url, updates(3)).await)
+    })
+}
+
+fn joined(result: peer::error::Result<()>) -> String {
+    match result {
+        Ok(()) => "closed".to_string(),
+        Err(error) => miette::Diagnostic::code(&error)
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn connect(client: &peer::Peer, role: &str, url: Url) -> peer::Handle {
+    match role {
+        "sink" => client.sink(url),
+        _ => client.source(url),
+    }
+    .unwrap()
+}
+
+fn refused(transport: String, name: String, role: String) -> String {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let mut address = located(&transport, &name);
+        address.query_pairs_mut().append_pair("token", "secret");
+        let (_server, client, mut url) = hosted(address).await;
+
+        url.query_pairs_mut()
+            .append_pair("token", "wrong")
+            .append_pair("plaintext", "true");
+        let handle = connect(&client, &role, url);
+        joined(client.join(handle).await)
+    })
+}
+
+fn exposed(plaintext: bool) -> String {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let mut address = located("grpc", "exposed");
+        address.query_pairs_mut().append_pair("token", "secret");
+        let (_server, client, mut url) = hosted(address).await;
+
+        url.query_pairs_mut().append_pair("token", "secret");
+        if plaintext {
+            url.query_pairs_mut().append_pair("plaintext", "true");
+        }
+        outcome(client.store(url, updates(3)).await)
+    })
+}
+
+fn inherited(transport: String, name: String) -> String {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let mut address = located(&transport, &name);
+        address.query_pairs_mut().append_pair("token", "secret");
+        let (_server, _, url) = hosted(address).await;
+
+        let client = peer::Assembler::new()
+            .token("secret".to_string())
+            .assemble();
+        outcome(client.store(url, updates(3)).await)
+    })
+}
+
+fn disclosed(transport: String, name: String) -> (bool, bool) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let mut address = located(&transport, &name);
+        address.query_pairs_mut().append_pair("token", "secret");
+        let (_server, client, mut url) = hosted(address).await;
+
+        url.query_pairs_mut()
+            .append_pair("token", "leaked")
+            .append_pair("plaintext", "true");
+        client.sink(url.clone()).unwrap();
+        let listed = client
+            .connections()
+            .iter()
+            .any(|descriptor| descriptor.address.as_str().contains("leaked"));
+
+        let error = client.store(url, updates(1)).await.unwrap_err();
+        (listed, error.to_string().contains("leaked"))
+    })
+}
+
+fn issued(name: &str) -> std::path::PathBuf {
+    let directory = std::env::temp_dir().join(name);
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let mut params = rcgen::CertificateParams::new(Vec::<String>::new()).unwrap();
+    params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    let authority =
+        rcgen::CertifiedIssuer::self_signed(params, rcgen::KeyPair::generate().unwrap()).unwrap();
+    std::fs::write(directory.join("authority.pem"), authority.pem()).unwrap();
+
+    for role in ["server", "client"] {
+        let key = rcgen::KeyPair::generate().unwrap();
+        let certificate = rcgen::CertificateParams::new(vec!["localhost".to_string()])
+            .unwrap()
+            .signed_by(&key, &authority)
+            .unwrap();
+        std::fs::write(directory.join(format!("{role}.pem")), certificate.pem()).unwrap();
+        std::fs::write(directory.join(format!("{role}.key")), key.serialize_pem()).unwrap();
+    }
+    directory
+}
+
+async fn secured(
+    directory: &std::path::Path,
+    mutual: bool,
+) -> (std::sync::Arc<peer::Peer>, Url) {
+    let file = |name: &str| directory.join(name);
+
+    let mut host = peer::Assembler::new()
+        .address(located("grpc", "encrypted"))
+        .directory(std::env::temp_dir())
+        .identity(file("server.pem"), file("server.key"));
+    if mutual {
+        host = host.authority(file("authority.pem"));
+    }
+    let host = std::sync::Arc::new(host.assemble());
+    let serving = host.clone();
+    tokio::spawn(async move { serving.serve().await });
+    let url = listening(&host).await;
+    (host, url)
+}
+
+fn encrypted(server: String, client: String) -> String {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let directory = issued(&format!(
+            "encrypted-{}-{server}-{client}",
+            std::process::id()
+        ));
+        let file = |name: &str| directory.join(name);
+        let (_host, mut url) = secured(&directory, server == "mutual").await;
+
+        let authority = match client.as_str() {
+            "untrusted" => "client.pem",
+            _ => "authority.pem",
+        };
+        if client != "plaintext" {
+            url.query_pairs_mut()
+                .append_pair("authority", &file(authority).display().to_string())
+                .append_pair("domain", "localhost");
+        }
+        if client == "identified" {
+            url.query_pairs_mut()
+                .append_pair("certificate", &file("client.pem").display().to_string())
+                .append_pair("key", &file("client.key").display().to_string());
+        }
+        let peer = peer::Assembler::new().assemble();
+        outcome(peer.store(url, updates(3)).await)
+    })
+}
+
+fn distrusted(role: String) -> String {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let directory = issued(&format!("distrusted-{}-{role}", std::process::id()));
+        let (_host, mut url) = secured(&directory, false).await;
+
+        url.query_pairs_mut()
+            .append_pair("authority", &directory.join("client.pem").display().to_string())
+            .append_pair("domain", "localhost");
+        let client = peer::Assembler::new().assemble();
+        let handle = connect(&client, &role, url);
+        joined(client.join(handle).await)
+    })
+}
+
+fn confined(transport: String, name: String, path: String) -> String {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let directory = std::env::temp_dir().join(format!("{name}.records"));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let server = std::sync::Arc::new(
+            peer::Assembler::new()
+                .address(located(&transport, &name))
+                .directory(directory.clone())
+                .assemble(),
+        );
+        let serving = server.clone();
+        tokio::spawn(async move { serving.serve().await });
+        let url = listening(&server).await;
+
+        let client = peer::Assembler::new().assemble();
+        let record = stored(&client, &url, 3).await;
+
+        let path = match path.as_str() {
+            "stored" => record.path.clone(),
+            "outside" => {
+                let outside = std::env::temp_dir().join(format!("{name}.record"));
+                std::fs::copy(&record.path, &outside).unwrap();
+                format!("../{name}.record")
+            }
+            path => path.to_string(),
+        };
+
+        match client.replay(url, stream::Record { path, ..record }, false).await {
+            Ok(updates) => format!("replayed {}", updates.len()),
+            Err(error) => miette::Diagnostic::code(&error)
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+        }
+    })
+}
+
+fn occupied(name: String) -> (String, bool) {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let url = located("unix", &name);
+        let path = std::path::PathBuf::from(url.path());
+        std::fs::write(&path, "kept").unwrap();
+
+        let server = peer::Assembler::new().address(url).assemble();
+        let code = match server.serve().await {
+            Ok(()) => String::new(),
+            Err(error) => miette::Diagnostic::code(&error)
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+        };
+        (code, std::fs::read_to_string(&path).is_ok_and(|kept| kept == "kept"))
+    })
+}
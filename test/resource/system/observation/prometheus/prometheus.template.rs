@@ -0,0 +1,94 @@
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+fn metric(kind: &str) -> stream::Metric {
+    let measure = match kind {
+        "counter" => stream::Measure::Counter(7),
+        "gauge" => stream::Measure::Gauge(-3),
+        _ => stream::Measure::Histogram(stream::Histogram {
+            bounds: vec![1, 4],
+            counts: vec![1, 2, 1],
+            count: 4,
+            sum: 12,
+        }),
+    };
+    stream::Metric {
+        name: kind.to_string(),
+        channels: vec![stream::channel::Channel {
+            name: "hypergraph".to_string(),
+            weight: 1,
+        }],
+        timestamp: 1,
+        measure,
+    }
+}
+
+fn exposition(kind: String) -> Vec<String> {
+    encode::prometheus::exposition([&metric(&kind)])
+        .lines()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn weighted(weights: Vec<u8>) -> Vec<String> {
+    let metrics = weights
+        .into_iter()
+        .map(|weight| stream::Metric {
+            name: "passes".to_string(),
+            channels: vec![stream::channel::Channel {
+                name: "hypergraph".to_string(),
+                weight,
+            }],
+            timestamp: 1,
+            measure: stream::Measure::Counter(7),
+        })
+        .collect::<Vec<_>>();
+    encode::prometheus::exposition(&metrics)
+        .lines()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn transported(kind: String) -> bool {
+    let update = stream::Update::Metric(metric(&kind));
+    decode::proto::update(encode::proto::update(update.clone())) == Some(update)
+}
+
+fn scraped(port: u16) -> Vec<String> {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let url = Url::parse(&format!("http://127.0.0.1:{port}")).unwrap();
+        let cancellation = CancellationToken::new();
+        let (sender, receiver) = tokio::sync::mpsc::channel(16);
+        let _server = http::Server::new(url, cancellation.clone())
+            .live(receiver)
+            .spawn()
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        for kind in ["counter", "gauge"] {
+            sender
+                .send(stream::Update::Metric(metric(kind)))
+                .await
+                .unwrap();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut connection = tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .unwrap();
+        let request = format!(
+            "GET {} HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n",
+            http::METRICS
+        );
+        connection.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        connection.read_to_string(&mut response).await.unwrap();
+        cancellation.cancel();
+
+        response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.lines().map(ToString::to_string).collect())
+            .unwrap_or_default()
+    })
+}
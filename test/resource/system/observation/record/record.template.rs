@@ -0,0 +1,134 @@
+use assemble::Assemble;
+
+fn updates(count: u64) -> Vec<stream::Update> {
+    (0..count)
+        .map(|index| {
+            stream::Update::Snapshot(stream::Snapshot {
+                timestamp: index * 10,
+                state: index.to_le_bytes().to_vec(),
+                trigger: "test".to_string(),
+            })
+        })
+        .collect()
+}
+
+fn location(name: &str) -> std::path::PathBuf {
+    let directory = std::env::temp_dir().join(format!("record-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    directory.join(name)
+}
+
+fn roundtrip(count: u64) -> (u64, bool) {
+    let path = location(&format!("roundtrip-{count}.record"));
+    let stored = record::write(&path, &updates(count)).unwrap();
+    (stored.count, record::read(&path).unwrap() == updates(count))
+}
+
+fn seek(count: u64, timestamp: u64) -> Vec<u64> {
+    let path = location(&format!("seek-{count}-{timestamp}.record"));
+    let mut writer = record::Assembler::new(&path).interval(4).assemble().unwrap();
+    for update in updates(count) {
+        writer.append(update).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let mut reader = record::open(&path).unwrap();
+    reader.seek(timestamp).unwrap();
+    reader
+        .map(|update| record::timestamp(&update.unwrap()))
+        .collect()
+}
+
+fn truncated(count: u64) -> usize {
+    let path = location(&format!("truncated-{count}.record"));
+    let mut writer = record::Assembler::new(&path).assemble().unwrap();
+    for update in updates(count) {
+        writer.append(update).unwrap();
+    }
+    writer.flush().unwrap();
+    drop(writer);
+
+    record::open(&path).unwrap().count()
+}
+
+fn rotated(path: &std::path::Path, count: u64, bytes: u64) {
+    let mut writer = record::Assembler::new(path).rotation(bytes).assemble().unwrap();
+    for update in updates(count) {
+        writer.append(update).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+fn rotation(count: u64, bytes: u64) -> (usize, bool) {
+    let path = location(&format!("rotation-{count}-{bytes}.record"));
+    rotated(&path, count, bytes);
+
+    let segments = record::segments(&path);
+    (segments.len(), record::read(&path).unwrap() == updates(count))
+}
+
+fn rewritten(first: u64, second: u64, bytes: u64) -> (usize, bool) {
+    let path = location(&format!("rewritten-{first}-{second}-{bytes}.record"));
+    rotated(&path, first, bytes);
+    rotated(&path, second, bytes);
+
+    let segments = record::segments(&path);
+    (segments.len(), record::read(&path).unwrap() == updates(second))
+}
+
+fn spanned(count: u64, bytes: u64, timestamp: u64) -> Vec<u64> {
+    let path = location(&format!("spanned-{count}-{bytes}-{timestamp}.record"));
+    rotated(&path, count, bytes);
+
+    let mut reader = record::open(&path).unwrap();
+    reader.seek(timestamp).unwrap();
+    reader
+        .map(|update| record::timestamp(&update.unwrap()))
+        .collect()
+}
+
+fn oversized(length: u64) -> String {
+    let path = location(&format!("oversized-{length}.record"));
+    let mut contents = b"VREC".to_vec();
+    let mut remaining = length;
+    while remaining >= 0x80 {
+        contents.push(u8::try_from(remaining & 0x7f).unwrap() | 0x80);
+        remaining >>= 7;
+    }
+    contents.push(u8::try_from(remaining).unwrap());
+    std::fs::write(&path, contents).unwrap();
+
+    match record::read(&path) {
+        Ok(updates) => format!("read {}", updates.len()),
+        Err(error) => miette::Diagnostic::code(&error)
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn cut(count: u64, bytes: u64) -> String {
+    let path = location(&format!("cut-{count}-{bytes}.record"));
+    let mut writer = record::Assembler::new(&path).assemble().unwrap();
+    for update in updates(count) {
+        writer.append(update).unwrap();
+    }
+    writer.flush().unwrap();
+    drop(writer);
+
+    let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+    let length = file.metadata().unwrap().len();
+    file.set_len(length - bytes).unwrap();
+
+    match record::read(&path) {
+        Ok(updates) => format!("read {}", updates.len()),
+        Err(error) => miette::Diagnostic::code(&error)
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn legacy(count: u64) -> bool {
+    let path = location(&format!("legacy-{count}.json"));
+    std::fs::write(&path, serde_json::to_vec(&updates(count)).unwrap()).unwrap();
+    record::read(&path).unwrap() == updates(count)
+}
@@ -0,0 +1,268 @@
+use assemble::Assemble;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+struct Relay {
+    sender: mpsc::Sender<stream::Update>,
+    dropped: Arc<AtomicU64>,
+    token: CancellationToken,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl Relay {
+    async fn send(&self, range: std::ops::Range<u64>) {
+        for index in range {
+            self.sender.send(update(index)).await.unwrap();
+        }
+    }
+
+    fn taken(&self) -> bool {
+        self.sender.capacity() == self.sender.max_capacity()
+    }
+
+    async fn finish(self) -> bool {
+        drop(self.sender);
+        tokio::time::timeout(Duration::from_secs(5), self.handle)
+            .await
+            .is_ok()
+    }
+}
+
+async fn until(condition: impl Fn() -> bool) -> bool {
+    let polled = async {
+        while !condition() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), polled)
+        .await
+        .is_ok()
+}
+
+fn update(index: u64) -> stream::Update {
+    stream::Update::Event(stream::Event {
+        parent: None,
+        metadata: stream::Metadata {
+            target: "relay".to_string(),
+            name: format!("update{index}"),
+            level: stream::Level::Info,
+        },
+        channels: Vec::new(),
+        timestamp: index + 1,
+        fields: Vec::new(),
+    })
+}
+
+fn describe(update: stream::Update) -> String {
+    let stream::Update::Event(event) = update else {
+        return String::new();
+    };
+    match event.fields.first() {
+        Some(field) => format!("{}={}", field.name, field.value),
+        None => event.metadata.name,
+    }
+}
+
+fn located(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("relay-{}-{name}", std::process::id()))
+}
+
+fn address(name: &str) -> Url {
+    let socket = located(&format!("{name}.sock"));
+    Url::parse(&format!("unix://{}?mode=push", socket.display())).unwrap()
+}
+
+fn relay(url: &Url, buffer: usize, spill: Option<PathBuf>) -> Relay {
+    let (sender, receiver) = mpsc::channel(64);
+    let token = CancellationToken::new();
+    let dropped = Arc::new(AtomicU64::new(0));
+    let handle = grpc::forward(
+        url.clone(),
+        buffer,
+        spill,
+        Arc::clone(&dropped),
+        receiver,
+        token.clone(),
+    )
+    .unwrap();
+    Relay {
+        sender,
+        dropped,
+        token,
+        handle,
+    }
+}
+
+async fn serve(url: &Url, runtime: &tokio::runtime::Handle) -> Arc<peer::Peer> {
+    let server = Arc::new(peer::Assembler::new().address(url.clone()).assemble());
+    let serving = Arc::clone(&server);
+    runtime.spawn(async move { serving.serve().await });
+    server.listening().await;
+    server
+}
+
+async fn received(server: &peer::Peer, count: usize) -> Vec<String> {
+    let mut updates = Vec::new();
+    while updates.len() < count {
+        match tokio::time::timeout(Duration::from_secs(5), server.next()).await {
+            Ok(Some(update)) => updates.push(describe(update)),
+            _ => break,
+        }
+    }
+    updates
+}
+
+fn restart(before: u64, after: u64) -> (usize, Vec<String>) {
+    let url = address("restart");
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let first = tokio::runtime::Runtime::new().unwrap();
+        let server = serve(&url, first.handle()).await;
+        let relay = relay(&url, 64, None);
+
+        relay.send(0..before).await;
+        let delivered = received(&server, before as usize).await.len();
+
+        tokio::task::spawn_blocking(move || {
+            first.shutdown_timeout(Duration::from_secs(5));
+        })
+        .await
+        .unwrap();
+        relay.send(before..before + after).await;
+
+        let server = serve(&url, &tokio::runtime::Handle::current()).await;
+        let recovered = received(&server, after as usize).await;
+        relay.token.cancel();
+        (delivered, recovered)
+    })
+}
+
+fn buffered(buffer: usize, count: u64) -> Vec<String> {
+    let url = address("buffered");
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let relay = relay(&url, buffer, None);
+        relay.send(0..count).await;
+        let overflow = count.saturating_sub(buffer as u64);
+        assert!(until(|| relay.dropped.load(Ordering::Relaxed) == overflow).await);
+
+        let server = serve(&url, &tokio::runtime::Handle::current()).await;
+        let recovered = received(&server, buffer + 1).await;
+        relay.token.cancel();
+        recovered
+    })
+}
+
+fn spilled(buffer: usize, count: u64) -> (Vec<String>, bool) {
+    let url = address("spilled");
+    let spill = located("spilled.record");
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let relay = relay(&url, buffer, Some(spill.clone()));
+        relay.send(0..count).await;
+        let written = until(|| relay.taken() && spill.exists()).await;
+
+        let server = serve(&url, &tokio::runtime::Handle::current()).await;
+        let recovered = received(&server, count as usize).await;
+        let drained = until(|| !spill.exists()).await;
+        relay.token.cancel();
+        (recovered, written && drained)
+    })
+}
+
+fn leftover(count: u64) -> (Vec<String>, bool) {
+    let url = address("leftover");
+    let spill = located("leftover.record");
+    let mut writer = record::Assembler::new(&spill).assemble().unwrap();
+    for index in 0..count {
+        writer.append(update(index)).unwrap();
+    }
+    writer.finish().unwrap();
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let server = serve(&url, &tokio::runtime::Handle::current()).await;
+        let relay = relay(&url, 64, Some(spill.clone()));
+        relay.send(count..count + 1).await;
+
+        let recovered = received(&server, count as usize + 1).await;
+        let drained = until(|| !spill.exists()).await;
+        relay.token.cancel();
+        (recovered, drained)
+    })
+}
+
+fn delivered(transport: String, count: u64) -> Vec<String> {
+    let url = match transport.as_str() {
+        "unix" => address("delivered"),
+        _ => Url::parse("inproc://relay-delivered?mode=push").unwrap(),
+    };
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let server = serve(&url, &tokio::runtime::Handle::current()).await;
+
+        let relay = relay(&url, 64, None);
+        relay.send(0..count).await;
+        let delivered = received(&server, count as usize).await;
+        relay.token.cancel();
+        delivered
+    })
+}
+
+fn finished(transport: String, count: u64) -> (Vec<String>, bool) {
+    let url = match transport.as_str() {
+        "unix" => address("finished"),
+        _ => Url::parse("inproc://relay-finished?mode=push").unwrap(),
+    };
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let server = serve(&url, &tokio::runtime::Handle::current()).await;
+
+        let relay = relay(&url, 64, None);
+        relay.send(0..count).await;
+        let delivered = received(&server, count as usize).await;
+        (delivered, relay.finish().await)
+    })
+}
+
+fn authenticated(token: String, count: u64) -> Vec<String> {
+    let served = Url::parse("grpc://127.0.0.1:0?token=secret").unwrap();
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let server = serve(&served, &tokio::runtime::Handle::current()).await;
+        let mut url = server.listening().await;
+        url.query_pairs_mut()
+            .clear()
+            .append_pair("mode", "push")
+            .append_pair("token", &token)
+            .append_pair("plaintext", "true");
+
+        let relay = relay(&url, 64, None);
+        relay.send(0..count).await;
+        let delivered = received(&server, count as usize).await;
+        relay.token.cancel();
+        delivered
+    })
+}
+
+fn exposed(plaintext: bool) -> (bool, bool) {
+    let mut url = Url::parse("grpc://127.0.0.1:1?mode=push&token=secret").unwrap();
+    if plaintext {
+        url.query_pairs_mut().append_pair("plaintext", "true");
+    }
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (_sender, receiver) = mpsc::channel(1);
+        let token = CancellationToken::new();
+        let forwarded = grpc::forward(
+            url,
+            1,
+            None,
+            Arc::new(AtomicU64::new(0)),
+            receiver,
+            token.clone(),
+        );
+        token.cancel();
+        match forwarded {
+            Ok(_) => (false, false),
+            Err(error) => (true, format!("{error:?}").contains("secret")),
+        }
+    })
+}
@@ -1,5 +1,6 @@
 pub use error;
 pub use execution;
+pub use property;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -70,6 +71,7 @@ impl Executor {
         tags: &[&str],
         parameters: Value,
         expected: Value,
+        tolerance: Option<Value>,
         function: impl FnOnce() -> Evaluation + Send + 'static,
     ) {
         let index = self.indices.entry(name.to_string()).or_insert(0);
@@ -86,6 +88,7 @@ impl Executor {
                 meta,
                 parameters,
                 expected,
+                tolerance,
                 result,
             }
         }));
@@ -16,6 +16,25 @@ pub enum Error {
         help: String,
     },
 
+    #[error("property does not hold")]
+    #[diagnostic(code(report::counterexample))]
+    Counterexample {
+        case: Value,
+        #[source_code]
+        source_code: NamedSource<String>,
+        #[label("minimal counterexample")]
+        span: SourceSpan,
+        #[help]
+        help: String,
+    },
+
+    #[error("invalid property")]
+    #[diagnostic(code(report::property))]
+    Property {
+        #[help]
+        help: String,
+    },
+
     #[error("{count} test case{plural} failed")]
     #[diagnostic(code(report::collection), url("{output}"))]
     Collection {
@@ -99,6 +118,23 @@ impl Error {
         }
     }
 
+    #[must_use]
+    pub fn counterexample(case: Value, help: String) -> Self {
+        let content = serde_json::to_string_pretty(&case).unwrap_or_default();
+        let span = SourceSpan::new(0.into(), content.len());
+        Self::Counterexample {
+            case,
+            source_code: NamedSource::new("counterexample", content).with_language("json"),
+            span,
+            help,
+        }
+    }
+
+    #[must_use]
+    pub fn property(help: String) -> Self {
+        Self::Property { help }
+    }
+
     #[must_use]
     pub fn serialization(key: &str, cause: serde_json::Error) -> Self {
         let content = format!("key: {key}");
@@ -38,6 +38,7 @@ impl Meta {
 pub enum Outcome {
     Pass,
     Mismatch(Map<String, Value>),
+    Counterexample(Value, String),
     Panic(String),
 }
 
@@ -48,6 +49,7 @@ pub struct Task {
     pub meta: Meta,
     pub parameters: Value,
     pub expected: Value,
+    pub tolerance: Option<Value>,
     pub result: Caught,
 }
 
@@ -55,9 +57,48 @@ pub struct Execution {
     pub meta: Meta,
     pub parameters: Value,
     pub expected: Value,
+    pub tolerance: Option<Value>,
     pub outcome: Outcome,
 }
 
+#[must_use]
+pub fn message(panic: &(dyn std::any::Any + Send)) -> String {
+    panic
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| panic.downcast_ref::<&str>().copied())
+        .unwrap_or("unknown panic")
+        .to_string()
+}
+
+pub fn panicked(
+    actuals: &Map<String, Value>,
+    expected: &str,
+    panic: Option<&str>,
+) -> Result<(), Box<Error>> {
+    if panic.is_some_and(|panic| panic.contains(expected)) {
+        return Ok(());
+    }
+    Err(Box::new(Error::mismatch(
+        actuals.clone(),
+        format!("expected panic containing {expected:?}, got: {panic:?}"),
+    )))
+}
+
+pub fn failed(
+    actuals: &Map<String, Value>,
+    expected: &str,
+    code: Option<&str>,
+) -> Result<(), Box<Error>> {
+    if code == Some(expected) {
+        return Ok(());
+    }
+    Err(Box::new(Error::mismatch(
+        actuals.clone(),
+        format!("expected error code {expected:?}, got: {code:?}"),
+    )))
+}
+
 impl Execution {
     #[must_use]
     pub fn resolve(task: Task) -> Self {
@@ -65,22 +106,16 @@ impl Execution {
             Ok(Ok(_)) => Outcome::Pass,
             Ok(Err(error)) => match *error {
                 Error::Mismatch { actuals, .. } => Outcome::Mismatch(actuals),
+                Error::Counterexample { case, help, .. } => Outcome::Counterexample(case, help),
                 other => Outcome::Panic(format!("{:?}", miette::Report::new(other))),
             },
-            Err(panic) => {
-                let message = panic
-                    .downcast_ref::<String>()
-                    .map(String::as_str)
-                    .or_else(|| panic.downcast_ref::<&str>().copied())
-                    .unwrap_or("unknown panic")
-                    .to_string();
-                Outcome::Panic(message)
-            }
+            Err(panic) => Outcome::Panic(message(panic.as_ref())),
         };
         Self {
             meta: task.meta,
             parameters: task.parameters,
             expected: task.expected,
+            tolerance: task.tolerance,
             outcome,
         }
     }
@@ -103,6 +138,8 @@ struct Group {
 struct Case {
     parameters: Value,
     returns: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tolerance: Option<Value>,
     unexpected: Option<Value>,
 }
 
@@ -129,6 +166,16 @@ pub fn write(
                 ));
                 Some(Value::Object(actuals.clone()))
             }
+            Outcome::Counterexample(ref case, ref help) => {
+                let minimal = serde_json::to_string_pretty(case).unwrap_or_default();
+                failures.push(error::Failure::new(
+                    execution.meta.identifier.clone(),
+                    format!("{help}\n\nminimal case for cases.json:\n{minimal}"),
+                ));
+                let mut map = Map::new();
+                map.insert("counterexample".to_string(), case.clone());
+                Some(Value::Object(map))
+            }
             Outcome::Panic(ref message) => {
                 failures.push(error::Failure::new(
                     execution.meta.identifier.clone(),
@@ -143,6 +190,7 @@ pub fn write(
         let case = Case {
             parameters: execution.parameters,
             returns: execution.expected,
+            tolerance: execution.tolerance,
             unexpected,
         };
 
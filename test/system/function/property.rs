@@ -0,0 +1,571 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use error::Error;
+
+const SHRINKS: usize = 1000;
+const UNBOUNDED: u32 = 8;
+
+#[derive(Deserialize)]
+pub struct Specification {
+    pub seed: u64,
+    pub count: usize,
+    pub parameters: BTreeMap<String, Strategy>,
+    #[serde(default)]
+    pub relations: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Strategy {
+    Range([i64; 2]),
+    Regex(String),
+    Vector {
+        of: Box<Strategy>,
+        length: [usize; 2],
+    },
+    OneOf(Vec<Strategy>),
+    Value(Value),
+}
+
+enum Generator {
+    Range(i64, i64),
+    Pattern(Hir),
+    Vector(Box<Generator>, usize, usize),
+    OneOf(Vec<Generator>),
+    Value(Value),
+}
+
+impl Generator {
+    fn compile(name: &str, strategy: &Strategy) -> Result<Self, Box<Error>> {
+        match strategy {
+            Strategy::Range([low, high]) if low > high => Err(Box::new(Error::property(format!(
+                "range for '{name}' is empty: {low} > {high}"
+            )))),
+            Strategy::Range([low, high]) => Ok(Self::Range(*low, *high)),
+            Strategy::Regex(pattern) => regex_syntax::Parser::new()
+                .parse(pattern)
+                .map(Self::Pattern)
+                .map_err(|cause| {
+                    Box::new(Error::property(format!(
+                        "regex for '{name}' is invalid: {cause}"
+                    )))
+                }),
+            Strategy::Vector {
+                length: [low, high],
+                ..
+            } if low > high => Err(Box::new(Error::property(format!(
+                "vector length for '{name}' is empty: {low} > {high}"
+            )))),
+            Strategy::Vector {
+                of,
+                length: [low, high],
+            } => Ok(Self::Vector(
+                Box::new(Self::compile(name, of)?),
+                *low,
+                *high,
+            )),
+            Strategy::OneOf(options) if options.is_empty() => Err(Box::new(Error::property(
+                format!("oneOf for '{name}' has no options"),
+            ))),
+            Strategy::OneOf(options) => options
+                .iter()
+                .map(|option| Self::compile(name, option))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Self::OneOf),
+            Strategy::Value(value) => Ok(Self::Value(value.clone())),
+        }
+    }
+
+    fn generate(&self, source: &mut Source) -> Value {
+        match self {
+            Self::Range(low, high) => Value::from(integer(source, *low, *high)),
+            Self::Pattern(hir) => {
+                let mut text = String::new();
+                pattern(hir, source, &mut text);
+                Value::String(text)
+            }
+            Self::Vector(of, low, high) => {
+                let length = low + source.index(high - low + 1);
+                Value::Array((0..length).map(|_| of.generate(source)).collect())
+            }
+            Self::OneOf(options) => options[source.index(options.len())].generate(source),
+            Self::Value(value) => value.clone(),
+        }
+    }
+}
+
+fn integer(source: &mut Source, low: i64, high: i64) -> i64 {
+    let (low, high) = (i128::from(low), i128::from(high));
+    let span = u64::try_from(high - low + 1).unwrap_or(u64::MAX);
+    let choice = i128::from(source.draw(span));
+    let near = (-low).min(high);
+    let value = if low >= 0 {
+        low + choice
+    } else if high <= 0 {
+        high - choice
+    } else if choice <= 2 * near {
+        let step = (choice + 1) / 2;
+        if choice % 2 == 1 { step } else { -step }
+    } else if high > -low {
+        choice - near
+    } else {
+        near - choice
+    };
+    i64::try_from(value.clamp(low, high)).expect("value is clamped into an i64 range")
+}
+
+fn pattern(hir: &Hir, source: &mut Source, text: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => text.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(Class::Unicode(class)) => {
+            let sizes = class
+                .ranges()
+                .iter()
+                .map(|range| u64::from(range.end()) - u64::from(range.start()) + 1)
+                .collect::<Vec<_>>();
+            let mut choice = source.draw(sizes.iter().sum());
+            for (range, size) in class.ranges().iter().zip(sizes) {
+                if choice < size {
+                    let point = u32::try_from(u64::from(range.start()) + choice)
+                        .expect("choice lies within a unicode range");
+                    text.push(char::from_u32(point).unwrap_or(range.start()));
+                    break;
+                }
+                choice -= size;
+            }
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            let sizes = class
+                .ranges()
+                .iter()
+                .map(|range| u64::from(range.end()) - u64::from(range.start()) + 1)
+                .collect::<Vec<_>>();
+            let mut choice = source.draw(sizes.iter().sum());
+            for (range, size) in class.ranges().iter().zip(sizes) {
+                if choice < size {
+                    let byte = u8::try_from(u64::from(range.start()) + choice)
+                        .expect("choice lies within a byte range");
+                    text.push(char::from(byte));
+                    break;
+                }
+                choice -= size;
+            }
+        }
+        HirKind::Repetition(repetition) => {
+            let maximum = repetition
+                .max
+                .unwrap_or(repetition.min.saturating_add(UNBOUNDED));
+            let extra = source.draw(u64::from(maximum - repetition.min) + 1);
+            let count = u64::from(repetition.min) + extra;
+            for _ in 0..count {
+                pattern(&repetition.sub, source, text);
+            }
+        }
+        HirKind::Capture(capture) => pattern(&capture.sub, source, text),
+        HirKind::Concat(items) => {
+            for item in items {
+                pattern(item, source, text);
+            }
+        }
+        HirKind::Alternation(items) => pattern(&items[source.index(items.len())], source, text),
+    }
+}
+
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut mixed = self.0;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        mixed ^ (mixed >> 31)
+    }
+}
+
+struct Source {
+    choices: Vec<u64>,
+    position: usize,
+    random: Option<Random>,
+}
+
+impl Source {
+    fn random(seed: u64) -> Self {
+        Self {
+            choices: Vec::new(),
+            position: 0,
+            random: Some(Random(seed)),
+        }
+    }
+
+    fn replay(choices: Vec<u64>) -> Self {
+        Self {
+            choices,
+            position: 0,
+            random: None,
+        }
+    }
+
+    fn draw(&mut self, bound: u64) -> u64 {
+        if bound <= 1 {
+            return 0;
+        }
+        let choice = if let Some(choice) = self.choices.get(self.position) {
+            *choice
+        } else {
+            let choice = self
+                .random
+                .as_mut()
+                .map_or(0, |random| random.next() % bound);
+            self.choices.push(choice);
+            choice
+        };
+        self.position += 1;
+        choice % bound
+    }
+
+    fn index(&mut self, bound: usize) -> usize {
+        let bound = u64::try_from(bound).unwrap_or(u64::MAX);
+        usize::try_from(self.draw(bound)).expect("choice is below a usize bound")
+    }
+
+    fn used(mut self) -> Vec<u64> {
+        self.choices.truncate(self.position);
+        self.choices
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Operator {
+    Equal,
+    Unequal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+enum Operand {
+    Path(Vec<String>),
+    Literal(Value),
+}
+
+struct Relation {
+    text: String,
+    left: Operand,
+    operator: Operator,
+    right: Operand,
+}
+
+impl Relation {
+    fn parse(text: &str) -> Result<Self, Box<Error>> {
+        let operators = [
+            ("==", Operator::Equal),
+            ("!=", Operator::Unequal),
+            ("<=", Operator::LessEqual),
+            (">=", Operator::GreaterEqual),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ];
+        let mut quoted = false;
+        let mut escaped = false;
+        let (position, token, operator) = text
+            .char_indices()
+            .find_map(|(position, character)| {
+                match (quoted, escaped, character) {
+                    (true, true, _) => escaped = false,
+                    (true, false, '\\') => escaped = true,
+                    (_, _, '"') => quoted = !quoted,
+                    (true, _, _) => {}
+                    (false, _, _) => {
+                        return operators
+                            .iter()
+                            .find(|(token, _)| text[position..].starts_with(token))
+                            .map(|(token, operator)| (position, *token, *operator));
+                    }
+                }
+                None
+            })
+            .ok_or_else(|| {
+                Box::new(Error::property(format!(
+                    "relation '{text}' has no comparison operator"
+                )))
+            })?;
+        Ok(Self {
+            text: text.to_string(),
+            left: Operand::parse(text, &text[..position])?,
+            operator,
+            right: Operand::parse(text, &text[position + token.len()..])?,
+        })
+    }
+
+    fn holds(&self, context: &Value) -> Result<bool, Box<Error>> {
+        let left = self.left.resolve(&self.text, context)?;
+        let right = self.right.resolve(&self.text, context)?;
+        let ordering = match (&left, &right) {
+            (Value::Number(left), Value::Number(right)) => {
+                left.as_f64().partial_cmp(&right.as_f64())
+            }
+            (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+            _ if left == right => Some(Ordering::Equal),
+            _ => None,
+        };
+        match (self.operator, ordering) {
+            (Operator::Equal, ordering) => Ok(ordering == Some(Ordering::Equal)),
+            (Operator::Unequal, ordering) => Ok(ordering != Some(Ordering::Equal)),
+            (_, None) => Err(Box::new(Error::property(format!(
+                "relation '{}' cannot order {left} and {right}",
+                self.text
+            )))),
+            (Operator::Less, Some(ordering)) => Ok(ordering.is_lt()),
+            (Operator::LessEqual, Some(ordering)) => Ok(ordering.is_le()),
+            (Operator::Greater, Some(ordering)) => Ok(ordering.is_gt()),
+            (Operator::GreaterEqual, Some(ordering)) => Ok(ordering.is_ge()),
+        }
+    }
+}
+
+impl Operand {
+    fn parse(relation: &str, text: &str) -> Result<Self, Box<Error>> {
+        let text = text.trim();
+        if text.starts_with("parameters") || text.starts_with("returns") {
+            return Ok(Self::Path(text.split('.').map(str::to_string).collect()));
+        }
+        serde_json::from_str(text).map(Self::Literal).map_err(|_| {
+            Box::new(Error::property(format!(
+                "relation '{relation}' has an operand '{text}' that is neither a path nor a JSON literal"
+            )))
+        })
+    }
+
+    fn resolve(&self, relation: &str, context: &Value) -> Result<Value, Box<Error>> {
+        let path = match self {
+            Self::Literal(value) => return Ok(value.clone()),
+            Self::Path(path) => path,
+        };
+        let mut value = context;
+        for segment in path {
+            let next = match value {
+                Value::Object(map) => map.get(segment),
+                Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            };
+            value = match (next, segment.as_str(), value) {
+                (Some(next), _, _) => next,
+                (None, "length", Value::Array(items)) => return Ok(Value::from(items.len())),
+                (None, "length", Value::String(text)) => {
+                    return Ok(Value::from(text.chars().count()));
+                }
+                (None, "length", Value::Object(map)) => return Ok(Value::from(map.len())),
+                (None, _, _) => {
+                    return Err(Box::new(Error::property(format!(
+                        "relation '{relation}' refers to '{}', which does not exist",
+                        path.join(".")
+                    ))));
+                }
+            };
+        }
+        Ok(value.clone())
+    }
+}
+
+enum Violation {
+    Panicked(String),
+    Mismatched(Map<String, Value>, String),
+    Broken(Map<String, Value>, String),
+}
+
+struct Failure {
+    parameters: Map<String, Value>,
+    violation: Violation,
+}
+
+impl Failure {
+    fn counterexample(self, steps: usize) -> Error {
+        let mut case = Map::new();
+        case.insert("tags".to_string(), Value::from(vec!["counterexample"]));
+        case.insert("parameters".to_string(), Value::Object(self.parameters));
+        let reason = match self.violation {
+            Violation::Panicked(message) => format!("panicked: {message}"),
+            Violation::Mismatched(mut returns, help) => {
+                if let Some(reference) = returns.remove("reference") {
+                    returns.insert("()".to_string(), reference);
+                }
+                case.insert("returns".to_string(), Value::Object(returns));
+                help
+            }
+            Violation::Broken(returns, relation) => {
+                format!(
+                    "relation '{relation}' does not hold, got: {}",
+                    Value::Object(returns)
+                )
+            }
+        };
+        Error::counterexample(
+            Value::Object(case),
+            format!("{reason} (shrunk in {steps} steps)"),
+        )
+    }
+}
+
+struct Property {
+    parameters: Vec<(String, Generator)>,
+    relations: Vec<Relation>,
+}
+
+impl Property {
+    fn compile(specification: &Specification) -> Result<Self, Box<Error>> {
+        Ok(Self {
+            parameters: specification
+                .parameters
+                .iter()
+                .map(|(name, strategy)| Ok((name.clone(), Generator::compile(name, strategy)?)))
+                .collect::<Result<Vec<_>, Box<Error>>>()?,
+            relations: specification
+                .relations
+                .iter()
+                .map(|relation| Relation::parse(relation))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+
+    fn generate(&self, mut source: Source) -> (Map<String, Value>, Vec<u64>) {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|(name, generator)| (name.clone(), generator.generate(&mut source)))
+            .collect();
+        (parameters, source.used())
+    }
+
+    fn attempt(
+        &self,
+        parameters: Map<String, Value>,
+        sample: &impl Fn(&Map<String, Value>) -> Result<Map<String, Value>, Box<Error>>,
+    ) -> Result<Option<Failure>, Box<Error>> {
+        let outcome =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sample(&parameters)));
+        let violation = match outcome {
+            Err(panic) => Violation::Panicked(execution::message(panic.as_ref())),
+            Ok(Err(error)) => match *error {
+                Error::Mismatch { actuals, help, .. } => Violation::Mismatched(actuals, help),
+                other => return Err(Box::new(other)),
+            },
+            Ok(Ok(returns)) => {
+                let context = serde_json::json!({ "parameters": parameters, "returns": returns });
+                let mut broken = None;
+                for relation in &self.relations {
+                    if !relation.holds(&context)? {
+                        broken = Some(relation.text.clone());
+                        break;
+                    }
+                }
+                match broken {
+                    Some(relation) => Violation::Broken(returns, relation),
+                    None => return Ok(None),
+                }
+            }
+        };
+        Ok(Some(Failure {
+            parameters,
+            violation,
+        }))
+    }
+
+    fn shrink(
+        &self,
+        mut choices: Vec<u64>,
+        mut failure: Failure,
+        sample: &impl Fn(&Map<String, Value>) -> Result<Map<String, Value>, Box<Error>>,
+    ) -> Result<Error, Box<Error>> {
+        let mut budget = SHRINKS;
+        let mut steps = 0;
+        'search: while budget > 0 {
+            for candidate in candidates(&choices) {
+                if budget == 0 {
+                    break 'search;
+                }
+                budget -= 1;
+                let (parameters, used) = self.generate(Source::replay(candidate));
+                if (used.len(), &used) >= (choices.len(), &choices) {
+                    continue;
+                }
+                if let Some(smaller) = self.attempt(parameters, sample)? {
+                    choices = used;
+                    failure = smaller;
+                    steps += 1;
+                    continue 'search;
+                }
+            }
+            break;
+        }
+        Ok(failure.counterexample(steps))
+    }
+}
+
+fn candidates(choices: &[u64]) -> Vec<Vec<u64>> {
+    let mut result = Vec::new();
+    for size in [8, 4, 2, 1] {
+        for start in 0..choices.len().saturating_sub(size - 1) {
+            let mut candidate = choices.to_vec();
+            candidate.drain(start..start + size);
+            result.push(candidate);
+        }
+    }
+    for (index, choice) in choices.iter().enumerate() {
+        for smaller in [0, choice / 2, choice.saturating_sub(1)] {
+            if smaller < *choice {
+                let mut candidate = choices.to_vec();
+                candidate[index] = smaller;
+                result.push(candidate);
+            }
+        }
+    }
+    result
+}
+
+pub fn parameter<T: DeserializeOwned>(
+    parameters: &Map<String, Value>,
+    name: &str,
+) -> Result<T, Box<Error>> {
+    let value = parameters.get(name).cloned().unwrap_or_default();
+    let json = value.to_string();
+    serde_json::from_value(value).map_err(|failure| {
+        Box::new(Error::Runtime(runtime::Runtime::deserialization(
+            std::any::type_name::<T>(),
+            name,
+            json,
+            failure,
+        )))
+    })
+}
+
+pub fn check(
+    specification: &str,
+    sample: impl Fn(&Map<String, Value>) -> Result<Map<String, Value>, Box<Error>>,
+) -> Result<Map<String, Value>, Box<Error>> {
+    let specification: Specification = serde_json::from_str(specification)
+        .map_err(|cause| Box::new(Error::property(format!("invalid generate block: {cause}"))))?;
+    let property = Property::compile(&specification)?;
+
+    for index in 0..specification.count {
+        let seed = specification
+            .seed
+            .wrapping_add(u64::try_from(index).unwrap_or(u64::MAX));
+        let (parameters, choices) = property.generate(Source::random(seed));
+        if let Some(failure) = property.attempt(parameters, &sample)? {
+            return Err(Box::new(property.shrink(choices, failure, &sample)?));
+        }
+    }
+
+    let mut actuals = Map::new();
+    actuals.insert("cases".to_string(), Value::from(specification.count));
+    Ok(actuals)
+}